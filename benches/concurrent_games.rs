@@ -0,0 +1,135 @@
+//! Measures how many player inputs per second the [`GameController`] handles when many games are played at the same time.
+//! 
+//! Every game is played by its own thread, so the throughput should grow with the amount of games (up to the amount of CPU cores) as long as the games do not wait for each other.
+//! The games are played both without storing them and with the [`FileGameStorage`] the server uses, which writes every updated game to disk.
+//! Run it with `cargo bench --bench concurrent_games`.
+
+use std::{env, fs, sync::{Arc, RwLock}, thread, time::{Duration, Instant}};
+
+use game_core::{game_controller::GameController, game_storage::GameStorage, game_data::{structs::{gamestate::GameState, new_game_info::NewGameInfo, player::Player, player_input::PlayerInput, rule_set::RuleSet, game_settings::GameSettingsChanges}, custom_types::{ErrorData, GameID, PlayerID}, enums::{player_input_type::PlayerInputType, in_game_id::InGameID}}};
+use logging::{logger::LogLevel, threshold_logger::ThresholdLogger};
+use rules::game_rule_checker::GameRuleChecker;
+use storage::file_game_storage::FileGameStorage;
+
+const GAME_AMOUNTS: [usize; 4] = [1, 4, 16, 64];
+const MOVES_PER_GAME: usize = 200;
+const STORAGE_FOLDER_NAME: &str = "concurrent_games_bench";
+
+/// A storage that does not store anything, so that the benchmark only measures the game controller.
+struct NoStorage;
+
+impl GameStorage for NoStorage {
+    fn save_game(&self, _game: &GameState) -> Result<(), ErrorData> {
+        Ok(())
+    }
+
+    fn remove_game(&self, _game_id: GameID) -> Result<(), ErrorData> {
+        Ok(())
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<GameState, ErrorData>>, ErrorData> {
+        Ok(Vec::new())
+    }
+}
+
+fn main() {
+    println!("Without storage:");
+    for game_amount in GAME_AMOUNTS {
+        run(game_amount, Box::new(NoStorage));
+    }
+
+    let storage_folder = env::temp_dir().join(STORAGE_FOLDER_NAME);
+    println!("With file storage in {}:", storage_folder.display());
+    for game_amount in GAME_AMOUNTS {
+        let _ = fs::remove_dir_all(&storage_folder);
+        run(game_amount, Box::new(FileGameStorage::new(storage_folder.clone())));
+    }
+    let _ = fs::remove_dir_all(&storage_folder);
+}
+
+fn run(game_amount: usize, storage: Box<dyn GameStorage + Send + Sync>) {
+    let game_controller = Arc::new(GameController::new(
+        Arc::new(RwLock::new(ThresholdLogger::new(LogLevel::Ignore, LogLevel::Ignore))),
+        Box::new(GameRuleChecker::new()),
+        storage,
+    ));
+    let start = Instant::now();
+    let threads: Vec<_> = (0..game_amount)
+        .map(|_| {
+            let game_controller = game_controller.clone();
+            thread::spawn(move || play_game(&game_controller))
+        })
+        .collect();
+    let handled_inputs: usize = threads
+        .into_iter()
+        .map(|thread| thread.join().expect("A game thread panicked"))
+        .sum();
+    print_result(game_amount, handled_inputs, start.elapsed());
+}
+
+fn print_result(game_amount: usize, handled_inputs: usize, elapsed: Duration) {
+    println!(
+        "{:>3} games: {:>7} inputs in {:>8.2?} ({:>9.0} inputs/s)",
+        game_amount,
+        handled_inputs,
+        elapsed,
+        handled_inputs as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Sets up a game with an orchestrator and one player, and then lets the player move and undo the move. Returns the amount of handled inputs.
+fn play_game(game_controller: &GameController) -> usize {
+    let orchestrator_id = game_controller.generate_player_id().expect("Failed to make player id");
+    let player_id = game_controller.generate_player_id().expect("Failed to make player id");
+    let game = game_controller
+        .create_new_game(NewGameInfo { host: Player::new(orchestrator_id, "Orchestrator".to_string()), name: "Benchmark".to_string(), map_name: None, rule_set: RuleSet::default(), settings: GameSettingsChanges::default() })
+        .expect("Failed to create game");
+    game_controller
+        .join_game(game.id, Player::new(player_id, "Player".to_string()))
+        .expect("Failed to join game");
+
+    let setup_inputs = [
+        input(orchestrator_id, game.id, PlayerInputType::ChangeRole, |i| i.related_role = Some(InGameID::Orchestrator)),
+        input(player_id, game.id, PlayerInputType::ChangeRole, |i| i.related_role = Some(InGameID::PlayerOne)),
+        input(orchestrator_id, game.id, PlayerInputType::AssignSituationCard, |i| i.situation_card_id = Some(1)),
+        input(orchestrator_id, game.id, PlayerInputType::StartGame, |_| ()),
+        input(orchestrator_id, game.id, PlayerInputType::NextTurn, |_| ()),
+    ];
+    for setup_input in setup_inputs {
+        game_controller.handle_player_input(setup_input).expect("Failed to set up game");
+    }
+
+    let mut handled_inputs = 0;
+    for _ in 0..MOVES_PER_GAME {
+        let game = game_controller.get_game_by_id(game.id).expect("Failed to get game");
+        let Some(node_id) = game.legal_nodes.first().copied() else {
+            break;
+        };
+        game_controller
+            .handle_player_input(input(player_id, game.id, PlayerInputType::Movement, |i| i.related_node_id = Some(node_id)))
+            .expect("Failed to move player");
+        game_controller
+            .handle_player_input(input(player_id, game.id, PlayerInputType::UndoAction, |_| ()))
+            .expect("Failed to undo move");
+        handled_inputs += 2;
+    }
+    handled_inputs
+}
+
+fn input(player_id: PlayerID, game_id: GameID, input_type: PlayerInputType, modify: impl FnOnce(&mut PlayerInput)) -> PlayerInput {
+    let mut input = PlayerInput {
+        player_id,
+        game_id,
+        input_type,
+        related_role: None,
+        related_node_id: None,
+        district_modifier: None,
+        situation_card_id: None,
+        edge_modifier: None,
+        related_bool: None,
+        related_path: None,
+        vehicle_id: 0,
+    };
+    modify(&mut input);
+    input
+}
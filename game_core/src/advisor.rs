@@ -0,0 +1,540 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_data::{
+        custom_types::{GameID, NodeID, PlayerID, VehicleID},
+        enums::{district::District, district_modifier_type::DistrictModifierType, game_error::GameError, in_game_id::InGameID, player_input_type::PlayerInputType, restriction_type::RestrictionType},
+        structs::{district_modifier::DistrictModifier, edge_restriction::EdgeRestriction, gamestate::GameState, player::Player, player_input::PlayerInput, situation_card::SituationCard, vehicle::Vehicle},
+    },
+    path_finding,
+    rule_checker::RuleChecker,
+};
+
+/// How many of the best suggestions are given to the orchestrator.
+const MAX_SUGGESTION_COUNT: usize = 5;
+/// How much the movement cost of the traffic in a district counts compared to one move on the routes of the players.
+const TRAFFIC_WEIGHT: i32 = 1;
+/// How much the movement cost of the traffic in a district of the goal counts, on top of the traffic weight.
+const GOAL_TRAFFIC_WEIGHT: i32 = 3;
+/// How much it counts that a player that is not part of the goal goes through a district of the goal, compared to one move on the routes of the players.
+const GOAL_DISTRICT_STEP_WEIGHT: i32 = 2;
+/// How much a move on the route of a player that is part of the goal counts, on top of the move itself.
+const GOAL_ROUTE_WEIGHT: i32 = 2;
+/// How much it counts that a player can not reach their objective at all, compared to one move on the routes of the players.
+const UNREACHABLE_OBJECTIVE_PENALTY: i32 = 20;
+
+/// The Evaluation struct describes how well a game fits the goal of its situation card, based on the cheapest routes the players can take to their objectives. A lower score is better.
+/// 
+/// The goal of a situation card is about some districts and vehicle types, see [`SituationCard::get_goal_districts`] and [`SituationCard::get_goal_vehicle_types`]. A player is part of the goal if the vehicle on their route has one of the vehicle types of the goal, or if their objective is in one of the districts of the goal. The game fits the goal when the players that are part of the goal get to their objectives quickly, while the other players and the traffic are kept out of the districts of the goal.
+/// 
+/// [`SituationCard::get_goal_districts`]: ../game_data/structs/situation_card/struct.SituationCard.html#method.get_goal_districts
+/// [`SituationCard::get_goal_vehicle_types`]: ../game_data/structs/situation_card/struct.SituationCard.html#method.get_goal_vehicle_types
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Evaluation {
+    /// The sum of the movement cost of the traffic in every district.
+    pub traffic_cost: i32,
+    /// The sum of the movement cost of the traffic in the districts of the goal.
+    pub goal_traffic_cost: i32,
+    /// How many steps the players that are not part of the goal take through the districts of the goal on their way to their objectives.
+    pub goal_district_steps: i32,
+    /// The sum of the moves the players that are part of the goal need to reach their next objective.
+    pub goal_route_cost: i32,
+    /// The sum of the moves the players need to reach their next objective.
+    pub total_route_cost: i32,
+    /// The amount of players that can not reach their next objective.
+    pub unreachable_objectives: i32,
+    pub score: i32,
+}
+
+/// The Suggestion struct describes an input the orchestrator could make and how the game would be evaluated after it.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Suggestion {
+    pub input: PlayerInput,
+    pub evaluation: Evaluation,
+    /// How much lower the score is than the score of the game as it is now.
+    pub improvement: i32,
+}
+
+/// The OrchestratorAdvice struct contains the evaluation of the game as it is now and the best suggestions for the orchestrator, the best suggestion first.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OrchestratorAdvice {
+    pub current: Evaluation,
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// The cheapest route of a vehicle to the next objective of its player, with the edges it goes along and the district of every edge.
+#[derive(Clone, Debug)]
+struct VehicleRoute {
+    vehicle_id: VehicleID,
+    moves_spent: i32,
+    steps: Vec<(NodeID, NodeID, District)>,
+}
+
+/// The routes of the vehicles of a player that can carry out the next objective of the player. A vehicle that can not reach the objective has no route.
+#[derive(Clone, Debug)]
+struct PlayerRoutes {
+    player_id: PlayerID,
+    vehicle_routes: Vec<(VehicleID, Option<VehicleRoute>)>,
+}
+
+/// Finds the district modifiers and park & ride placements that would make the game fit the goal of its situation card the best.
+///
+/// Every candidate input is checked with the rule checker and applied to a copy of the game with `apply_input`, and then the copy is evaluated. The routes of the game as it is now are found once, and after a candidate only the routes it can change are found again: the routes of the vehicles that go through the district of a district modifier or have its vehicle type, and the routes that go along the edge of a park & ride placement or belong to a bus. Only the candidates that would improve the game are suggested.
+/// Tolls are not suggested, since they do not change which routes the players can take.
+pub fn advise(
+    game: &GameState,
+    orchestrator_id: PlayerID,
+    rule_checker: &dyn RuleChecker,
+    apply_input: impl Fn(PlayerInput, &mut GameState) -> Result<(), GameError>,
+) -> Result<OrchestratorAdvice, GameError> {
+    if game.situation_card.is_none() {
+        return Err(GameError::Conflict("The game has no situation card to give advice for!".to_string()));
+    }
+    let mut orchestrators_turn = game.clone();
+    orchestrators_turn.current_players_turn = InGameID::Orchestrator;
+
+    let start_of_turn = start_of_turn(game);
+    let current_routes = find_player_routes(&start_of_turn, rule_checker, None);
+    let current = score(&start_of_turn, &current_routes);
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for input in candidate_inputs(game, orchestrator_id) {
+        if rule_checker.is_input_valid(&orchestrators_turn, &input).is_some() {
+            continue;
+        }
+        let mut game_clone = start_of_turn.clone();
+        if apply_input(input.clone(), &mut game_clone).is_err() {
+            continue;
+        }
+        let routes = find_player_routes(&game_clone, rule_checker, Some((&input, &current_routes)));
+        let evaluation = score(&game_clone, &routes);
+        let improvement = current.score - evaluation.score;
+        if improvement > 0 {
+            suggestions.push(Suggestion { input, evaluation, improvement });
+        }
+    }
+    suggestions.sort_by_key(|suggestion| -suggestion.improvement);
+    suggestions.truncate(MAX_SUGGESTION_COUNT);
+    Ok(OrchestratorAdvice { current, suggestions })
+}
+
+/// Evaluates how well the game fits the goal of its situation card. The routes are found as if every player starts their turn now.
+pub fn evaluate(game: &GameState, rule_checker: &dyn RuleChecker) -> Evaluation {
+    let start_of_turn = start_of_turn(game);
+    let routes = find_player_routes(&start_of_turn, rule_checker, None);
+    score(&start_of_turn, &routes)
+}
+
+/// Returns a copy of the game where no district has been entered yet, so that the routes are found as if every player starts their turn now.
+fn start_of_turn(game: &GameState) -> GameState {
+    let mut game_clone = game.clone();
+    game_clone.accessed_districts.clear();
+    game_clone
+}
+
+/// Finds the routes of every player to their next objective. If the routes of the game before the given candidate input are given, the routes the input can not change are used again instead of being searched for.
+fn find_player_routes(game: &GameState, rule_checker: &dyn RuleChecker, previous: Option<(&PlayerInput, &[PlayerRoutes])>) -> Vec<PlayerRoutes> {
+    let mut all_player_routes = Vec::new();
+    for player in game.players.iter().filter(|player| player.in_game_id != InGameID::Orchestrator) {
+        let Some(objective_card) = &player.objective_card else {
+            continue;
+        };
+        // Any of the vehicles can pick up the package, but only the vehicle that carries it can drop it off.
+        let (target, vehicles) = if !objective_card.picked_package_up {
+            (objective_card.pick_up_node_id, player.vehicles.iter().collect::<Vec<_>>())
+        } else if !objective_card.dropped_package_off {
+            (objective_card.drop_off_node_id, player.vehicles.iter().filter(|vehicle| vehicle.has_package).collect())
+        } else {
+            continue;
+        };
+        let previous_player_routes = previous.and_then(|(input, routes)| {
+            routes
+                .iter()
+                .find(|player_routes| player_routes.player_id == player.unique_id)
+                .map(|player_routes| (input, player_routes))
+        });
+        let vehicle_routes = vehicles
+            .into_iter()
+            .filter(|vehicle| vehicle.position_node_id.is_some())
+            .map(|vehicle| {
+                let previous_route = previous_player_routes.and_then(|(input, player_routes)| {
+                    player_routes
+                        .vehicle_routes
+                        .iter()
+                        .find(|(vehicle_id, _)| *vehicle_id == vehicle.id)
+                        .filter(|(_, route)| !can_change_route(game, input, player, vehicle, route.as_ref()))
+                        .map(|(_, route)| route.clone())
+                });
+                let route = match previous_route {
+                    Some(route) => route,
+                    None => find_vehicle_route(game, player, vehicle, target, rule_checker),
+                };
+                (vehicle.id, route)
+            })
+            .collect();
+        all_player_routes.push(PlayerRoutes { player_id: player.unique_id, vehicle_routes });
+    }
+    all_player_routes
+}
+
+/// Finds the cheapest route of the vehicle to the target node, and the district of every edge along it.
+fn find_vehicle_route(game: &GameState, player: &Player, vehicle: &Vehicle, target: NodeID, rule_checker: &dyn RuleChecker) -> Option<VehicleRoute> {
+    let position = vehicle.position_node_id?;
+    if position == target {
+        return Some(VehicleRoute { vehicle_id: vehicle.id, moves_spent: 0, steps: Vec::new() });
+    }
+    let route = path_finding::find_cheapest_route(game, player.unique_id, vehicle.id, target, rule_checker).ok()?;
+    let mut steps = Vec::new();
+    let mut from_node_id: NodeID = position;
+    for node_id in route.path.iter() {
+        let district = game
+            .map
+            .get_neighbour_relationships_of_node_with_id(from_node_id)
+            .and_then(|relationships| relationships.into_iter().find(|relationship| &relationship.to == node_id))
+            .map(|relationship| relationship.neighbourhood)?;
+        steps.push((from_node_id, *node_id, district));
+        from_node_id = *node_id;
+    }
+    Some(VehicleRoute { vehicle_id: vehicle.id, moves_spent: i32::from(route.moves_spent), steps })
+}
+
+/// Checks if the candidate input can change the cheapest route of the vehicle, or make a vehicle without a route able to reach its objective.
+/// 
+/// A new district modifier only changes the cost of entering its district and who can enter it, so it can only change the routes that go through the district and the routes of the vehicles it lets in or gives priority. Park & ride on an edge only takes the edge away from the cars and gives it to the buses. Removing a district modifier can open new routes, so it can change every route.
+fn can_change_route(game: &GameState, input: &PlayerInput, player: &Player, vehicle: &Vehicle, route: Option<&VehicleRoute>) -> bool {
+    let steps = route.map(|route| route.steps.as_slice()).unwrap_or_default();
+    match (&input.input_type, &input.district_modifier, &input.edge_modifier) {
+        (PlayerInputType::ModifyDistrict, Some(modifier), _) if !modifier.delete => {
+            let goes_through_district = steps.iter().any(|(_, _, district)| *district == modifier.district);
+            let applies_to_vehicle = match modifier.vehicle_type {
+                Some(RestrictionType::Destination) => GameState::player_has_objective_in_district(&game.map, player, modifier.district),
+                Some(vehicle_type) => vehicle.has_vehicle_type(vehicle_type),
+                None => true,
+            };
+            goes_through_district || applies_to_vehicle
+        }
+        (PlayerInputType::ModifyEdgeRestrictions, _, Some(edge)) => {
+            vehicle.is_bus
+                || steps.iter().any(|(from, to, _)| (*from, *to) == (edge.node_one, edge.node_two) || (*from, *to) == (edge.node_two, edge.node_one))
+        }
+        _ => true,
+    }
+}
+
+/// Scores the game with the given routes of the players, see [`Evaluation`].
+fn score(game: &GameState, all_player_routes: &[PlayerRoutes]) -> Evaluation {
+    let situation_card = game.situation_card.as_ref();
+    let goal_districts = situation_card.map(SituationCard::get_goal_districts).unwrap_or_default();
+    let goal_vehicle_types = situation_card.map(SituationCard::get_goal_vehicle_types).unwrap_or_default();
+    let costs = situation_card.map(|card| card.costs.clone()).unwrap_or_default();
+    let traffic_cost: i32 = costs.iter().map(|cost| i32::from(cost.traffic.get_movement_cost())).sum();
+    let goal_traffic_cost: i32 = costs
+        .iter()
+        .filter(|cost| goal_districts.contains(&cost.neighbourhood))
+        .map(|cost| i32::from(cost.traffic.get_movement_cost()))
+        .sum();
+
+    let mut goal_district_steps = 0;
+    let mut goal_route_cost = 0;
+    let mut total_route_cost = 0;
+    let mut unreachable_objectives = 0;
+    for player_routes in all_player_routes {
+        let Ok(player) = game.get_player_with_unique_id(player_routes.player_id) else {
+            continue;
+        };
+        let Some(route) = player_routes
+            .vehicle_routes
+            .iter()
+            .filter_map(|(_, route)| route.as_ref())
+            .min_by_key(|route| route.moves_spent)
+        else {
+            unreachable_objectives += 1;
+            continue;
+        };
+        total_route_cost += route.moves_spent;
+        let is_part_of_goal = matches!(player.get_vehicle(route.vehicle_id), Ok(vehicle) if goal_vehicle_types.iter().any(|vehicle_type| vehicle.has_vehicle_type(*vehicle_type)))
+            || goal_districts.iter().any(|district| GameState::player_has_objective_in_district(&game.map, &player, *district));
+        if is_part_of_goal {
+            goal_route_cost += route.moves_spent;
+        } else {
+            goal_district_steps += route.steps.iter().filter(|(_, _, district)| goal_districts.contains(district)).count() as i32;
+        }
+    }
+
+    Evaluation {
+        traffic_cost,
+        goal_traffic_cost,
+        goal_district_steps,
+        goal_route_cost,
+        total_route_cost,
+        unreachable_objectives,
+        score: TRAFFIC_WEIGHT * traffic_cost
+            + GOAL_TRAFFIC_WEIGHT * goal_traffic_cost
+            + GOAL_DISTRICT_STEP_WEIGHT * goal_district_steps
+            + total_route_cost
+            + GOAL_ROUTE_WEIGHT * goal_route_cost
+            + UNREACHABLE_OBJECTIVE_PENALTY * unreachable_objectives,
+    }
+}
+
+/// Creates the inputs the orchestrator could make. The district modifiers are only made for the vehicle types the players have and for destination traffic, and park & ride is only placed on edges next to a parking spot or another park & ride edge.
+fn candidate_inputs(game: &GameState, orchestrator_id: PlayerID) -> Vec<PlayerInput> {
+    let mut inputs = Vec::new();
+    let Some(situation_card) = &game.situation_card else {
+        return inputs;
+    };
+
+    let mut vehicle_types: Vec<RestrictionType> = vec![RestrictionType::Destination];
+    for vehicle in game.players.iter().flat_map(|player| player.vehicles.iter()) {
+        for vehicle_type in vehicle.vehicle_types.iter() {
+            if !vehicle_types.contains(vehicle_type) {
+                vehicle_types.push(*vehicle_type);
+            }
+        }
+    }
+
+    for cost in situation_card.costs.iter() {
+        for modifier in [DistrictModifierType::Access, DistrictModifierType::Priority] {
+            for vehicle_type in vehicle_types.iter() {
+                let mut input = orchestrator_input(orchestrator_id, game.id, PlayerInputType::ModifyDistrict);
+                input.district_modifier = Some(DistrictModifier {
+                    district: cost.neighbourhood,
+                    modifier,
+                    vehicle_type: Some(*vehicle_type),
+                    associated_movement_value: None,
+                    associated_money_value: None,
+                    delete: false,
+                });
+                inputs.push(input);
+            }
+        }
+    }
+
+    for (node_id, relationships) in game.map.edges.iter() {
+        for relationship in relationships.iter() {
+            if node_id > &relationship.to || relationship.restriction.is_some() || !relationship.is_modifiable {
+                continue;
+            }
+            if !is_next_to_park_and_ride(game, *node_id) && !is_next_to_park_and_ride(game, relationship.to) {
+                continue;
+            }
+            let mut input = orchestrator_input(orchestrator_id, game.id, PlayerInputType::ModifyEdgeRestrictions);
+            input.edge_modifier = Some(EdgeRestriction::new(*node_id, relationship.to, RestrictionType::ParkAndRide));
+            inputs.push(input);
+        }
+    }
+    inputs
+}
+
+fn is_next_to_park_and_ride(game: &GameState, node_id: NodeID) -> bool {
+    matches!(game.map.get_node_by_id(node_id), Ok(node) if node.is_parking_spot)
+        || matches!(
+            game.map.get_neighbour_relationships_of_node_with_id(node_id),
+            Some(relationships) if relationships.iter().any(|relationship| relationship.restriction == Some(RestrictionType::ParkAndRide))
+        )
+}
+
+const fn orchestrator_input(player_id: PlayerID, game_id: GameID, input_type: PlayerInputType) -> PlayerInput {
+    PlayerInput {
+        player_id,
+        game_id,
+        input_type,
+        related_role: None,
+        related_node_id: None,
+        district_modifier: None,
+        situation_card_id: None,
+        edge_modifier: None,
+        related_bool: None,
+        related_path: None,
+        vehicle_id: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game_data::{
+            enums::{game_error::InvalidInputReason, traffic::Traffic},
+            structs::{node::Node, rule_info::RuleInfo, rule_violation::RuleViolation},
+        },
+        test_utils::{small_game, AllowAllRuleChecker, ORCHESTRATOR_ID, PLAYER_ID},
+    };
+
+    /// A rule checker that only lets a vehicle into a district with access modifiers if one of them lets it in, like the rule of the server does.
+    struct AccessRuleChecker;
+
+    impl RuleChecker for AccessRuleChecker {
+        fn is_input_valid(&self, game: &GameState, input: &PlayerInput) -> Option<GameError> {
+            if input.input_type != PlayerInputType::Movement {
+                return None;
+            }
+            let player = game.get_player_with_unique_id(input.player_id).ok()?;
+            let vehicle = player.get_vehicle(input.vehicle_id).ok()?;
+            let district = game
+                .map
+                .get_neighbour_relationships_of_node_with_id(vehicle.position_node_id?)?
+                .into_iter()
+                .find(|relationship| Some(relationship.to) == input.related_node_id)?
+                .neighbourhood;
+            let access_modifiers: Vec<&DistrictModifier> = game
+                .district_modifiers
+                .iter()
+                .filter(|modifier| modifier.district == district && modifier.modifier == DistrictModifierType::Access)
+                .collect();
+            let is_let_in = access_modifiers.iter().any(|modifier| match modifier.vehicle_type {
+                Some(RestrictionType::Destination) => GameState::player_has_objective_in_district(&game.map, &player, district),
+                Some(vehicle_type) => vehicle.has_vehicle_type(vehicle_type),
+                None => false,
+            });
+            match access_modifiers.is_empty() || is_let_in {
+                true => None,
+                false => Some(GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, "The vehicle is not let into the district!".to_string())),
+            }
+        }
+
+        fn get_rule_violations(&self, _game: &GameState, _input: &PlayerInput) -> Vec<RuleViolation> {
+            Vec::new()
+        }
+
+        fn get_rules_info(&self) -> Vec<RuleInfo> {
+            Vec::new()
+        }
+    }
+
+    fn apply_orchestrator_input(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
+        match (input.district_modifier, input.edge_modifier) {
+            (Some(district_modifier), _) => game.add_district_modifier(district_modifier),
+            (_, Some(edge_restriction)) => game.add_edge_restriction(&edge_restriction, true),
+            _ => Err(GameError::InvalidInput(InvalidInputReason::MissingData, "The input has no modifier!".to_string())),
+        }
+    }
+
+    /// Returns the small game where the city centre is the goal and has a lot of traffic. The player has to pick up the package at node 0 and their vehicle is at node 3, so the cheapest route goes around the city centre.
+    fn game_with_busy_goal_district() -> GameState {
+        let mut game = small_game();
+        let mut situation_card = game.situation_card.clone().unwrap();
+        for cost in situation_card.costs.iter_mut().filter(|cost| cost.neighbourhood == District::CityCentre) {
+            cost.traffic = Traffic::LevelFour;
+        }
+        situation_card.goal_districts = vec![District::CityCentre];
+        situation_card.objective_cards[0].pick_up_node_id = 0;
+        game.map.update_neighbourhood_cost(&situation_card);
+        game.situation_card = Some(situation_card.clone());
+        game.custom_situation_card = Some(situation_card.clone());
+        game.players[1].objective_card = Some(situation_card.objective_cards[0].clone());
+        game.players[1].vehicles[0].position_node_id = Some(3);
+        game
+    }
+
+    #[test]
+    fn evaluation_counts_the_steps_through_the_goal_of_a_player_outside_it() {
+        let mut game = small_game();
+        let situation_card = game.situation_card.as_mut().unwrap();
+        situation_card.goal_districts = vec![District::CityCentre];
+        let objective_card = game.players[1].objective_card.as_mut().unwrap();
+        objective_card.pick_up_node_id = 0;
+        objective_card.drop_off_node_id = 4;
+        game.players[1].vehicles[0].position_node_id = Some(2);
+
+        let evaluation = evaluate(&game, &AllowAllRuleChecker);
+
+        assert_eq!(evaluation.total_route_cost, 2);
+        assert_eq!(evaluation.goal_district_steps, 1);
+        assert_eq!(evaluation.goal_route_cost, 0);
+        assert_eq!(evaluation.unreachable_objectives, 0);
+        assert_eq!(evaluation.score, 2 + GOAL_DISTRICT_STEP_WEIGHT);
+    }
+
+    #[test]
+    fn a_player_with_a_vehicle_type_of_the_goal_is_part_of_the_goal() {
+        let mut game = small_game();
+        let situation_card = game.situation_card.as_mut().unwrap();
+        situation_card.goal_districts = vec![District::CityCentre];
+        situation_card.goal_vehicle_types = vec![RestrictionType::Emergency];
+        let objective_card = game.players[1].objective_card.as_mut().unwrap();
+        objective_card.pick_up_node_id = 0;
+        objective_card.drop_off_node_id = 4;
+        game.players[1].vehicles[0].position_node_id = Some(2);
+        game.players[1].vehicles[0].vehicle_types = vec![RestrictionType::Emergency];
+
+        let evaluation = evaluate(&game, &AllowAllRuleChecker);
+
+        assert_eq!(evaluation.goal_district_steps, 0);
+        assert_eq!(evaluation.goal_route_cost, 2);
+        assert_eq!(evaluation.score, 2 + GOAL_ROUTE_WEIGHT * 2);
+    }
+
+    #[test]
+    fn an_unreachable_objective_is_penalised() {
+        let mut game = small_game();
+        game.map.nodes.push(Node::new(5, "Island".to_string()));
+        game.players[1].vehicles[0].position_node_id = Some(5);
+
+        let evaluation = evaluate(&game, &AllowAllRuleChecker);
+
+        assert_eq!(evaluation.unreachable_objectives, 1);
+        assert_eq!(evaluation.score, UNREACHABLE_OBJECTIVE_PENALTY);
+    }
+
+    #[test]
+    fn advice_needs_a_situation_card() {
+        let mut game = small_game();
+        game.situation_card = None;
+
+        let result = advise(&game, ORCHESTRATOR_ID, &AllowAllRuleChecker, apply_orchestrator_input);
+
+        assert!(matches!(result, Err(GameError::Conflict(_))));
+    }
+
+    #[test]
+    fn advice_suggests_taking_the_traffic_out_of_the_goal_district() {
+        let game = game_with_busy_goal_district();
+
+        let advice = advise(&game, ORCHESTRATOR_ID, &AccessRuleChecker, apply_orchestrator_input).unwrap();
+
+        let best = &advice.suggestions[0];
+        let district_modifier = best.input.district_modifier.as_ref().unwrap();
+        assert_eq!(district_modifier.district, District::CityCentre);
+        assert_eq!(district_modifier.modifier, DistrictModifierType::Access);
+        assert_eq!(best.evaluation.goal_traffic_cost, 0);
+        assert_eq!(best.improvement, advice.current.score - best.evaluation.score);
+        assert!(advice.suggestions.windows(2).all(|pair| pair[0].improvement >= pair[1].improvement));
+        assert!(advice.suggestions.iter().all(|suggestion| suggestion.improvement > 0));
+    }
+
+    #[test]
+    fn reused_routes_give_the_same_score_as_finding_every_route_again() {
+        let mut game = game_with_busy_goal_district();
+        game.map.nodes[1].is_parking_spot = true;
+        game.players[1].vehicles[0].vehicle_types = vec![RestrictionType::Emergency];
+        let start_of_turn = start_of_turn(&game);
+        let current_routes = find_player_routes(&start_of_turn, &AccessRuleChecker, None);
+
+        let candidates = candidate_inputs(&game, ORCHESTRATOR_ID);
+
+        assert!(candidates.iter().any(|input| input.input_type == PlayerInputType::ModifyEdgeRestrictions));
+        for input in candidates {
+            let mut game_clone = start_of_turn.clone();
+            apply_orchestrator_input(input.clone(), &mut game_clone).unwrap();
+            let routes = find_player_routes(&game_clone, &AccessRuleChecker, Some((&input, &current_routes)));
+            assert_eq!(score(&game_clone, &routes), evaluate(&game_clone, &AccessRuleChecker), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn candidates_are_made_for_the_vehicle_types_of_the_players() {
+        let mut game = small_game();
+        game.players[1].vehicles[0].vehicle_types = vec![RestrictionType::Heavy];
+
+        let candidates = candidate_inputs(&game, PLAYER_ID);
+
+        let vehicle_types: Vec<Option<RestrictionType>> = candidates
+            .iter()
+            .filter_map(|input| input.district_modifier.as_ref())
+            .filter(|modifier| modifier.district == District::CityCentre && modifier.modifier == DistrictModifierType::Access)
+            .map(|modifier| modifier.vehicle_type)
+            .collect();
+        assert_eq!(vehicle_types, vec![Some(RestrictionType::Destination), Some(RestrictionType::Heavy)]);
+        assert!(candidates.iter().all(|input| input.input_type == PlayerInputType::ModifyDistrict && input.player_id == PLAYER_ID));
+    }
+}
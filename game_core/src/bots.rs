@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_data::{
+        custom_types::{GameID, NodeID, PlayerID, VehicleID},
+        enums::player_input_type::PlayerInputType,
+        structs::{gamestate::GameState, neighbour_relationship::NeighbourRelationship, player_input::PlayerInput},
+    },
+    path_finding,
+    rule_checker::RuleChecker,
+};
+
+/// The BotStrategy enum describes how a bot chooses the path it moves along.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BotStrategy {
+    /// Moves along the path with the fewest nodes, without looking at what it costs.
+    Greedy,
+    /// Moves along the path that costs the fewest moves, including the cost of entering new districts.
+    CostAware,
+    /// Prefers the railway and only moves along roads when the railway does not take it closer.
+    Green,
+}
+
+impl BotStrategy {
+    /// The weight the strategy gives the edge when choosing which path to move along.
+    fn edge_weight(&self, relationship: &NeighbourRelationship) -> u32 {
+        match self {
+            Self::Green if relationship.is_connected_through_rail => 1,
+            Self::Green => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// The Bot struct describes a computer controlled player in a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bot {
+    pub player_id: PlayerID,
+    pub game_id: GameID,
+    pub strategy: BotStrategy,
+}
+
+impl Bot {
+    pub const fn new(player_id: PlayerID, game_id: GameID, strategy: BotStrategy) -> Self {
+        Self {
+            player_id,
+            game_id,
+            strategy,
+        }
+    }
+
+    /// Chooses the vehicle the bot should move this turn and the path it should move along to get closer to picking up or dropping off its package. Returns `None` if the bot should not move.
+    ///
+    /// The vehicle that carries the package is moved to the drop off node, and otherwise the vehicle with the fewest nodes to the pick up node is moved there.
+    /// The bot plans its whole path with its strategy and then moves as far along it as the rule checker allows this turn. If the rule checker does not allow any part of the path, the bot falls back to the cheapest path the rules allow.
+    pub fn plan_movement(&self, game: &GameState, rule_checker: &dyn RuleChecker) -> Option<(VehicleID, Vec<NodeID>)> {
+        let player = game.get_player_with_unique_id(self.player_id).ok()?;
+        let objective_card = player.objective_card.clone()?;
+        let (vehicle, target) = if !objective_card.picked_package_up {
+            let target = objective_card.pick_up_node_id;
+            let vehicle = player.vehicles.iter().min_by_key(|vehicle| {
+                vehicle
+                    .position_node_id
+                    .and_then(|position| game.map.shortest_path(position, target, |_| 1))
+                    .map_or(usize::MAX, |path| path.len())
+            })?;
+            (vehicle, target)
+        } else if !objective_card.dropped_package_off {
+            (player.vehicles.iter().find(|vehicle| vehicle.has_package)?, objective_card.drop_off_node_id)
+        } else {
+            return None;
+        };
+        let vehicle_id = vehicle.id;
+        let position = vehicle.position_node_id?;
+        self.plan_vehicle_movement(game, vehicle_id, position, target, rule_checker)
+            .map(|path| (vehicle_id, path))
+    }
+
+    fn plan_vehicle_movement(&self, game: &GameState, vehicle_id: VehicleID, position: NodeID, target: NodeID, rule_checker: &dyn RuleChecker) -> Option<Vec<NodeID>> {
+        // The package is only picked up when a player moves to the node, so a bot that starts on it has to move away and back again.
+        if position == target {
+            return game
+                .map
+                .get_neighbour_relationships_of_node_with_id(position)?
+                .iter()
+                .map(|relationship| vec![relationship.to, position])
+                .find(|path| self.is_path_valid(game, vehicle_id, path, rule_checker));
+        }
+
+        let planned_path = match self.strategy {
+            BotStrategy::CostAware => None,
+            _ => game.map.shortest_path(position, target, |relationship| self.strategy.edge_weight(relationship)),
+        };
+        if let Some(path) = planned_path.and_then(|path| self.longest_valid_part_of_path(game, vehicle_id, &path, rule_checker)) {
+            return Some(path);
+        }
+
+        let cheapest_path = path_finding::find_cheapest_route(game, self.player_id, vehicle_id, target, rule_checker).ok()?.path;
+        self.longest_valid_part_of_path(game, vehicle_id, &cheapest_path, rule_checker)
+    }
+
+    fn longest_valid_part_of_path(&self, game: &GameState, vehicle_id: VehicleID, path: &[NodeID], rule_checker: &dyn RuleChecker) -> Option<Vec<NodeID>> {
+        (1..=path.len())
+            .rev()
+            .map(|length| path[..length].to_vec())
+            .find(|part| self.is_path_valid(game, vehicle_id, part, rule_checker))
+    }
+
+    fn is_path_valid(&self, game: &GameState, vehicle_id: VehicleID, path: &[NodeID], rule_checker: &dyn RuleChecker) -> bool {
+        rule_checker.is_input_valid(game, &self.movement_input(vehicle_id, path.to_vec())).is_none()
+    }
+
+    /// Creates the input for moving the vehicle with the given id of the bot along the given path.
+    pub fn movement_input(&self, vehicle_id: VehicleID, path: Vec<NodeID>) -> PlayerInput {
+        let mut input = self.input(PlayerInputType::Movement);
+        input.vehicle_id = vehicle_id;
+        input.related_path = Some(path);
+        input
+    }
+
+    /// Creates an input of the given type from the bot with none of the optional values set.
+    pub const fn input(&self, input_type: PlayerInputType) -> PlayerInput {
+        PlayerInput {
+            player_id: self.player_id,
+            game_id: self.game_id,
+            input_type,
+            related_role: None,
+            related_node_id: None,
+            district_modifier: None,
+            situation_card_id: None,
+            edge_modifier: None,
+            related_bool: None,
+            related_path: None,
+            vehicle_id: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game_data::{enums::{district::District, game_error::GameError}, structs::{rule_info::RuleInfo, rule_violation::RuleViolation, vehicle::Vehicle}},
+        test_utils::{small_game, AllowAllRuleChecker, PLAYER_ID},
+    };
+
+    /// A rule checker that only allows paths up to the given length.
+    struct MaxPathLengthRuleChecker(usize);
+
+    impl RuleChecker for MaxPathLengthRuleChecker {
+        fn is_input_valid(&self, _game: &GameState, input: &PlayerInput) -> Option<GameError> {
+            match input.related_path.as_ref().map_or(1, Vec::len) <= self.0 {
+                true => None,
+                false => Some(GameError::Conflict("The path is too long!".to_string())),
+            }
+        }
+
+        fn get_rule_violations(&self, _game: &GameState, _input: &PlayerInput) -> Vec<RuleViolation> {
+            Vec::new()
+        }
+
+        fn get_rules_info(&self) -> Vec<RuleInfo> {
+            Vec::new()
+        }
+    }
+
+    /// Returns the small game where the vehicle of the player has picked up the package at node 1, and a road goes straight from node 1 to node 3 through the city centre, which costs 5 moves to enter.
+    fn game_with_package_at_node_one() -> GameState {
+        let mut game = small_game();
+        game.map.edges.get_mut(&1).unwrap().push(NeighbourRelationship::new(3, District::CityCentre, 1, false));
+        game.map.edges.get_mut(&3).unwrap().push(NeighbourRelationship::new(1, District::CityCentre, 1, false));
+        game.map.change_neighbourhood_cost(District::CityCentre, 5);
+        let player = &mut game.players[1];
+        player.vehicles[0].position_node_id = Some(1);
+        player.vehicles[0].has_package = true;
+        if let Some(card) = player.objective_card.as_mut() {
+            card.picked_package_up = true;
+        }
+        game
+    }
+
+    fn planned_path(game: &GameState, strategy: BotStrategy) -> Option<(VehicleID, Vec<NodeID>)> {
+        Bot::new(PLAYER_ID, game.id, strategy).plan_movement(game, &AllowAllRuleChecker)
+    }
+
+    #[test]
+    fn bot_moves_to_the_pick_up_node_first() {
+        assert_eq!(planned_path(&small_game(), BotStrategy::Greedy), Some((0, vec![1])));
+    }
+
+    #[test]
+    fn greedy_bot_takes_the_path_with_the_fewest_nodes() {
+        assert_eq!(planned_path(&game_with_package_at_node_one(), BotStrategy::Greedy), Some((0, vec![3])));
+    }
+
+    #[test]
+    fn cost_aware_bot_takes_the_cheapest_path() {
+        assert_eq!(planned_path(&game_with_package_at_node_one(), BotStrategy::CostAware), Some((0, vec![4, 3])));
+    }
+
+    #[test]
+    fn green_bot_prefers_the_railway() {
+        let mut game = game_with_package_at_node_one();
+        game.map.change_neighbourhood_cost(District::CityCentre, 0);
+        for relationship in game.map.edges.values_mut().flatten().filter(|relationship| relationship.neighbourhood == District::Suburbs) {
+            relationship.is_connected_through_rail = true;
+        }
+
+        assert_eq!(planned_path(&game, BotStrategy::Green), Some((0, vec![4, 3])));
+        assert_eq!(planned_path(&game, BotStrategy::Greedy), Some((0, vec![3])));
+    }
+
+    #[test]
+    fn bot_moves_the_vehicle_with_the_package() {
+        let mut game = game_with_package_at_node_one();
+        let player = &mut game.players[1];
+        player.vehicles[0].has_package = false;
+        player.vehicles.push(Vehicle::new(1, 4, 8, Vec::new()));
+        player.vehicles[1].has_package = true;
+
+        assert_eq!(planned_path(&game, BotStrategy::Greedy), Some((1, vec![3])));
+    }
+
+    #[test]
+    fn bot_only_moves_as_far_as_the_rules_allow() {
+        let game = game_with_package_at_node_one();
+        let bot = Bot::new(PLAYER_ID, game.id, BotStrategy::CostAware);
+
+        assert_eq!(bot.plan_movement(&game, &MaxPathLengthRuleChecker(1)), Some((0, vec![4])));
+        assert_eq!(bot.plan_movement(&game, &MaxPathLengthRuleChecker(0)), None);
+    }
+
+    #[test]
+    fn bot_on_the_pick_up_node_moves_away_and_back() {
+        let mut game = small_game();
+        game.players[1].vehicles[0].position_node_id = Some(1);
+
+        let (_, path) = planned_path(&game, BotStrategy::Greedy).unwrap();
+
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[1], 1);
+    }
+
+    #[test]
+    fn bot_that_has_delivered_its_package_does_not_move() {
+        let mut game = small_game();
+        if let Some(card) = game.players[1].objective_card.as_mut() {
+            card.picked_package_up = true;
+            card.dropped_package_off = true;
+        }
+
+        assert_eq!(planned_path(&game, BotStrategy::Greedy), None);
+    }
+}
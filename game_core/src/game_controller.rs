@@ -1,21 +1,60 @@
 use std::{
     any::type_name,
-    sync::{Arc, RwLock},
-    time::{Instant},
+    collections::HashMap,
+    sync::{atomic::{AtomicU64, Ordering}, Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::{Duration, Instant},
 };
 
 use logging::logger::{LogData, LogLevel, Logger};
+use rand::{distributions::Alphanumeric, Rng};
 
 use crate::{
-    rule_checker::RuleChecker, game_data::{structs::{gamestate::GameState, new_game_info::NewGameInfo, player_input::PlayerInput, player::Player, situation_card_list::SituationCardList}, custom_types::{GameID, PlayerID, NodeID}, enums::{player_input_type::PlayerInputType}, constants::PLAYER_TIMEOUT},
+    advisor::{self, OrchestratorAdvice}, bots::{Bot, BotStrategy}, path_finding, rule_checker::RuleChecker, game_storage::GameStorage, situation_card_list::situation_card_list, game_data::{structs::{gamestate::GameState, new_game_info::NewGameInfo, player_input::PlayerInput, player::Player, situation_card_definition::SituationCardDefinition, game_results::GameResults, action_log::ActionLogEntry, route::Route, rule_violation::RuleViolation, rule_info::RuleInfo, player_credentials::PlayerCredentials, game_settings::GameSettings, spectator::Spectator}, custom_types::{GameID, PlayerID, PlayerToken, NodeID, VehicleID}, enums::{player_input_type::PlayerInputType, game_end_reason::GameEndReason, in_game_id::InGameID, game_error::{GameError, InvalidInputReason}}, constants::{PLAYER_TIMEOUT, DISCONNECT_GRACE_PERIOD}},
 };
 
+/// The amount of characters in a player token.
+const PLAYER_TOKEN_LENGTH: usize = 32;
+
+/// The rule name given to the failures found when handling an input on a copy of the game in [`GameController::validate_player_input`].
+const HANDLE_INPUT_RULE_NAME: &str = "handle_input";
+
+/// A game that can be shared between threads. Every game has its own lock so that handling one game does not block the others.
+pub type SharedGame = Arc<Mutex<GameState>>;
+
+/// A copy of an updated game that has to be stored. It is taken while the game is locked and stored with `store_game` after the lock of the game is released, so that the game is not locked while it's written to the storage.
+#[must_use]
+struct GameToStore {
+    /// Tells which of the copies of the same game is the newest, so that an older copy never replaces a newer one in the storage.
+    version: u64,
+    /// The stored version of the game at the time the copy was taken. A copy of a game that has been removed keeps the stored version of the removed game, so it is never stored over a new game with the same id.
+    stored_version: Arc<Mutex<u64>>,
+    game: GameState,
+}
+
 /// The GameController struct is the game manager and is what should be used to control all of the games on the server. It has all the neccessary functions to create and handle games.
+/// 
+/// All the functions take `&self` so that the game controller can be shared between threads without a lock around it. The games are stored in a map where every game has its own lock, so requests for different games do not wait for each other.
+/// To avoid deadlocks the locks are always taken in this order: first the map of games, then a single game. The unique ids, the player tokens, the reconnect codes, the updated game ids and the bots are only locked for short moments and never while waiting for the other locks.
+/// The games are stored after their lock is released. The stored version of each game has its own lock, so storing a game only waits for other copies of the same game being stored.
 pub struct GameController {
-    pub games: Vec<GameState>,
-    pub unique_ids: Vec<(PlayerID, Instant)>,
+    pub games: RwLock<HashMap<GameID, SharedGame>>,
+    pub unique_ids: Mutex<Vec<(PlayerID, Instant)>>,
+    pub player_tokens: Mutex<HashMap<PlayerID, PlayerToken>>,
+    pub reconnect_codes: Mutex<HashMap<PlayerID, PlayerToken>>,
     pub logger: Arc<RwLock<dyn Logger + Send + Sync>>,
     pub rule_checker: Box<dyn RuleChecker + Send + Sync>,
+    pub storage: Box<dyn GameStorage + Send + Sync>,
+    /// The version of the newest copy of every game that has been stored. A removed game gets the version `u64::MAX`, so that copies taken before it was removed are not stored, and its entry is dropped once it's removed from the storage.
+    stored_game_versions: Mutex<HashMap<GameID, Arc<Mutex<u64>>>>,
+    next_game_version: AtomicU64,
+    pub updated_game_ids: Mutex<Vec<GameID>>,
+    pub bots: Mutex<Vec<Bot>>,
+    /// The game balance values used in new games that are not given their own settings.
+    pub default_game_settings: GameSettings,
+    /// How long a player can go without checking in before they are disconnected.
+    pub player_timeout: Duration,
+    /// How long a disconnected player keeps their seat before their unique id and their player are removed.
+    pub disconnect_grace_period: Duration,
 }
 
 macro_rules! log {
@@ -27,46 +66,198 @@ macro_rules! log {
 }
 
 impl GameController {
-    /// Creates a new game and assigns the host to the game.
+    /// Creates a new game controller with the default game settings, the `PLAYER_TIMEOUT` and the `DISCONNECT_GRACE_PERIOD` defined in [`constants`](../game_data/constants/index.html). Use [`GameController::with_settings`] to use other values.
     pub fn new(
         logger: Arc<RwLock<dyn Logger + Send + Sync>>,
         rule_checker: Box<dyn RuleChecker + Send + Sync>,
+        storage: Box<dyn GameStorage + Send + Sync>,
+    ) -> Self {
+        Self::with_settings(logger, rule_checker, storage, GameSettings::default(), PLAYER_TIMEOUT, DISCONNECT_GRACE_PERIOD)
+    }
+
+    /// Creates a new game controller that gives new games the given game settings, unless they are given their own. Players that have not checked in within the given timeout are disconnected, and are removed when the grace period is over as well.
+    pub fn with_settings(
+        logger: Arc<RwLock<dyn Logger + Send + Sync>>,
+        rule_checker: Box<dyn RuleChecker + Send + Sync>,
+        storage: Box<dyn GameStorage + Send + Sync>,
+        default_game_settings: GameSettings,
+        player_timeout: Duration,
+        disconnect_grace_period: Duration,
     ) -> Self {
         Self {
-            games: Vec::new(),
-            unique_ids: Vec::new(),
+            games: RwLock::new(HashMap::new()),
+            unique_ids: Mutex::new(Vec::new()),
+            player_tokens: Mutex::new(HashMap::new()),
+            reconnect_codes: Mutex::new(HashMap::new()),
             logger,
             rule_checker,
+            storage,
+            stored_game_versions: Mutex::new(HashMap::new()),
+            next_game_version: AtomicU64::new(1),
+            updated_game_ids: Mutex::new(Vec::new()),
+            bots: Mutex::new(Vec::new()),
+            default_game_settings,
+            player_timeout,
+            disconnect_grace_period,
         }
     }
 
+    /// Restores the games stored in the storage of the game controller and returns how many games were restored. The players in the restored games get their unique ids, tokens and reconnect codes back, and have to check in before the player timeout like any other player. The bots of the restored games are created again from the strategies stored with their players. Stored games that can not be loaded are logged and skipped.
+    pub fn restore_stored_games(&self) -> Result<usize, GameError> {
+        log!(self.logger, LogLevel::Debug, "Restoring stored games!");
+        let stored_games = match self.storage.load_games().map_err(GameError::Internal) {
+            Ok(games) => games,
+            Err(e) => {
+                log!(self.logger, LogLevel::Error, format!("Failed to load the stored games because: {}", e).as_str());
+                return Err(e);
+            }
+        };
+        let mut games = self.write_games()?;
+        let mut unique_ids = self.lock_unique_ids()?;
+        let mut player_tokens = self.lock_player_tokens()?;
+        let mut reconnect_codes = self.lock_reconnect_codes()?;
+        let mut restored_games_amount = 0;
+        for stored_game in stored_games {
+            let mut game = match stored_game {
+                Ok(game) => game,
+                Err(e) => {
+                    log!(self.logger, LogLevel::Error, format!("Skipping a stored game that could not be loaded because: {}", e).as_str());
+                    continue;
+                }
+            };
+            if games.contains_key(&game.id) {
+                log!(self.logger, LogLevel::Warning, format!("A game with id {} already exists and the stored game with the same id is therefore not restored!", game.id).as_str());
+                continue;
+            }
+            // The players get a new check in when they are restored, so they are connected until they have not checked in within the player timeout.
+            for player in game.players.iter_mut() {
+                player.is_disconnected = false;
+                if unique_ids.iter().all(|(id, _)| id != &player.unique_id) {
+                    unique_ids.push((player.unique_id, Instant::now()));
+                }
+            }
+            for spectator in game.spectators.iter() {
+                if unique_ids.iter().all(|(id, _)| id != &spectator.unique_id) {
+                    unique_ids.push((spectator.unique_id, Instant::now()));
+                }
+            }
+            let restored_bots = game
+                .players
+                .iter()
+                .filter_map(|player| player.bot_strategy.map(|strategy| Bot::new(player.unique_id, game.id, strategy)));
+            self.lock_bots()?.extend(restored_bots);
+            player_tokens.extend(game.player_tokens.clone());
+            reconnect_codes.extend(game.reconnect_codes.clone());
+            log!(self.logger, LogLevel::Info, format!("Restored game with id: {}", game.id).as_str());
+            games.insert(game.id, Arc::new(Mutex::new(game)));
+            restored_games_amount += 1;
+        }
+        Ok(restored_games_amount)
+    }
+
     /// Gets all the created games on the server.
-    pub fn get_created_games(&mut self) -> Vec<GameState> {
+    pub fn get_created_games(&self) -> Vec<GameState> {
         self.remove_empty_games();
-        self.games.clone()
+        self.get_all_games()
     }
 
     /// Generates a new unique id that a player can use and returns it, but also puts it in the list of unique ids that the controller has.
-    pub fn generate_player_id(&mut self) -> Result<PlayerID, &str> {
+    pub fn generate_player_id(&self) -> Result<PlayerID, GameError> {
         log!(self.logger, LogLevel::Debug, "Generating new player ID");
-        let new_id = match self.generate_unused_unique_id() {
+        let Ok(mut unique_ids) = self.lock_unique_ids() else {
+            return Err(GameError::Internal("Failed to make new ID because the unique ids could not be accessed!".to_string()));
+        };
+        let new_id = match self.generate_unused_unique_id(&unique_ids) {
             Some(i) => i,
             None => {
                 log!(self.logger, LogLevel::Error, "Failed to make new ID!");
-                return Err("Failed to make new ID!")
+                return Err(GameError::Internal("Failed to make new ID!".to_string()))
             },
         };
 
-        self.unique_ids.push((new_id, Instant::now()));
+        unique_ids.push((new_id, Instant::now()));
 
         log!(self.logger, LogLevel::Debug, format!("Made unique ID: {}", new_id).as_str());
         
         Ok(new_id)
     }
 
+    /// Generates a new unique id like [`GameController::generate_player_id`] together with a secret token and a reconnect code that belong to it. The token has to be given with [`GameController::authenticate`] to prove that a request is made by the player with the id, and the reconnect code with [`GameController::reconnect_player`] to get a new token if the token is lost.
+    pub fn generate_player_credentials(&self) -> Result<PlayerCredentials, GameError> {
+        let player_id = self.generate_player_id()?;
+        let token = Self::generate_player_token();
+        let reconnect_code = Self::generate_player_token();
+        self.lock_player_tokens()?.insert(player_id, token.clone());
+        self.lock_reconnect_codes()?.insert(player_id, reconnect_code.clone());
+        Ok(PlayerCredentials { player_id, token, reconnect_code })
+    }
+
+    /// Gives the disconnected or connected player with the given id their seat in the game with the given id back, and a new token that replaces the old one. The reconnect code of the player has to be given, so that a client that lost the token can still reclaim the seat. The old token can not be used after this, so only the newest session of the player can act for them.
+    ///
+    /// Will return an error if the reconnect code does not belong to the player, if the player has been removed because the grace period for disconnected players is over, or if the player is not in the game.
+    pub fn reconnect_player(&self, game_id: GameID, player_id: PlayerID, reconnect_code: Option<&str>) -> Result<PlayerCredentials, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Player with id {} is trying to reconnect to the game with id {}", player_id, game_id).as_str());
+        let reconnect_code = match (reconnect_code, self.lock_reconnect_codes()?.get(&player_id)) {
+            (Some(given_code), Some(reconnect_code)) if given_code == reconnect_code => reconnect_code.clone(),
+            (None, _) => {
+                log!(self.logger, LogLevel::Warning, format!("Player with id {} tried to reconnect without a reconnect code!", player_id).as_str());
+                return Err(GameError::Unauthorized("The request does not have a reconnect code!".to_string()));
+            }
+            _ => {
+                log!(self.logger, LogLevel::Warning, format!("Player with id {} tried to reconnect with a reconnect code that does not belong to the player!", player_id).as_str());
+                return Err(GameError::Unauthorized("The reconnect code does not belong to the player!".to_string()));
+            }
+        };
+        if !self.is_player_id_registered(player_id) {
+            log!(self.logger, LogLevel::Warning, format!("Player with id {} has been removed and can not reconnect", player_id).as_str());
+            return Err(GameError::NotFound(format!("Player with id {} does not exist, the seat of the player can therefore not be reclaimed!", player_id)));
+        }
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let token = Self::generate_player_token();
+        let game_to_store = {
+            let mut game = Self::lock_game(&shared_game)?;
+            if !game.can_be_followed_by(player_id) {
+                return Err(GameError::Forbidden(format!("The player with id {} is not in the game with id {}!", player_id, game_id)));
+            }
+            self.lock_player_tokens()?.insert(player_id, token.clone());
+            game.player_tokens.insert(player_id, token.clone());
+            self.handle_updated_game(&game)
+        };
+        self.store_game(&game_to_store);
+        self.update_check_in_and_remove_inactive(player_id)?;
+        log!(self.logger, LogLevel::Info, format!("Player with id {} reconnected to the game with id {}", player_id, game_id).as_str());
+        Ok(PlayerCredentials { player_id, token, reconnect_code })
+    }
+
+    fn generate_player_token() -> PlayerToken {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(PLAYER_TOKEN_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    /// Checks that the given token is the token that was made for the player with the given id. Will return an error if there is no token or it does not belong to the player.
+    pub fn authenticate(&self, player_id: PlayerID, token: Option<&str>) -> Result<(), GameError> {
+        let Some(token) = token else {
+            log!(self.logger, LogLevel::Warning, format!("A request for player with id {} was made without a token!", player_id).as_str());
+            return Err(GameError::Unauthorized("The request does not have a player token!".to_string()));
+        };
+        match self.lock_player_tokens()?.get(&player_id) {
+            Some(player_token) if player_token == token => Ok(()),
+            _ => {
+                log!(self.logger, LogLevel::Warning, format!("A request for player with id {} was made with a token that does not belong to the player!", player_id).as_str());
+                Err(GameError::Unauthorized("The player token does not belong to the player!".to_string()))
+            },
+        }
+    }
+
     /// Creates a new game based and assigns the host (the one who requested to create a game) to the game.
-    pub fn create_new_game(&mut self, new_lobby: NewGameInfo) -> Result<GameState, String> {
-        let new_game = match self.create_new_game_and_assign_host(new_lobby) {
+    pub fn create_new_game(&self, new_lobby: NewGameInfo) -> Result<GameState, GameError> {
+        let mut games = self.write_games()?;
+        let new_game = match self.create_new_game_and_assign_host(&games, new_lobby) {
             Ok(game) => game,
             Err(e) => {
                 log!(self.logger, LogLevel::Error, format!("Failed to create new game because: {}", e).as_str());
@@ -74,89 +265,150 @@ impl GameController {
             },
         };
         log!(self.logger, LogLevel::Info, format!("Created new game with id: {}", new_game.id).as_str());
-        self.games.push(new_game.clone());
+        games.insert(new_game.id, Arc::new(Mutex::new(new_game.clone())));
+        let game_to_store = self.handle_updated_game(&new_game);
+        drop(games);
+        self.store_game(&game_to_store);
         Ok(new_game)
     }
 
     /// Handles the player input and returns the new game state if the player input was valid.
-    pub fn handle_player_input(&mut self, player_input: PlayerInput) -> Result<GameState, String> {
+    /// 
+    /// Only the game the input is for is locked while the input is handled.
+    pub fn handle_player_input(&self, player_input: PlayerInput) -> Result<GameState, GameError> {
         log!(self.logger, LogLevel::Debug, format!("Handling player input: {:?}", player_input).as_str());
-        self.remove_empty_games();
         self.remove_inactive_ids();
 
-        if !self
-            .unique_ids
-            .iter()
-            .any(|(id, _)| id == &player_input.player_id)
-        {
+        if !self.is_player_id_registered(player_input.player_id) {
             log!(self.logger, LogLevel::Error, format!("There does not exist a player with the unique id {} and can therefore not handle the player input", player_input.player_id).as_str());
-            return Err("There does not exist a player with the unique id".to_string());
+            return Err(GameError::NotFound("There does not exist a player with the unique id".to_string()));
         }
 
-        let mut games_iter = self.games.iter_mut();
-
         let connected_game_id = player_input.game_id;
 
-        let related_game = match games_iter.find(|game| game.id == connected_game_id) {
+        let shared_game = match self.get_shared_game(connected_game_id) {
             Some(game) => game,
             None => {
                 log!(self.logger, LogLevel::Error, "Could not find the game the player has done an input for!");
-                return Err("Could not find the game the player has done an input for!".to_string())
+                return Err(GameError::NotFound("Could not find the game the player has done an input for!".to_string()))
             }
         };
-        log!(self.logger, LogLevel::Debug, format!("Found game with id: {}", related_game.id).as_str());
+        let (apply_result, game_to_store) = {
+            let mut related_game = Self::lock_game(&shared_game)?;
+            log!(self.logger, LogLevel::Debug, format!("Found game with id: {}", related_game.id).as_str());
 
-        let mut related_game_clone = related_game.clone();
-        match Self::apply_game_actions(&mut related_game_clone) {
-            Ok(_) => (),
+            // Spectators can only follow the game, so the only input they can do is to stop spectating it.
+            if related_game.contains_spectator_with_unique_id(player_input.player_id) {
+                if player_input.input_type != PlayerInputType::LeaveGame {
+                    log!(self.logger, LogLevel::Error, format!("The spectator with id {} tried to do an input in the game with id: {}", player_input.player_id, related_game.id).as_str());
+                    return Err(GameError::Forbidden("Spectators can not do inputs in the game they are spectating!".to_string()));
+                }
+                related_game.remove_spectator_with_id(player_input.player_id);
+                log!(self.logger, LogLevel::Info, format!("The spectator with id {} stopped spectating the game with id: {}", player_input.player_id, related_game.id).as_str());
+                let game_to_store = self.handle_updated_game(&related_game);
+                let updated_game = related_game.clone();
+                drop(related_game);
+                self.store_game(&game_to_store);
+                return Ok(updated_game);
+            }
+
+            let mut related_game_clone = related_game.clone();
+            match Self::apply_game_actions(&mut related_game_clone) {
+                Ok(_) => (),
+                Err(e) => {
+                    log!(self.logger, LogLevel::Error, format!("Failed to apply previous game actions to the clone of the game with id: {} because: {}", related_game.id, e).as_str());
+                    return Err(e);
+                },
+            }
+            log!(self.logger, LogLevel::Debug, format!("Applied previous game actions to the clone of the game with id: {}", related_game.id).as_str());
+
+            if let Some(error) = self
+                .rule_checker
+                .is_input_valid(&related_game_clone, &player_input)
+            {
+                log!(self.logger, LogLevel::Error, format!("The input was not valid for the game with id: {} because: {}", related_game.id, error).as_str());
+                return Err(error.map_message(|message| format!("The input was not valid! Because: {message}")));
+            }
+            log!(self.logger, LogLevel::Debug, format!("The input was valid for the game with id: {}", related_game.id).as_str());
+
+            match Self::handle_input(player_input.clone(), &mut related_game) {
+                Ok(_) => (),
+                Err(e) => {
+                    log!(self.logger, LogLevel::Error, format!("Failed to handle player input because: {}", e).as_str());
+                    return Err(e);
+                }
+            };
+            log!(self.logger, LogLevel::Info, format!("Added/Handled the new input to the game with id: {}", related_game.id).as_str());
+
+            let mut game_clone = related_game.clone();
+            let apply_result = Self::apply_game_actions(&mut game_clone);
+            if apply_result.is_ok() {
+                related_game.record_action(player_input.clone(), game_clone.state_hash());
+                if self.skip_disconnected_turns(&mut related_game) {
+                    game_clone = related_game.clone();
+                }
+            }
+            (apply_result.map(|_| game_clone), self.handle_updated_game(&related_game))
+        };
+        self.store_game(&game_to_store);
+        let mut game_clone = match apply_result {
+            Ok(game_clone) => game_clone,
             Err(e) => {
-                log!(self.logger, LogLevel::Error, format!("Failed to apply previous game actions to the clone of the game with id: {} because: {}", related_game.id, e).as_str());
+                log!(self.logger, LogLevel::Error, format!("Failed to apply the game actions to the clone of the game with id: {} because: {}", connected_game_id, e).as_str());
                 return Err(e);
             },
+        };
+        if game_clone.players.is_empty() {
+            self.remove_empty_games();
         }
-        log!(self.logger, LogLevel::Debug, format!("Applied previous game actions to the clone of the game with id: {}", related_game.id).as_str());
 
-        if let Some(error) = self
-            .rule_checker
-            .is_input_valid(&related_game_clone, &player_input)
-        {
-            log!(self.logger, LogLevel::Error, format!("The input was not valid for the game with id: {} because: {}", related_game.id, error).as_str());
-            return Err(format!("The input was not valid! Because: {error}"));
+        self.get_legal_nodes(&mut game_clone, player_input.player_id, player_input.vehicle_id);
+        Ok(game_clone)
+    }
+
+    /// Checks the player input against every rule without handling it, and returns every rule it breaks. The input is valid if the list is empty.
+    ///
+    /// If the input breaks no rules, it is also handled on a copy of the game, so that inputs that would fail when being handled are found as well. Those failures are given with the rule name `handle_input`.
+    pub fn validate_player_input(&self, player_input: PlayerInput) -> Result<Vec<RuleViolation>, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Validating player input: {:?}", player_input).as_str());
+        if !self.is_player_id_registered(player_input.player_id) {
+            return Err(GameError::NotFound("There does not exist a player with the unique id".to_string()));
+        }
+        let game = self.get_game_with_applied_actions(player_input.game_id)?;
+        let violations = self.rule_checker.get_rule_violations(&game, &player_input);
+        if !violations.is_empty() {
+            return Ok(violations);
         }
-        log!(self.logger, LogLevel::Debug, format!("The input was valid for the game with id: {}", related_game.id).as_str());
 
-        match Self::handle_input(player_input.clone(), related_game) {
-            Ok(_) => (),
-            Err(e) => {
-                log!(self.logger, LogLevel::Error, format!("Failed to handle player input because: {}", e).as_str());
-                return Err(e);
-            }
+        let mut game_clone = game;
+        let result = match Self::handle_input(player_input, &mut game_clone) {
+            Ok(_) => Self::apply_game_actions(&mut game_clone),
+            Err(e) => Err(e),
         };
-        log!(self.logger, LogLevel::Info, format!("Added/Handled the new input to the game with id: {}", related_game.id).as_str());
-
-        let mut game_clone = related_game.clone();
-        match Self::apply_game_actions(&mut game_clone) {
-            Ok(_) => {
-                self.get_legal_nodes(&mut game_clone, player_input.player_id);
-                Ok(game_clone.clone())
-            },
-            Err(e) => {
-                log!(self.logger, LogLevel::Error, format!("Failed to apply the game actions to the clone of the game with id: {} because: {}", related_game.id, e).as_str());
-                Err(e)
-            },
+        match result {
+            Ok(_) => Ok(Vec::new()),
+            Err(e) => Ok(vec![RuleViolation::new(HANDLE_INPUT_RULE_NAME, &e)]),
         }
     }
 
+    /// Returns a description of every rule of the rule checker, including if it's used by default and if it can be turned off for a game.
+    pub fn get_rules_info(&self) -> Vec<RuleInfo> {
+        self.rule_checker.get_rules_info()
+    }
+
     /// Returns the amount of unique player ids that have been created.
     pub fn get_amount_of_created_player_ids(&self) -> i32 {
-        self.unique_ids.len() as i32
+        match self.lock_unique_ids() {
+            Ok(unique_ids) => unique_ids.len() as i32,
+            Err(_) => 0,
+        }
     }
 
     /// Returns all the games that have not started yet.
     pub fn get_all_lobbies(&self) -> Vec<GameState> {
         log!(self.logger, LogLevel::Debug, "Getting all lobbies!");
         let mut lobbies = Vec::new();
-        self.games.clone().into_iter().for_each(|game| {
+        self.get_all_games().into_iter().for_each(|game| {
             if game.is_lobby {
                 lobbies.push(game);
             }
@@ -165,41 +417,76 @@ impl GameController {
     }
 
     /// Adds the player to the game if there is room for the player and the player is not in another game. It will also return other errors if it cannot add the player to the game.
-    pub fn join_game(&mut self, game_id: GameID, player: Player) -> Result<GameState, String> {
+    pub fn join_game(&self, game_id: GameID, player: Player) -> Result<GameState, GameError> {
         log!(self.logger, LogLevel::Debug, format!("Player with id: {} is trying to join game with id: {}", player.unique_id, game_id).as_str());
-        for game in self.games.iter() {
-            if game.contains_player_with_unique_id(player.unique_id) {
+        // The map of games is write locked so that the player cannot join two games at the same time.
+        let games = self.write_games()?;
+        for shared_game in games.values() {
+            if Self::lock_game(shared_game)?.contains_player_with_unique_id(player.unique_id) {
                 log!(self.logger, LogLevel::Error, format!("The player with id: {} is already connected to another game.", player.unique_id).as_str());
-                return Err("The player is already connected to another game.".to_string());
+                return Err(GameError::Conflict("The player is already connected to another game.".to_string()));
             }
         }
-        let mut games_iter = self.games.iter_mut();
-        let related_game = match games_iter.find(|game| game.id == game_id) {
+        let shared_game = match games.get(&game_id) {
             Some(game) => game,
             None => {
                 log!(self.logger, LogLevel::Error, format!("Could not find the game the player with id: {} is trying to join!", player.unique_id).as_str());
-                return Err("Could not find the game the player is trying to join!".to_string())
+                return Err(GameError::NotFound("Could not find the game the player is trying to join!".to_string()))
             }
         };
+        let mut related_game = Self::lock_game(shared_game)?;
         match related_game.assign_player_to_game(player.clone()) {
-            Ok(_) => (),
+            Ok(_) => self.keep_player_token_in_game(&mut related_game, player.unique_id),
             Err(e) => {
                 log!(self.logger, LogLevel::Error, format!("Failed to assign player with id: {} to game with id: {} because: {}", player.unique_id, game_id, e).as_str());
                 return Err(e);
             },
         };
         log!(self.logger, LogLevel::Info, format!("Player with id: {} joined game with id: {}", player.unique_id, game_id).as_str());
-        Ok(related_game.clone())
+        let game_to_store = self.handle_updated_game(&related_game);
+        let joined_game = related_game.clone();
+        drop(related_game);
+        drop(games);
+        self.store_game(&game_to_store);
+        Ok(joined_game)
+    }
+
+    /// Adds the spectator to the game with the given id, so that they can follow the game without playing it. Both lobbies and games that have started can be spectated, and the spectators do not count against the max amount of players. If there was a problem with adding the spectator it will return an error.
+    pub fn join_game_as_spectator(&self, game_id: GameID, spectator: Spectator) -> Result<GameState, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Player with id: {} is trying to spectate game with id: {}", spectator.unique_id, game_id).as_str());
+        if !self.is_player_id_registered(spectator.unique_id) {
+            log!(self.logger, LogLevel::Error, format!("There does not exist a player with the unique id {} and it can therefore not spectate a game", spectator.unique_id).as_str());
+            return Err(GameError::NotFound("A player that has a unique ID that was not made by the server cannot spectate a game.".to_string()));
+        }
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            log!(self.logger, LogLevel::Error, format!("Could not find the game the player with id: {} is trying to spectate!", spectator.unique_id).as_str());
+            return Err(GameError::NotFound("Could not find the game the player is trying to spectate!".to_string()));
+        };
+        let mut related_game = Self::lock_game(&shared_game)?;
+        let spectator_id = spectator.unique_id;
+        match related_game.add_spectator(spectator) {
+            Ok(_) => self.keep_player_token_in_game(&mut related_game, spectator_id),
+            Err(e) => {
+                log!(self.logger, LogLevel::Error, format!("Failed to add spectator with id: {} to game with id: {} because: {}", spectator_id, game_id, e).as_str());
+                return Err(e);
+            },
+        };
+        log!(self.logger, LogLevel::Info, format!("Player with id: {} is spectating game with id: {}", spectator_id, game_id).as_str());
+        let game_to_store = self.handle_updated_game(&related_game);
+        let updated_game = related_game.clone();
+        drop(related_game);
+        self.store_game(&game_to_store);
+        Ok(updated_game)
     }
 
-    /// Gets the game with the given id. If there was a problem with getting the game it will return a string with the error.
-    pub fn get_game_by_id(&mut self, game_id: GameID) -> Result<GameState, String> {
+    /// Gets the game with the given id. If there was a problem with getting the game it will return an error.
+    pub fn get_game_by_id(&self, game_id: GameID) -> Result<GameState, GameError> {
         log!(self.logger, LogLevel::Debug, format!("Trying to get game with id: {}", game_id).as_str());
-        let Some(game) = self.games.iter().find(|g| g.id == game_id) else {
+        let Some(shared_game) = self.get_shared_game(game_id) else {
             log!(self.logger, LogLevel::Error, format!("There is no game with id {} and can therefore not return the wanted game!", game_id).as_str());
-            return Err(format!("There is no game with id {}!", game_id));
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
         };
-        let mut game_clone = game.clone();
+        let mut game_clone = Self::lock_game(&shared_game)?.clone();
         match Self::apply_game_actions(&mut game_clone) {
             Ok(_) => {
                 if !game_clone.is_lobby {
@@ -207,12 +494,12 @@ impl GameController {
                     let players = game_clone.players.clone();
                     let Some(player) = players.iter().find(|p| p.in_game_id == current_players_turn) else {
                         log!(self.logger, LogLevel::Error, format!("Failed to apply the game actions to the clone of the game with id {} because there is no player that has the current in game turn {:?} and can therefore not return the wanted game!", game_id, current_players_turn).as_str());
-                        return Err(format!("There is no player that has the current in game turn {:?}!", current_players_turn));
+                        return Err(GameError::Internal(format!("There is no player that has the current in game turn {:?}!", current_players_turn)));
                     };
-                    self.get_legal_nodes(&mut game_clone, player.unique_id);
+                    self.get_legal_nodes(&mut game_clone, player.unique_id, 0);
                 }
                 log!(self.logger, LogLevel::Info, format!("Returning game with id: {}", game_id).as_str());
-                Ok(game_clone.clone())},
+                Ok(game_clone)},
             Err(e) => {
                 log!(self.logger, LogLevel::Error, format!("Failed to apply the game actions to the clone of the game with id: {} because: {} and can therefore not return the wanted game", game_id, e).as_str());
                 Err(e)
@@ -220,58 +507,682 @@ impl GameController {
         }
     }
 
-    /// Tells the game controller that a unique id is used by a player. This will also remove all inactive players. This means that if a player has not checked in after some amount of time, defined in [`constants`](../game_data/constants/index.html) as `PLAYER_TIMEOUT`, they will be removed.
+    /// Gets the cheapest route to every node the vehicle with the given id of the player with the given id can reach with the moves it has left this turn.
+    pub fn get_reachable_nodes(&self, game_id: GameID, player_id: PlayerID, vehicle_id: VehicleID) -> Result<Vec<Route>, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Trying to find the nodes vehicle with id {} of player with id {} can reach in game with id {}", vehicle_id, player_id, game_id).as_str());
+        let game = self.get_game_with_applied_actions(game_id)?;
+        match path_finding::find_routes(&game, player_id, vehicle_id, self.rule_checker.as_ref(), true) {
+            Ok(routes) => Ok(routes),
+            Err(e) => {
+                log!(self.logger, LogLevel::Debug, format!("Failed to find the nodes player with id {} can reach because: {}", player_id, e).as_str());
+                Err(e)
+            },
+        }
+    }
+
+    /// Gets the cheapest route for the vehicle with the given id of the player with the given id to the node with the given id, no matter how many moves the vehicle has left this turn.
+    pub fn get_cheapest_route(&self, game_id: GameID, player_id: PlayerID, vehicle_id: VehicleID, to_node_id: NodeID) -> Result<Route, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Trying to find the cheapest route for vehicle with id {} of player with id {} to node with id {} in game with id {}", vehicle_id, player_id, to_node_id, game_id).as_str());
+        let game = self.get_game_with_applied_actions(game_id)?;
+        match path_finding::find_cheapest_route(&game, player_id, vehicle_id, to_node_id, self.rule_checker.as_ref()) {
+            Ok(route) => Ok(route),
+            Err(e) => {
+                log!(self.logger, LogLevel::Debug, format!("Failed to find the cheapest route for player with id {} because: {}", player_id, e).as_str());
+                Err(e)
+            },
+        }
+    }
+
+    /// Gets suggestions for which district modifiers and park & ride placements the orchestrator could make to fit the goal of the situation card better. Only the orchestrator of the game can get the suggestions.
+    pub fn get_orchestrator_advice(&self, game_id: GameID, player_id: PlayerID) -> Result<OrchestratorAdvice, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Player with id {} is trying to get orchestrator advice for game with id {}", player_id, game_id).as_str());
+        let game = self.get_game_with_applied_actions(game_id)?;
+        if game.is_lobby {
+            return Err(GameError::Conflict("The game has not started yet!".to_string()));
+        }
+        match game.get_player_with_unique_id(player_id) {
+            Ok(player) if player.in_game_id == InGameID::Orchestrator => (),
+            Ok(_) => return Err(GameError::Forbidden("Only the orchestrator can get advice!".to_string())),
+            Err(e) => return Err(e),
+        }
+        match advisor::advise(&game, player_id, self.rule_checker.as_ref(), Self::apply_input) {
+            Ok(advice) => Ok(advice),
+            Err(e) => {
+                log!(self.logger, LogLevel::Debug, format!("Failed to get orchestrator advice for game with id {} because: {}", game_id, e).as_str());
+                Err(e)
+            },
+        }
+    }
+
+    /// Gets the final results of the game with the given id. Will return an error if the game does not exist or has not ended yet.
+    pub fn get_game_results(&self, game_id: GameID) -> Result<GameResults, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Trying to get the results of game with id: {}", game_id).as_str());
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            log!(self.logger, LogLevel::Error, format!("There is no game with id {} and can therefore not return the results of the game!", game_id).as_str());
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let Some(results) = Self::lock_game(&shared_game)?.game_results.clone() else {
+            log!(self.logger, LogLevel::Debug, format!("The game with id {} has not ended yet and has therefore no results!", game_id).as_str());
+            return Err(GameError::Conflict(format!("The game with id {} has not ended yet!", game_id)));
+        };
+        Ok(results)
+    }
+
+    /// Gets every accepted input of the game with the given id, in the order they were handled. Will return an error if the game does not exist.
+    pub fn get_action_log(&self, game_id: GameID) -> Result<Vec<ActionLogEntry>, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Trying to get the action log of game with id: {}", game_id).as_str());
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            log!(self.logger, LogLevel::Error, format!("There is no game with id {} and can therefore not return the action log of the game!", game_id).as_str());
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let entries = Self::lock_game(&shared_game)?.action_log.entries.clone();
+        Ok(entries)
+    }
+
+    /// Reconstructs the game with the given id as it was at the start of the given turn, by replaying the action log from when the game was last started. Turn 0 is the game right after it was started.
+    /// 
+    /// Will return an error if the game does not exist, has not been started, or has not reached the given turn yet. If the replay does not give the same game states as when the game was played, for example because a player was removed for not checking in, a warning is logged and the replay continues.
+    pub fn get_game_state_at_turn(&self, game_id: GameID, turn: u32) -> Result<GameState, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Trying to replay game with id {} to turn {}", game_id, turn).as_str());
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            log!(self.logger, LogLevel::Error, format!("There is no game with id {} and can therefore not replay the game!", game_id).as_str());
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let action_log = Self::lock_game(&shared_game)?.action_log.clone();
+        let Some(start_state) = action_log.start_state.clone() else {
+            return Err(GameError::Conflict(format!("The game with id {} has not been started and can therefore not be replayed!", game_id)));
+        };
+        if turn > action_log.turns_played {
+            return Err(GameError::NotFound(format!("The game with id {} has only played {} turns since it was started!", game_id, action_log.turns_played)));
+        }
+
+        let mut game = start_state.into_game()?;
+        for entry in action_log.entries_since_start() {
+            if entry.turn >= turn {
+                break;
+            }
+            match Self::handle_input(entry.input.clone(), &mut game) {
+                Ok(_) => (),
+                Err(e) => {
+                    log!(self.logger, LogLevel::Error, format!("Failed to replay the input {:?} in the game with id {} because: {}", entry.input, game_id, e).as_str());
+                    return Err(e.map_message(|message| format!("Failed to replay the game because: {message}")));
+                }
+            }
+            let mut game_clone = game.clone();
+            Self::apply_game_actions(&mut game_clone)?;
+            if game_clone.state_hash() != entry.state_hash {
+                log!(self.logger, LogLevel::Warning, format!("The replay of the game with id {} differs from the played game after the input {:?}", game_id, entry.input).as_str());
+            }
+        }
+        Self::apply_game_actions(&mut game)?;
+        log!(self.logger, LogLevel::Info, format!("Replayed game with id {} to turn {}", game_id, turn).as_str());
+        Ok(game)
+    }
+
+    /// Lets the orchestrator of a lobby upload their own situation card and assigns it to the lobby. The situation card is validated against the map of the lobby, and its ID can not be the same as the ID of one of the situation cards of the server. Returns the updated game, or an error if the situation card could not be used.
+    pub fn assign_custom_situation_card(&self, game_id: GameID, player_id: PlayerID, definition: SituationCardDefinition) -> Result<GameState, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Player with id {} is trying to upload a situation card to the game with id {}", player_id, game_id).as_str());
+        if !self.is_player_id_registered(player_id) {
+            log!(self.logger, LogLevel::Error, format!("There does not exist a player with the unique id {} and can therefore not upload a situation card", player_id).as_str());
+            return Err(GameError::NotFound("There does not exist a player with the unique id".to_string()));
+        }
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            log!(self.logger, LogLevel::Error, format!("There is no game with id {} and can therefore not upload a situation card to it!", game_id).as_str());
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let mut game = Self::lock_game(&shared_game)?;
+        if !game.is_lobby {
+            return Err(GameError::Conflict("A situation card can only be uploaded before the game has started!".to_string()));
+        }
+        match game.get_player_with_unique_id(player_id) {
+            Ok(player) if player.in_game_id == InGameID::Orchestrator => (),
+            Ok(_) => return Err(GameError::Forbidden("Only the orchestrator can upload a situation card!".to_string())),
+            Err(e) => return Err(e),
+        }
+
+        let card = definition.into_situation_card();
+        if situation_card_list().iter().any(|c| c.card_id == card.card_id) {
+            return Err(GameError::Conflict(format!("The ID {} is already used by a situation card on the server!", card.card_id)));
+        }
+        let previous_custom_situation_card = game.custom_situation_card.replace(card.clone());
+        if let Err(e) = game.assign_situation_card(card) {
+            game.custom_situation_card = previous_custom_situation_card;
+            log!(self.logger, LogLevel::Error, format!("Failed to assign the uploaded situation card to the game with id {} because: {}", game_id, e).as_str());
+            return Err(e);
+        }
+        log!(self.logger, LogLevel::Info, format!("Assigned the uploaded situation card to the game with id {}", game_id).as_str());
+        let game_to_store = self.handle_updated_game(&game);
+        let updated_game = game.clone();
+        drop(game);
+        self.store_game(&game_to_store);
+        Ok(updated_game)
+    }
+
+    /// Tells the game controller that a unique id is used by a player. This will also remove all inactive players. This means that if a player has not checked in within the player timeout of the game controller, they will be removed.
     pub fn update_check_in_and_remove_inactive(
-        &mut self,
+        &self,
         player_id: PlayerID,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         log!(self.logger, LogLevel::Debug, format!("Updating check in for player with id: {}", player_id).as_str());
-        if self.unique_ids.iter().all(|(id, _)| id != &player_id) {
-            log!(self.logger, LogLevel::Error, format!("Player with id {} does not exist and can therefore not update the check in!", player_id).as_str());
-            return Err(format!("Player with id {} does not exist!", player_id));
-        }
-        for mut id in self.unique_ids.iter_mut() {
-            if id.0 == player_id {
-                id.1 = Instant::now();
-            }
+        let was_disconnected = {
+            let mut unique_ids = self.lock_unique_ids()?;
+            let Some((_, last_checkin)) = unique_ids.iter_mut().find(|(id, _)| id == &player_id) else {
+                log!(self.logger, LogLevel::Error, format!("Player with id {} does not exist and can therefore not update the check in!", player_id).as_str());
+                return Err(GameError::NotFound(format!("Player with id {} does not exist!", player_id)));
+            };
+            let was_disconnected = last_checkin.elapsed() >= self.player_timeout;
+            *last_checkin = Instant::now();
+            was_disconnected
+        };
+        if was_disconnected {
+            self.set_player_disconnected(player_id, false);
         }
         self.remove_inactive_ids();
-        self.remove_empty_games();
         log!(self.logger, LogLevel::Debug, format!("Updated check in for player with id {} and removed unused ids and empty games!", player_id).as_str());
         Ok(())
     }
 
-    fn remove_empty_games(&mut self) {
+    /// Adds a bot with the given strategy to the lobby with the given id and gives it the first free player role. Only a player in the lobby can add a bot to it.
+    pub fn add_bot(&self, game_id: GameID, player_id: PlayerID, strategy: BotStrategy) -> Result<GameState, GameError> {
+        log!(self.logger, LogLevel::Debug, format!("Player with id {} is trying to add a {:?} bot to game with id {}", player_id, strategy, game_id).as_str());
+        let bot_id = self.generate_player_id()?;
+        let bot = Bot::new(bot_id, game_id, strategy);
+        // The map of games is write locked like when a player joins a game, and the lobby is checked and joined while the game is locked, so that the game can not be started in between.
+        let games = self.write_games()?;
+        let Some(shared_game) = games.get(&game_id) else {
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let mut game = Self::lock_game(shared_game)?;
+        if !game.is_lobby {
+            return Err(GameError::Conflict("Bots can only be added to a lobby!".to_string()));
+        }
+        if !game.contains_player_with_unique_id(player_id) {
+            return Err(GameError::Forbidden("Only a player in the lobby can add a bot to it!".to_string()));
+        }
+        let mut free_roles = vec![InGameID::PlayerOne, InGameID::PlayerTwo, InGameID::PlayerThree, InGameID::PlayerFour, InGameID::PlayerFive, InGameID::PlayerSix];
+        free_roles.retain(|role| game.players.iter().all(|player| &player.in_game_id != role));
+        let Some(role) = free_roles.first().copied() else {
+            return Err(GameError::Conflict("There are no free player roles in the lobby!".to_string()));
+        };
+
+        let mut bot_player = Player::new(bot_id, format!("{:?} bot", strategy));
+        bot_player.bot_strategy = Some(strategy);
+        game.assign_player_to_game(bot_player)?;
+        let mut change_role_input = bot.input(PlayerInputType::ChangeRole);
+        change_role_input.related_role = Some(role);
+        if let Err(e) = Self::handle_input(change_role_input.clone(), &mut game) {
+            game.remove_player_with_id(bot_id);
+            return Err(e);
+        }
+        Self::record_server_action(&mut game, change_role_input);
+        self.lock_bots()?.push(bot);
+        let game_to_store = self.handle_updated_game(&game);
+        drop(game);
+        drop(games);
+        self.store_game(&game_to_store);
+        log!(self.logger, LogLevel::Info, format!("Added a {:?} bot with id {} to game with id {}", strategy, bot_id, game_id).as_str());
+        self.get_game_by_id(game_id)
+    }
+
+    /// Ends the current turn of every game where the turn has run out of time. The turn is ended with a `NextTurn` input from the player whose turn it is, so the pending actions of the player are done and the input is logged just like when the player ends the turn themselves. Running games without a started turn timer, e.g. games restored from the storage, get their turn timer started.
+    pub fn end_expired_turns(&self) {
+        let shared_games: Vec<SharedGame> = match self.read_games() {
+            Ok(games) => games.values().cloned().collect(),
+            Err(_) => return,
+        };
+        for shared_game in shared_games {
+            let (game_id, turn, current_player_id) = {
+                let Ok(mut game) = Self::lock_game(&shared_game) else {
+                    continue;
+                };
+                if game.is_lobby || game.settings.turn_time_limit_seconds.is_none() {
+                    continue;
+                }
+                if game.turn_deadline.is_none() {
+                    game.restart_turn_timer();
+                    continue;
+                }
+                if !game.is_turn_time_up() {
+                    continue;
+                }
+                let current_player_id = game
+                    .players
+                    .iter()
+                    .find(|player| player.in_game_id == game.current_players_turn)
+                    .map(|player| player.unique_id);
+                (game.id, game.current_players_turn, current_player_id)
+            };
+            log!(self.logger, LogLevel::Info, format!("The turn of {:?} in the game with id {} has run out of time", turn, game_id).as_str());
+
+            if let Some(player_id) = current_player_id {
+                match self.handle_player_input(Self::server_input(player_id, game_id, PlayerInputType::NextTurn)) {
+                    Ok(_) => continue,
+                    Err(e) => log!(self.logger, LogLevel::Warning, format!("Could not end the turn of the player with id {} in the game with id {} because: {}", player_id, game_id, e).as_str()),
+                }
+            }
+
+            // The turn is ended without the pending actions if it could not be ended as normal, so that the game does not get stuck. The game is checked again since the turn may have been ended in the meantime.
+            let game_to_store = {
+                let Ok(mut game) = Self::lock_game(&shared_game) else {
+                    continue;
+                };
+                if game.current_players_turn != turn || !game.is_turn_time_up() {
+                    continue;
+                }
+                if let Err(e) = Self::end_turn_without_actions(&mut game, current_player_id.unwrap_or_default()) {
+                    log!(self.logger, LogLevel::Error, format!("Failed to end the turn in the game with id {} because: {}", game_id, e).as_str());
+                    continue;
+                }
+                self.skip_disconnected_turns(&mut game);
+                self.handle_updated_game(&game)
+            };
+            self.store_game(&game_to_store);
+        }
+    }
+
+    /// Undoes the pending actions of the player with the given id and ends their turn. The undone actions and the ended turn are inputs in the action log, so that a replay of the game ends the turn in the same way.
+    fn end_turn_without_actions(game: &mut GameState, player_id: PlayerID) -> Result<(), GameError> {
+        while !game.actions.is_empty() {
+            let undo_action = Self::server_input(player_id, game.id, PlayerInputType::UndoAction);
+            Self::handle_input(undo_action.clone(), game)?;
+            Self::record_server_action(game, undo_action);
+        }
+        let next_turn = Self::server_input(player_id, game.id, PlayerInputType::NextTurn);
+        Self::handle_input(next_turn.clone(), game)?;
+        Self::record_server_action(game, next_turn);
+        Ok(())
+    }
+
+    /// Ends the turns of the disconnected players as long as it's the turn of one of them, so that the other players do not have to wait for them. A turn is ended with the actions the player has done so far if possible, and without them if not. The ended turns are inputs in the action log, so that a replay of the game skips the same turns. Returns `true` if a turn was ended, else it will return `false`.
+    fn skip_disconnected_turns(&self, game: &mut GameState) -> bool {
+        let mut has_ended_turn = false;
+        while game.is_current_turn_skipped() {
+            let Some(player_id) = game
+                .players
+                .iter()
+                .find(|player| player.in_game_id == game.current_players_turn)
+                .map(|player| player.unique_id)
+            else {
+                break;
+            };
+            let next_turn = Self::server_input(player_id, game.id, PlayerInputType::NextTurn);
+            match Self::handle_input(next_turn.clone(), game) {
+                Ok(_) => Self::record_server_action(game, next_turn),
+                Err(e) => {
+                    log!(self.logger, LogLevel::Warning, format!("Could not do the actions of the disconnected player with id {} because: {}", player_id, e).as_str());
+                    if let Err(e) = Self::end_turn_without_actions(game, player_id) {
+                        log!(self.logger, LogLevel::Error, format!("Failed to skip the turn of the disconnected player with id {} because: {}", player_id, e).as_str());
+                        break;
+                    }
+                }
+            }
+            log!(self.logger, LogLevel::Info, format!("Skipped the turn of the disconnected player with id {} in the game with id {}", player_id, game.id).as_str());
+            has_ended_turn = true;
+        }
+        has_ended_turn
+    }
+
+    /// Lets every bot whose turn it is move and end its turn. The bots are checked in so that they are not removed as inactive, and bots that are left alone in a game leave it so that the game can be removed.
+    pub fn play_bot_turns(&self) {
+        let Ok(bots) = self.lock_bots().map(|bots| bots.clone()) else {
+            return;
+        };
+        if let Ok(mut unique_ids) = self.lock_unique_ids() {
+            for (id, last_checkin) in unique_ids.iter_mut() {
+                if bots.iter().any(|bot| &bot.player_id == id) {
+                    *last_checkin = Instant::now();
+                }
+            }
+        }
+
+        let mut removed_bot_ids: Vec<PlayerID> = Vec::new();
+        for bot in bots.iter() {
+            let game = match self.get_game_with_applied_actions(bot.game_id) {
+                Ok(game) if game.contains_player_with_unique_id(bot.player_id) => game,
+                _ => {
+                    removed_bot_ids.push(bot.player_id);
+                    continue;
+                }
+            };
+            if game.players.iter().all(|player| bots.iter().any(|bot| bot.player_id == player.unique_id)) {
+                log!(self.logger, LogLevel::Info, format!("The bot with id {} is the only one left in game with id {} and leaves the game", bot.player_id, bot.game_id).as_str());
+                let _ = self.handle_player_input(bot.input(PlayerInputType::LeaveGame));
+                removed_bot_ids.push(bot.player_id);
+                continue;
+            }
+            let is_bots_turn = matches!(game.get_player_with_unique_id(bot.player_id), Ok(player) if player.in_game_id == game.current_players_turn);
+            if game.is_lobby || game.is_game_over() || !is_bots_turn {
+                continue;
+            }
+
+            if let Some((vehicle_id, path)) = bot.plan_movement(&game, self.rule_checker.as_ref()) {
+                if let Err(e) = self.handle_player_input(bot.movement_input(vehicle_id, path)) {
+                    log!(self.logger, LogLevel::Warning, format!("The bot with id {} could not move because: {}", bot.player_id, e).as_str());
+                }
+            }
+            if let Err(e) = self.handle_player_input(bot.input(PlayerInputType::NextTurn)) {
+                log!(self.logger, LogLevel::Warning, format!("The bot with id {} could not end its turn because: {}", bot.player_id, e).as_str());
+            }
+        }
+
+        if !removed_bot_ids.is_empty() {
+            if let Ok(mut bots) = self.lock_bots() {
+                bots.retain(|bot| !removed_bot_ids.contains(&bot.player_id));
+            }
+        }
+    }
+
+    fn remove_empty_games(&self) {
         log!(self.logger, LogLevel::Debug, "Removing empty games!");
-        self.games.retain(|game| !game.players.is_empty());
+        // The games are first checked with a read lock, so that the map of games is only write locked when there is something to remove.
+        if self.get_all_games().iter().all(|game| !game.players.is_empty()) {
+            return;
+        }
+        let Ok(mut games) = self.write_games() else {
+            return;
+        };
+        let empty_game_ids: Vec<GameID> = games
+            .iter()
+            .filter(|(_, game)| matches!(Self::lock_game(game), Ok(game) if game.players.is_empty()))
+            .map(|(id, _)| *id)
+            .collect();
+        for game_id in empty_game_ids.iter() {
+            games.remove(game_id);
+        }
+        drop(games);
+        for game_id in empty_game_ids {
+            let stored_version = self.get_stored_game_version(game_id);
+            let Ok(mut stored_version) = stored_version.lock() else {
+                continue;
+            };
+            *stored_version = u64::MAX;
+            match self.storage.remove_game(game_id) {
+                // The copies taken before the game was removed keep the tombstone, so the entry can be dropped and a new game with the same id is stored again.
+                Ok(_) => match self.stored_game_versions.lock() {
+                    Ok(mut stored_game_versions) => { stored_game_versions.remove(&game_id); },
+                    Err(poisoned) => { poisoned.into_inner().remove(&game_id); },
+                },
+                Err(e) => log!(self.logger, LogLevel::Error, format!("Failed to remove the stored game with id {} because: {}", game_id, e).as_str()),
+            }
+        }
     }
 
-    fn remove_inactive_ids(&mut self) {
+    /// Removes the ids that have not checked in within the player timeout and their players. The games are only locked if some ids were removed.
+    fn remove_inactive_ids(&self) {
         log!(self.logger, LogLevel::Debug, "Removing inactive ids!");
-        self.unique_ids
-            .retain(|(_, last_checkin)| last_checkin.elapsed() < PLAYER_TIMEOUT);
-        let remaining_ids = self.unique_ids.clone();
-        self.games.iter_mut().for_each(|game| {
-            game.players
-                .retain(|player| remaining_ids.iter().any(|(id, _)| &player.unique_id == id));
-        });
+        let (remaining_ids, disconnected_ids, has_removed_ids) = {
+            let Ok(mut unique_ids) = self.lock_unique_ids() else {
+                return;
+            };
+            let id_amount = unique_ids.len();
+            unique_ids.retain(|(_, last_checkin)| last_checkin.elapsed() < self.player_timeout + self.disconnect_grace_period);
+            let disconnected_ids: Vec<PlayerID> = unique_ids
+                .iter()
+                .filter(|(_, last_checkin)| last_checkin.elapsed() >= self.player_timeout)
+                .map(|(id, _)| *id)
+                .collect();
+            (unique_ids.clone(), disconnected_ids, unique_ids.len() != id_amount)
+        };
+        for player_id in disconnected_ids {
+            self.set_player_disconnected(player_id, true);
+        }
+        if !has_removed_ids {
+            return;
+        }
+        if let Ok(mut player_tokens) = self.lock_player_tokens() {
+            player_tokens.retain(|player_id, _| remaining_ids.iter().any(|(id, _)| id == player_id));
+        }
+        if let Ok(mut reconnect_codes) = self.lock_reconnect_codes() {
+            reconnect_codes.retain(|player_id, _| remaining_ids.iter().any(|(id, _)| id == player_id));
+        }
+        for shared_game in self.get_all_shared_games() {
+            let game_to_store = {
+                let Ok(mut game) = Self::lock_game(&shared_game) else {
+                    continue;
+                };
+                let removed_player_ids: Vec<PlayerID> = game
+                    .players
+                    .iter()
+                    .map(|player| player.unique_id)
+                    .filter(|player_id| remaining_ids.iter().all(|(id, _)| id != player_id))
+                    .collect();
+                let removed_spectator_ids: Vec<PlayerID> = game
+                    .spectators
+                    .iter()
+                    .map(|spectator| spectator.unique_id)
+                    .filter(|spectator_id| remaining_ids.iter().all(|(id, _)| id != spectator_id))
+                    .collect();
+                if removed_player_ids.is_empty() && removed_spectator_ids.is_empty() {
+                    continue;
+                }
+                // The spectators are not part of the game that is replayed, so they are removed without an input in the action log.
+                for spectator_id in removed_spectator_ids {
+                    game.remove_spectator_with_id(spectator_id);
+                }
+                for player_id in removed_player_ids {
+                    // The player is removed as if they left the game, so that a replay of the game removes them at the same point.
+                    let leave_game = Self::server_input(player_id, game.id, PlayerInputType::LeaveGame);
+                    match Self::handle_input(leave_game.clone(), &mut game) {
+                        Ok(_) => Self::record_server_action(&mut game, leave_game),
+                        Err(e) => log!(self.logger, LogLevel::Error, format!("Failed to remove the inactive player with id {} from the game with id {} because: {}", player_id, game.id, e).as_str()),
+                    }
+                }
+                if game.players.is_empty() {
+                    continue;
+                }
+                self.skip_disconnected_turns(&mut game);
+                self.handle_updated_game(&game)
+            };
+            self.store_game(&game_to_store);
+        }
+        self.remove_empty_games();
         log!(self.logger, LogLevel::Debug, "Removed inactive ids!");
     }
 
-    fn change_role_player(input: PlayerInput, game: &mut GameState) -> Result<(), &str> {
+    /// Marks the player with the given id as disconnected or connected again in the games they are in. If it's the turn of a player that is disconnected, their turn is skipped with [`GameController::skip_disconnected_turns`].
+    fn set_player_disconnected(&self, player_id: PlayerID, is_disconnected: bool) {
+        for shared_game in self.get_all_shared_games() {
+            let game_to_store = {
+                let Ok(mut game) = Self::lock_game(&shared_game) else {
+                    continue;
+                };
+                if !game.set_player_disconnected(player_id, is_disconnected) {
+                    continue;
+                }
+                match is_disconnected {
+                    true => log!(self.logger, LogLevel::Info, format!("Player with id {} has not checked in and is disconnected from the game with id {}", player_id, game.id).as_str()),
+                    false => log!(self.logger, LogLevel::Info, format!("Player with id {} is connected to the game with id {} again", player_id, game.id).as_str()),
+                }
+                self.skip_disconnected_turns(&mut game);
+                self.handle_updated_game(&game)
+            };
+            self.store_game(&game_to_store);
+        }
+    }
+
+    /// Returns the ids of the games that have been updated since the last time this function was called, so that the clients following them can be notified.
+    pub fn take_updated_game_ids(&self) -> Vec<GameID> {
+        match self.updated_game_ids.lock() {
+            Ok(mut updated_game_ids) => std::mem::take(&mut *updated_game_ids),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Marks the game as updated and returns a copy of it that has to be stored with `store_game` once the lock of the game is released. The game is given already locked, so this never waits for the lock of the game or the map of games.
+    fn handle_updated_game(&self, game: &GameState) -> GameToStore {
+        if let Ok(mut updated_game_ids) = self.updated_game_ids.lock() {
+            if !updated_game_ids.contains(&game.id) {
+                updated_game_ids.push(game.id);
+            }
+        }
+        // The version is taken while the game is locked, so the copies of a game get higher versions in the order the game was updated.
+        GameToStore {
+            version: self.next_game_version.fetch_add(1, Ordering::SeqCst),
+            stored_version: self.get_stored_game_version(game.id),
+            game: game.clone(),
+        }
+    }
+
+    /// Stores the copy of the game, unless a newer copy of the game has already been stored or the game has been removed. Must not be called while the game is locked.
+    fn store_game(&self, game_to_store: &GameToStore) {
+        let game_id = game_to_store.game.id;
+        let Ok(mut stored_version) = game_to_store.stored_version.lock() else {
+            log!(self.logger, LogLevel::Error, format!("The stored version of the game with id {} is poisoned and the game can therefore not be stored!", game_id).as_str());
+            return;
+        };
+        if *stored_version >= game_to_store.version {
+            return;
+        }
+        match self.storage.save_game(&game_to_store.game) {
+            Ok(_) => {
+                *stored_version = game_to_store.version;
+                log!(self.logger, LogLevel::Debug, format!("Stored game with id: {}", game_id).as_str());
+            },
+            Err(e) => log!(self.logger, LogLevel::Error, format!("Failed to store game with id {} because: {}", game_id, e).as_str()),
+        }
+    }
+
+    /// Gets the lock of the stored version of the game with the given id, which has to be held while the game is written to or removed from the storage.
+    fn get_stored_game_version(&self, game_id: GameID) -> Arc<Mutex<u64>> {
+        match self.stored_game_versions.lock() {
+            Ok(mut stored_game_versions) => stored_game_versions.entry(game_id).or_default().clone(),
+            Err(poisoned) => poisoned.into_inner().entry(game_id).or_default().clone(),
+        }
+    }
+
+    fn read_games(&self) -> Result<RwLockReadGuard<'_, HashMap<GameID, SharedGame>>, GameError> {
+        match self.games.read() {
+            Ok(games) => Ok(games),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The map of games is poisoned and can not be read!");
+                Err(GameError::Internal("Failed to access the games on the server!".to_string()))
+            }
+        }
+    }
+
+    fn write_games(&self) -> Result<RwLockWriteGuard<'_, HashMap<GameID, SharedGame>>, GameError> {
+        match self.games.write() {
+            Ok(games) => Ok(games),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The map of games is poisoned and can not be written to!");
+                Err(GameError::Internal("Failed to access the games on the server!".to_string()))
+            }
+        }
+    }
+
+    fn lock_game(game: &SharedGame) -> Result<MutexGuard<'_, GameState>, GameError> {
+        match game.lock() {
+            Ok(game) => Ok(game),
+            Err(_) => Err(GameError::Internal("Failed to access the game because it is poisoned!".to_string())),
+        }
+    }
+
+    fn lock_unique_ids(&self) -> Result<MutexGuard<'_, Vec<(PlayerID, Instant)>>, GameError> {
+        match self.unique_ids.lock() {
+            Ok(unique_ids) => Ok(unique_ids),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The unique ids are poisoned and can not be accessed!");
+                Err(GameError::Internal("Failed to access the unique ids!".to_string()))
+            }
+        }
+    }
+
+    fn lock_player_tokens(&self) -> Result<MutexGuard<'_, HashMap<PlayerID, PlayerToken>>, GameError> {
+        match self.player_tokens.lock() {
+            Ok(player_tokens) => Ok(player_tokens),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The player tokens are poisoned and can not be accessed!");
+                Err(GameError::Internal("Failed to access the player tokens!".to_string()))
+            }
+        }
+    }
+
+    fn lock_reconnect_codes(&self) -> Result<MutexGuard<'_, HashMap<PlayerID, PlayerToken>>, GameError> {
+        match self.reconnect_codes.lock() {
+            Ok(reconnect_codes) => Ok(reconnect_codes),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The reconnect codes are poisoned and can not be accessed!");
+                Err(GameError::Internal("Failed to access the reconnect codes!".to_string()))
+            }
+        }
+    }
+
+    /// Puts the token and the reconnect code of the player in the game, so that they are stored with the game and the player can keep using them if the game is restored.
+    fn keep_player_token_in_game(&self, game: &mut GameState, player_id: PlayerID) {
+        if let Ok(player_tokens) = self.lock_player_tokens() {
+            if let Some(token) = player_tokens.get(&player_id) {
+                game.player_tokens.insert(player_id, token.clone());
+            }
+        }
+        if let Ok(reconnect_codes) = self.lock_reconnect_codes() {
+            if let Some(reconnect_code) = reconnect_codes.get(&player_id) {
+                game.reconnect_codes.insert(player_id, reconnect_code.clone());
+            }
+        }
+    }
+
+    fn lock_bots(&self) -> Result<MutexGuard<'_, Vec<Bot>>, GameError> {
+        match self.bots.lock() {
+            Ok(bots) => Ok(bots),
+            Err(_) => {
+                log!(self.logger, LogLevel::Error, "The bots are poisoned and can not be accessed!");
+                Err(GameError::Internal("Failed to access the bots!".to_string()))
+            }
+        }
+    }
+
+    fn get_shared_game(&self, game_id: GameID) -> Option<SharedGame> {
+        match self.read_games() {
+            Ok(games) => games.get(&game_id).cloned(),
+            Err(_) => None,
+        }
+    }
+
+    /// Gets a copy of the game with the given id where the actions that are waiting for the end of the turn have been applied.
+    fn get_game_with_applied_actions(&self, game_id: GameID) -> Result<GameState, GameError> {
+        let Some(shared_game) = self.get_shared_game(game_id) else {
+            return Err(GameError::NotFound(format!("There is no game with id {}!", game_id)));
+        };
+        let mut game_clone = Self::lock_game(&shared_game)?.clone();
+        Self::apply_game_actions(&mut game_clone)?;
+        Ok(game_clone)
+    }
+
+    fn get_all_shared_games(&self) -> Vec<SharedGame> {
+        match self.read_games() {
+            Ok(games) => games.values().cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn get_all_games(&self) -> Vec<GameState> {
+        self.get_all_shared_games()
+            .iter()
+            .filter_map(|game| Self::lock_game(game).ok().map(|game| game.clone()))
+            .collect()
+    }
+
+    fn is_player_id_registered(&self, player_id: PlayerID) -> bool {
+        match self.lock_unique_ids() {
+            Ok(unique_ids) => unique_ids.iter().any(|(id, _)| id == &player_id),
+            Err(_) => false,
+        }
+    }
+
+    fn change_role_player(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
         let Some(related_role) = input.related_role else {
-            return Err("There was no related role to change to!");
+            return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no related role to change to!".to_string()));
         };
         game.assign_player_role((input.player_id, related_role))
     }
 
-    fn generate_unused_unique_id(&mut self) -> Option<PlayerID> {
+    fn generate_unused_unique_id(&self, unique_ids: &[(PlayerID, Instant)]) -> Option<PlayerID> {
         log!(self.logger, LogLevel::Debug, "Generating unused unique id!");
         let mut id: PlayerID = rand::random::<PlayerID>();
         let mut found_unique_id = false;
         for _ in 0..100_000 {
             {
-                if !self.unique_ids.iter().any(|(l_id, _)| l_id == &id) {
+                if !unique_ids.iter().any(|(l_id, _)| l_id == &id) {
                     found_unique_id = true;
                     break;
                 }
@@ -288,64 +1199,103 @@ impl GameController {
     }
 
     fn create_new_game_and_assign_host(
-        &mut self,
+        &self,
+        games: &HashMap<GameID, SharedGame>,
         new_lobby: NewGameInfo,
-    ) -> Result<GameState, String> {
+    ) -> Result<GameState, GameError> {
         log!(self.logger, LogLevel::Debug, format!("Trying to create a new game with name {} and assigning host with id {}", new_lobby.name, new_lobby.host.unique_id).as_str());
-        if self
-            .unique_ids
-            .iter()
-            .all(|(id, _)| id != &new_lobby.host.unique_id)
-        {
+        if !self.is_player_id_registered(new_lobby.host.unique_id) {
             log!(self.logger, LogLevel::Error, "A player that has a unique ID that was not made by the server cannot create a lobby and can therefore not create a new game");
-            return Err("A player that has a unique ID that was not made by the server cannot create a lobby.".to_string());
+            return Err(GameError::NotFound("A player that has a unique ID that was not made by the server cannot create a lobby.".to_string()));
         }
 
-        for game in self.games.iter() {
-            if game.contains_player_with_unique_id(new_lobby.host.unique_id) {
+        for shared_game in games.values() {
+            if Self::lock_game(shared_game)?.contains_player_with_unique_id(new_lobby.host.unique_id) {
                 log!(self.logger, LogLevel::Error, "A player that is already connected to a game in progress cannot create a new game");
-                return Err("A player that is already connected to a game in progress cannot create a new game.".to_string());
+                return Err(GameError::Conflict("A player that is already connected to a game in progress cannot create a new game.".to_string()));
             }
         }
 
-        let mut new_game = GameState::new(new_lobby.name.clone(), self.generate_unused_game_id());
+        if let Err(e) = new_lobby.rule_set.validate(&self.rule_checker.get_rules_info()) {
+            log!(self.logger, LogLevel::Error, format!("The rule set of the new game is not valid because: {}", e).as_str());
+            return Err(e);
+        }
+
+        let settings = new_lobby.settings.apply_to(self.default_game_settings);
+        if let Err(e) = settings.validate() {
+            log!(self.logger, LogLevel::Error, format!("The settings of the new game are not valid because: {}", e).as_str());
+            return Err(e);
+        }
+
+        let mut new_game = GameState::new(new_lobby.name.clone(), self.generate_unused_game_id(games));
+        new_game.rule_set = new_lobby.rule_set.clone();
+        new_game.settings = settings;
+        if let Some(map_name) = new_lobby.map_name {
+            match new_game.set_map(map_name) {
+                Ok(_) => (),
+                Err(e) => {
+                    log!(self.logger, LogLevel::Error, format!("Failed to set the map of the new game because: {}", e).as_str());
+                    return Err(e.map_message(|message| format!("Failed to create new game because: {message}")));
+                },
+            }
+        }
         match new_game.assign_player_to_game(new_lobby.host.clone()) {
-            Ok(_) => (),
+            Ok(_) => self.keep_player_token_in_game(&mut new_game, new_lobby.host.unique_id),
             Err(e) => {
                 log!(self.logger, LogLevel::Error, format!("Failed to assign host with id {} to the new game because: {}", new_lobby.host.unique_id, e).as_str());
-                return Err(format!("Failed to create new game because: {e}"));
+                return Err(e.map_message(|message| format!("Failed to create new game because: {message}")));
             },
         };
         log!(self.logger, LogLevel::Info, format!("Created new game with name {} and assigned host with id {}", new_lobby.name, new_lobby.host.unique_id).as_str());
         Ok(new_game)
     }
 
-    fn generate_unused_game_id(&self) -> GameID {
+    fn generate_unused_game_id(&self, games: &HashMap<GameID, SharedGame>) -> GameID {
         log!(self.logger, LogLevel::Debug, "Trying to generate unused game id!");
-        let mut existing_game_ids = Vec::new();
-        for game in self.games.iter() {
-            existing_game_ids.push(game.id);
-        }
-
         let mut id = rand::random::<GameID>();
-        while existing_game_ids.contains(&id) {
+        while games.contains_key(&id) {
             id = rand::random::<GameID>();
         }
         log!(self.logger, LogLevel::Debug, format!("Generated unused game id: {}", id).as_str());
         id
     }
 
-    fn apply_game_actions(game: &mut GameState) -> Result<(), String> {
+    /// Returns an input the server makes on behalf of the player with the given unique id.
+    const fn server_input(player_id: PlayerID, game_id: GameID, input_type: PlayerInputType) -> PlayerInput {
+        PlayerInput {
+            player_id,
+            game_id,
+            input_type,
+            related_role: None,
+            related_node_id: None,
+            district_modifier: None,
+            situation_card_id: None,
+            edge_modifier: None,
+            related_bool: None,
+            related_path: None,
+            vehicle_id: 0,
+        }
+    }
+
+    /// Adds an input the server has handled on behalf of a player to the action log of the game, so that turns and replays count it like an input from the player.
+    fn record_server_action(game: &mut GameState, input: PlayerInput) {
+        let mut game_clone = game.clone();
+        if Self::apply_game_actions(&mut game_clone).is_ok() {
+            game.record_action(input, game_clone.state_hash());
+        }
+    }
+
+    fn apply_game_actions(game: &mut GameState) -> Result<(), GameError> {
         for action in game.actions.clone().iter() {
             match Self::apply_input(action.clone(), game) {
                 Ok(_) => (),
-                Err(e) => return Err(e + " No actions are applied to the game."),
+                Err(e) => return Err(e.map_message(|message| message + " No actions are applied to the game.")),
             };
         }
         Ok(())
     }
 
-    fn game_next_turn(game: &mut GameState) -> Result<(), String> {
+    fn game_next_turn(game: &mut GameState) -> Result<(), GameError> {
         let mut game_clone = game.clone();
         match Self::apply_game_actions(&mut game_clone) {
             Ok(_) => (),
@@ -357,7 +1307,19 @@ impl GameController {
         Ok(())
     }
 
-    fn add_action(input: PlayerInput, game: &mut GameState) -> Result<(), String> {
+    fn game_end(game: &mut GameState) -> Result<(), GameError> {
+        let mut game_clone = game.clone();
+        match Self::apply_game_actions(&mut game_clone) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+        std::mem::swap(game, &mut game_clone);
+        game.actions.clear();
+        game.end_game(GameEndReason::DeclaredByOrchestrator);
+        Ok(())
+    }
+
+    fn add_action(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
         let mut game_clone = game.clone();
         for action in game.actions.iter() {
             match Self::apply_input(action.clone(), &mut game_clone) {
@@ -373,13 +1335,15 @@ impl GameController {
         Ok(())
     }
 
-    fn handle_input(input: PlayerInput, game: &mut GameState) -> Result<(), String> {
+    fn handle_input(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
         if input.input_type == PlayerInputType::NextTurn {
             return Self::game_next_turn(game);
+        } else if input.input_type == PlayerInputType::EndGame {
+            return Self::game_end(game);
         } else if input.input_type == PlayerInputType::UndoAction {
             match game.actions.pop() {
                 Some(_) => return Ok(()),
-                None => return Err("There is no action to undo!".to_string()),
+                None => return Err(GameError::Conflict("There is no action to undo!".to_string())),
             }
         } else if input.input_type == PlayerInputType::ChangeRole
             || input.input_type == PlayerInputType::StartGame
@@ -395,7 +1359,7 @@ impl GameController {
         Self::add_action(input, game)
     }
 
-    fn apply_input(input: PlayerInput, game: &mut GameState) -> Result<(), String> {
+    fn apply_input(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
         match input.input_type {
             PlayerInputType::Movement => match Self::handle_movement(input, game) {
                 Ok(_) => Ok(()),
@@ -403,17 +1367,17 @@ impl GameController {
             },
             PlayerInputType::ChangeRole => match Self::change_role_player(input, game) {
                 Ok(_) => Ok(()),
-                Err(e) => Err(e.to_string()),
+                Err(e) => Err(e),
             },
             PlayerInputType::All => {
-                Err("This input type should not be used by players".to_string())
+                Err(GameError::InvalidInput(InvalidInputReason::MissingData, "This input type should not be used by players".to_string()))
             }
-            PlayerInputType::NextTurn => Err(
+            PlayerInputType::NextTurn | PlayerInputType::EndGame => Err(GameError::Internal(
                 "This is not an action that can be handled by GameController::apply_input!"
                     .to_string(),
-            ),
+            )),
             PlayerInputType::UndoAction => {
-                Err("This cannot be done in GameController::apply_input!".to_string())
+                Err(GameError::Internal("This cannot be done in GameController::apply_input!".to_string()))
             }
             PlayerInputType::ModifyDistrict => {
                 match Self::handle_district_restriction(input, game) {
@@ -427,17 +1391,10 @@ impl GameController {
             },
             PlayerInputType::AssignSituationCard => {
                 let Some(id) = input.situation_card_id else {
-                    return Err("There was no situation card id in the input, maybe deserialization problem?".to_string());
+                    return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no situation card id in the input, maybe deserialization problem?".to_string()));
                 };
-                match SituationCardList::get_default_situation_card_by_id(id) {
-                    Ok(card) => {
-                        game.situation_card = Some(card);
-                        match game.update_node_map_with_situation_card() {
-                            Ok(_) => (),
-                            Err(e) => return Err(e),
-                        }
-                        Ok(())
-                    }
+                match game.get_situation_card_by_id(id) {
+                    Ok(card) => game.assign_situation_card(card),
                     Err(e) => Err(e),
                 }
             }
@@ -447,7 +1404,7 @@ impl GameController {
             }
             PlayerInputType::ModifyEdgeRestrictions => {
                 let Some(edge_mod) = input.edge_modifier else {
-                    return Err("There was no park and ride modifier when wanting to modify park and ride!".to_string());
+                    return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no park and ride modifier when wanting to modify park and ride!".to_string()));
                 };
                 if edge_mod.delete {
                     return game.remove_restriction_from_edge(&edge_mod);
@@ -456,38 +1413,54 @@ impl GameController {
             }
             PlayerInputType::SetPlayerBusBool => {
                 let Some(boolean) = input.related_bool else {
-                    return Err("There was no bool to set the bus bool with!".to_string());
+                    return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no bool to set the bus bool with!".to_string()));
                 };
-                game.set_player_bus_bool(input.player_id, boolean);
+                game.set_player_bus_bool(input.player_id, input.vehicle_id, boolean);
                 Ok(())
             },
         }
     }
 
-    fn get_legal_nodes(&mut self, game: &mut GameState, player_id: PlayerID) {
-        log!(self.logger, LogLevel::Debug, format!("Getting legal nodes for player with id {}!", player_id).as_str());
-        let mut legal_nodes: Vec<NodeID> = Vec::new();
-
+    /// Puts the legal nodes of every vehicle of the player with the given id in the game, and the legal nodes of the vehicle with the given id in `legal_nodes`.
+    fn get_legal_nodes(&self, game: &mut GameState, player_id: PlayerID, vehicle_id: VehicleID) {
+        log!(self.logger, LogLevel::Debug, format!("Getting legal nodes for the vehicles of player with id {}!", player_id).as_str());
         let player =  match game.get_player_with_unique_id(player_id) {
             Ok(player) => player,
             Err(_) => {
                 return;
             },
         };
+        let legal_nodes_per_vehicle: Vec<Vec<NodeID>> = player
+            .vehicles
+            .iter()
+            .map(|vehicle| self.find_legal_nodes(game, &player, vehicle.id))
+            .collect();
+        game.legal_nodes = player
+            .vehicles
+            .iter()
+            .position(|vehicle| vehicle.id == vehicle_id)
+            .map(|index| legal_nodes_per_vehicle[index].clone())
+            .unwrap_or_default();
+        game.legal_nodes_per_vehicle = legal_nodes_per_vehicle;
+        log!(self.logger, LogLevel::Debug, format!("Got legal nodes for player with id {}!", player_id).as_str());
+    }
 
-        let Some(current_player_node_id) = player.position_node_id else {
-            return;
+    fn find_legal_nodes(&self, game: &GameState, player: &Player, vehicle_id: VehicleID) -> Vec<NodeID> {
+        let mut legal_nodes: Vec<NodeID> = Vec::new();
+
+        let Some(current_player_node_id) = player.get_vehicle(vehicle_id).ok().and_then(|vehicle| vehicle.position_node_id) else {
+            return legal_nodes;
         };
 
         let neighbouring_node_relationships = match game.map.get_neighbour_relationships_of_node_with_id(current_player_node_id) {
             Some(neighbours) => neighbours,
             None => {
-                return;
+                return legal_nodes;
             },
         };
 
         let Some(connected_game_id) = player.connected_game_id else {
-            return;
+            return legal_nodes;
         };
 
         for relationship in neighbouring_node_relationships {
@@ -500,21 +1473,47 @@ impl GameController {
                 related_node_id: Some(relationship.to), 
                 situation_card_id: None, 
                 edge_modifier: None, 
-                related_bool: None
+                related_bool: None,
+                related_path: None,
+                vehicle_id,
             };
             self.rule_checker.is_input_valid(game, &input).map_or_else(|| legal_nodes.push(relationship.to), |e| log!(self.logger, LogLevel::Debug, format!("Input was not valid because: {}", e).as_str()));
         }
-        game.legal_nodes = legal_nodes;
-        log!(self.logger, LogLevel::Debug, format!("Got legal nodes for player with id {}!", player_id).as_str());
+        legal_nodes
     }
 
-    fn handle_movement(input: PlayerInput, game: &mut GameState) -> Result<(), String> {
+    fn handle_movement(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
+        if let Some(path) = input.related_path {
+            if path.is_empty() {
+                return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "The movement path has no nodes!".to_string()));
+            }
+            for node_id in path {
+                Self::move_player_one_step(input.player_id, input.vehicle_id, node_id, game)?;
+            }
+            return Ok(());
+        }
         let Some(related_node_id) = input.related_node_id else {
-            return Err("There was no node related to the movement!".to_string());
+            return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no node related to the movement!".to_string()));
+        };
+        Self::move_player_one_step(input.player_id, input.vehicle_id, related_node_id, game)
+    }
+
+    fn move_player_one_step(player_id: PlayerID, vehicle_id: VehicleID, related_node_id: NodeID, game: &mut GameState) -> Result<(), GameError> {
+        let vehicle = match game.get_player_with_unique_id(player_id).and_then(|player| player.get_vehicle(vehicle_id).cloned()) {
+            Ok(vehicle) => vehicle,
+            Err(e) => return Err(e.map_message(|message| format!("Failed to move player because: {message}"))),
         };
-        match game.move_player_with_id(input.player_id, related_node_id) {
+        let Some(from_node_id) = vehicle.position_node_id else {
+            return Err(GameError::InvalidInput(InvalidInputReason::NoPosition, "Failed to move player because: The vehicle is not at any node!".to_string()));
+        };
+        match game.move_player_with_id(player_id, vehicle_id, related_node_id) {
+            Ok(_) => (),
+            Err(e) => return Err(e.map_message(|message| format!("Failed to move player because: {message}"))),
+        }
+
+        match game.record_movement_statistics(player_id, vehicle_id, from_node_id, vehicle.remaining_moves) {
             Ok(_) => (),
-            Err(e) => return Err(format!("Failed to move player because: {e}")),
+            Err(e) => return Err(e),
         }
 
         match game.update_objective_status() {
@@ -524,9 +1523,9 @@ impl GameController {
         Ok(())
     }
 
-    fn handle_district_restriction(input: PlayerInput, game: &mut GameState) -> Result<(), String> {
+    fn handle_district_restriction(input: PlayerInput, game: &mut GameState) -> Result<(), GameError> {
         let Some(district_modifier) = input.district_modifier else {
-            return Err("There was no district in the input modifier even though it was marked as a district input".to_string());
+            return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no district in the input modifier even though it was marked as a district input".to_string()));
         };
         if district_modifier.delete {
             return game.remove_district_modifier(district_modifier);
@@ -534,3 +1533,723 @@ impl GameController {
         game.add_district_modifier(district_modifier)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game_data::{custom_types::SituationCardID, structs::{game_settings::GameSettingsChanges, situation_card_definition::SituationCardFile}}, test_utils::{input, player, small_game, test_controller, MemoryStorage, NoLogger, PLAYER_ID}};
+
+    fn stored_game(game_id: GameID, host_id: PlayerID) -> GameState {
+        let mut game = GameState::new(format!("Game {}", game_id), game_id);
+        game.assign_player_to_game(Player::new(host_id, "Host".to_string())).unwrap();
+        game
+    }
+
+    fn new_game_info(host_id: PlayerID) -> NewGameInfo {
+        NewGameInfo {
+            host: Player::new(host_id, "Host".to_string()),
+            name: "Game".to_string(),
+            map_name: None,
+            rule_set: Default::default(),
+            settings: Default::default(),
+        }
+    }
+
+    /// Creates a lobby where the host is the orchestrator, and returns the ids of the lobby and the orchestrator.
+    fn lobby_with_orchestrator(controller: &GameController) -> (GameID, PlayerID) {
+        let host_id = controller.generate_player_id().unwrap();
+        let game = controller.create_new_game(new_game_info(host_id)).unwrap();
+        let mut change_role = input(host_id, game.id, PlayerInputType::ChangeRole);
+        change_role.related_role = Some(InGameID::Orchestrator);
+        controller.handle_player_input(change_role).unwrap();
+        (game.id, host_id)
+    }
+
+    fn custom_situation_card(card_id: SituationCardID) -> SituationCardDefinition {
+        let file: SituationCardFile = serde_json::from_str(include_str!("../resources/situation_cards.json")).unwrap();
+        let mut definition = file.situation_cards[0].clone();
+        definition.card_id = card_id;
+        definition
+    }
+
+    /// Creates a game with an orchestrator and the given amount of players, starts it with the first situation card of the server and ends the first turn of the orchestrator, so that it's the turn of the first player. Returns the id of the game and the unique ids of the orchestrator and the players.
+    fn start_game(controller: &GameController, player_amount: usize) -> (GameID, Vec<PlayerID>) {
+        start_game_with_settings(controller, player_amount, GameSettingsChanges::default())
+    }
+
+    /// Starts a game like [`start_game`], with the given changes to the default settings.
+    fn start_game_with_settings(controller: &GameController, player_amount: usize, settings: GameSettingsChanges) -> (GameID, Vec<PlayerID>) {
+        let player_ids: Vec<PlayerID> = (0..=player_amount).map(|_| controller.generate_player_credentials().unwrap().player_id).collect();
+        let mut new_game = new_game_info(player_ids[0]);
+        new_game.settings = settings;
+        let game_id = controller.create_new_game(new_game).unwrap().id;
+        for player_id in player_ids[1..].iter() {
+            controller.join_game(game_id, Player::new(*player_id, "Player".to_string())).unwrap();
+        }
+        let roles = [InGameID::Orchestrator, InGameID::PlayerOne, InGameID::PlayerTwo, InGameID::PlayerThree];
+        for (player_id, role) in player_ids.iter().zip(roles) {
+            let mut change_role = input(*player_id, game_id, PlayerInputType::ChangeRole);
+            change_role.related_role = Some(role);
+            controller.handle_player_input(change_role).unwrap();
+        }
+        let mut assign_situation_card = input(player_ids[0], game_id, PlayerInputType::AssignSituationCard);
+        assign_situation_card.situation_card_id = Some(1);
+        controller.handle_player_input(assign_situation_card).unwrap();
+        controller.handle_player_input(input(player_ids[0], game_id, PlayerInputType::StartGame)).unwrap();
+        controller.handle_player_input(input(player_ids[0], game_id, PlayerInputType::NextTurn)).unwrap();
+        (game_id, player_ids)
+    }
+
+    /// Makes the unique id look like it has not checked in for the given time.
+    fn set_time_since_check_in(controller: &GameController, player_id: PlayerID, time_since_check_in: Duration) {
+        let mut unique_ids = controller.unique_ids.lock().unwrap();
+        let (_, last_check_in) = unique_ids.iter_mut().find(|(id, _)| *id == player_id).unwrap();
+        *last_check_in = Instant::now().checked_sub(time_since_check_in).unwrap();
+    }
+
+    /// Makes the unique id look like it has not checked in for longer than `PLAYER_TIMEOUT`, so that the player is disconnected.
+    fn disconnect_player_id(controller: &GameController, player_id: PlayerID) {
+        set_time_since_check_in(controller, player_id, PLAYER_TIMEOUT);
+    }
+
+    /// Makes the unique id look like it has not checked in for longer than `PLAYER_TIMEOUT` and `DISCONNECT_GRACE_PERIOD`, so that the player is removed.
+    fn expire_player_id(controller: &GameController, player_id: PlayerID) {
+        set_time_since_check_in(controller, player_id, PLAYER_TIMEOUT + DISCONNECT_GRACE_PERIOD);
+    }
+
+    fn reconnect_code(controller: &GameController, player_id: PlayerID) -> PlayerToken {
+        controller.reconnect_codes.lock().unwrap()[&player_id].clone()
+    }
+
+    fn stored_players(storage: &MemoryStorage, game_id: GameID) -> Vec<PlayerID> {
+        let games = storage.games.lock().unwrap();
+        games[&game_id].players.iter().map(|player| player.unique_id).collect()
+    }
+
+    #[test]
+    fn stored_games_are_restored_with_their_players_and_unloadable_games_are_skipped() {
+        let mut storage = MemoryStorage::default();
+        storage.save_game(&stored_game(5, 9)).unwrap();
+        storage.unloadable_games.push("The game file is broken".to_string());
+        let controller = test_controller(storage);
+
+        assert_eq!(controller.restore_stored_games().unwrap(), 1);
+
+        assert_eq!(controller.get_game_by_id(5).unwrap().players[0].unique_id, 9);
+        assert!(controller.update_check_in_and_remove_inactive(9).is_ok());
+    }
+
+    #[test]
+    fn stored_game_does_not_replace_a_game_with_the_same_id() {
+        let storage = MemoryStorage::default();
+        storage.save_game(&stored_game(5, 9)).unwrap();
+        let controller = test_controller(storage);
+
+        assert_eq!(controller.restore_stored_games().unwrap(), 1);
+        assert_eq!(controller.restore_stored_games().unwrap(), 0);
+    }
+
+    #[test]
+    fn game_is_stored_again_after_every_change() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host_id = controller.generate_player_id().unwrap();
+        let guest_id = controller.generate_player_id().unwrap();
+
+        let game = controller.create_new_game(new_game_info(host_id)).unwrap();
+        assert_eq!(stored_players(&storage, game.id), vec![host_id]);
+        controller.join_game(game.id, Player::new(guest_id, "Guest".to_string())).unwrap();
+
+        assert_eq!(stored_players(&storage, game.id), vec![host_id, guest_id]);
+    }
+
+    #[test]
+    fn game_is_removed_from_the_storage_when_the_last_player_leaves() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host_id = controller.generate_player_id().unwrap();
+        let game = controller.create_new_game(new_game_info(host_id)).unwrap();
+
+        controller.handle_player_input(input(host_id, game.id, PlayerInputType::LeaveGame)).unwrap();
+
+        assert!(storage.games.lock().unwrap().is_empty());
+        assert!(controller.get_game_by_id(game.id).is_err());
+    }
+
+    #[test]
+    fn game_with_the_id_of_a_removed_game_is_stored() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host_id = controller.generate_player_id().unwrap();
+        let game = controller.create_new_game(new_game_info(host_id)).unwrap();
+        controller.handle_player_input(input(host_id, game.id, PlayerInputType::LeaveGame)).unwrap();
+        storage.save_game(&stored_game(game.id, host_id)).unwrap();
+        controller.restore_stored_games().unwrap();
+        storage.games.lock().unwrap().clear();
+        let guest_id = controller.generate_player_id().unwrap();
+
+        controller.join_game(game.id, Player::new(guest_id, "Guest".to_string())).unwrap();
+
+        assert_eq!(stored_players(&storage, game.id), vec![host_id, guest_id]);
+    }
+
+    #[test]
+    fn updated_games_are_only_taken_once() {
+        let controller = test_controller(MemoryStorage::default());
+        let host_id = controller.generate_player_id().unwrap();
+        let game = controller.create_new_game(NewGameInfo { host: Player::new(host_id, "Host".to_string()), name: "Updated".to_string(), map_name: None, rule_set: Default::default(), settings: Default::default() }).unwrap();
+        let player_id = controller.generate_player_id().unwrap();
+        controller.join_game(game.id, Player::new(player_id, "Player".to_string())).unwrap();
+
+        assert_eq!(controller.take_updated_game_ids(), vec![game.id]);
+        assert!(controller.take_updated_game_ids().is_empty());
+    }
+
+    #[test]
+    fn orchestrator_can_upload_a_situation_card_to_their_lobby() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let (game_id, orchestrator_id) = lobby_with_orchestrator(&controller);
+
+        let game = controller.assign_custom_situation_card(game_id, orchestrator_id, custom_situation_card(100)).unwrap();
+
+        assert_eq!(game.get_situation_card_by_id(100).unwrap().card_id, 100);
+        assert_eq!(game.situation_card.map(|card| card.card_id), Some(100));
+        let stored_card = storage.games.lock().unwrap()[&game_id].custom_situation_card.clone();
+        assert_eq!(stored_card.map(|card| card.card_id), Some(100));
+    }
+
+    #[test]
+    fn only_the_orchestrator_can_upload_a_situation_card() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = lobby_with_orchestrator(&controller);
+        let player_id = controller.generate_player_id().unwrap();
+        controller.join_game(game_id, Player::new(player_id, "Player".to_string())).unwrap();
+
+        let result = controller.assign_custom_situation_card(game_id, player_id, custom_situation_card(100));
+
+        assert!(matches!(result, Err(GameError::Forbidden(_))));
+
+        assert!(controller.get_game_by_id(game_id).unwrap().custom_situation_card.is_none());
+    }
+
+    #[test]
+    fn uploaded_situation_card_can_not_use_the_id_of_a_situation_card_on_the_server() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, orchestrator_id) = lobby_with_orchestrator(&controller);
+
+        let result = controller.assign_custom_situation_card(game_id, orchestrator_id, custom_situation_card(1));
+
+        assert!(matches!(result, Err(GameError::Conflict(_))));
+
+        assert!(controller.get_game_by_id(game_id).unwrap().custom_situation_card.is_none());
+    }
+
+    #[test]
+    fn situation_card_that_can_not_be_used_on_the_map_does_not_replace_the_uploaded_card() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, orchestrator_id) = lobby_with_orchestrator(&controller);
+        controller.assign_custom_situation_card(game_id, orchestrator_id, custom_situation_card(100)).unwrap();
+        let mut invalid_card = custom_situation_card(101);
+        invalid_card.objective_cards[0].drop_off_node_id = 200;
+
+        let result = controller.assign_custom_situation_card(game_id, orchestrator_id, invalid_card);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::InvalidSituationCard, _))));
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert_eq!(game.custom_situation_card.map(|card| card.card_id), Some(100));
+        assert_eq!(game.situation_card.map(|card| card.card_id), Some(100));
+    }
+
+    #[test]
+    fn replayed_game_has_the_state_hash_recorded_in_the_action_log() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let game = controller.get_game_by_id(game_id).unwrap();
+        let mut movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        movement.related_node_id = Some(game.legal_nodes[0]);
+        controller.handle_player_input(movement).unwrap();
+        controller.handle_player_input(input(player_ids[1], game_id, PlayerInputType::NextTurn)).unwrap();
+
+        let action_log = controller.get_action_log(game_id).unwrap();
+        let replayed_game = controller.get_game_state_at_turn(game_id, 2).unwrap();
+
+        assert_eq!(action_log.last().map(|entry| entry.turn), Some(1));
+        assert_eq!(action_log.last().map(|entry| entry.state_hash), Some(replayed_game.state_hash()));
+        assert!(matches!(controller.get_game_state_at_turn(game_id, 3), Err(GameError::NotFound(_))));
+    }
+
+    #[test]
+    fn removed_inactive_player_is_logged_and_removed_in_the_replay() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 2);
+        expire_player_id(&controller, player_ids[2]);
+
+        controller.update_check_in_and_remove_inactive(player_ids[0]).unwrap();
+        controller.handle_player_input(input(player_ids[1], game_id, PlayerInputType::NextTurn)).unwrap();
+
+        let action_log = controller.get_action_log(game_id).unwrap();
+        let removal = &action_log[action_log.len() - 2];
+        assert_eq!((removal.input.player_id, removal.input.input_type.clone()), (player_ids[2], PlayerInputType::LeaveGame));
+        let replayed_game = controller.get_game_state_at_turn(game_id, 2).unwrap();
+        assert_eq!(replayed_game.players.len(), 2);
+        assert_eq!(action_log.last().map(|entry| entry.state_hash), Some(replayed_game.state_hash()));
+    }
+
+    fn movement_along(path: Vec<NodeID>) -> PlayerInput {
+        let mut movement = input(PLAYER_ID, 1, PlayerInputType::Movement);
+        movement.related_path = Some(path);
+        movement
+    }
+
+    #[test]
+    fn movement_along_a_path_moves_through_every_node() {
+        let mut game = small_game();
+
+        GameController::handle_movement(movement_along(vec![1, 2, 3]), &mut game).unwrap();
+
+        let player = player(&game);
+        assert_eq!(player.vehicles[0].position_node_id, Some(3));
+        assert_eq!(player.vehicles[0].remaining_moves, game.get_starting_player_movement_value() - 3);
+        assert_eq!(player.statistics.moves_spent, 3);
+        assert!(matches!(player.objective_card, Some(card) if card.picked_package_up && card.dropped_package_off));
+    }
+
+    #[test]
+    fn movement_along_a_path_that_is_not_connected_is_an_error() {
+        let mut game = small_game();
+
+        let result = GameController::handle_movement(movement_along(vec![1, 3]), &mut game);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::NotNeighbour, _))));
+    }
+
+    #[test]
+    fn movement_along_an_empty_path_is_an_error() {
+        let mut game = small_game();
+
+        let result = GameController::handle_movement(movement_along(Vec::new()), &mut game);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::MissingData, _))));
+        assert_eq!(player(&game).vehicles[0].position_node_id, Some(0));
+    }
+
+    #[test]
+    fn bot_is_added_with_a_free_role_and_restored_with_its_strategy() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host_id = controller.generate_player_id().unwrap();
+        let game_id = controller.create_new_game(new_game_info(host_id)).unwrap().id;
+
+        let game = controller.add_bot(game_id, host_id, BotStrategy::Green).unwrap();
+
+        let bot_player = game.players.iter().find(|player| player.unique_id != host_id).unwrap();
+        assert_eq!(bot_player.in_game_id, InGameID::PlayerOne);
+        assert_eq!(bot_player.bot_strategy, Some(BotStrategy::Green));
+        let restored_controller = test_controller(storage);
+        restored_controller.restore_stored_games().unwrap();
+        assert_eq!(*restored_controller.bots.lock().unwrap(), vec![Bot::new(bot_player.unique_id, game_id, BotStrategy::Green)]);
+    }
+
+    #[test]
+    fn only_players_in_a_lobby_can_add_bots_to_it() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let outsider_id = controller.generate_player_id().unwrap();
+        let lobby_id = controller.create_new_game(new_game_info(outsider_id)).unwrap().id;
+
+        assert!(matches!(controller.add_bot(game_id, player_ids[0], BotStrategy::Greedy), Err(GameError::Conflict(_))));
+        assert!(matches!(controller.add_bot(lobby_id, player_ids[0], BotStrategy::Greedy), Err(GameError::Forbidden(_))));
+        assert!(controller.bots.lock().unwrap().is_empty());
+        assert_eq!(controller.get_game_by_id(lobby_id).unwrap().players.len(), 1);
+    }
+
+    #[test]
+    fn bot_moves_and_ends_its_turn() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, host_id) = lobby_with_orchestrator(&controller);
+        controller.add_bot(game_id, host_id, BotStrategy::CostAware).unwrap();
+        let mut assign_situation_card = input(host_id, game_id, PlayerInputType::AssignSituationCard);
+        assign_situation_card.situation_card_id = Some(1);
+        controller.handle_player_input(assign_situation_card).unwrap();
+        controller.handle_player_input(input(host_id, game_id, PlayerInputType::StartGame)).unwrap();
+        controller.handle_player_input(input(host_id, game_id, PlayerInputType::NextTurn)).unwrap();
+        let start_position = controller.get_game_by_id(game_id).unwrap().players[1].vehicles[0].position_node_id;
+
+        controller.play_bot_turns();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert_eq!(game.current_players_turn, InGameID::Orchestrator);
+        assert_ne!(game.players[1].vehicles[0].position_node_id, start_position);
+    }
+
+    #[test]
+    fn input_of_an_unknown_player_is_not_validated() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = start_game(&controller, 1);
+
+        let result = controller.validate_player_input(input(12345, game_id, PlayerInputType::NextTurn));
+
+        assert!(matches!(result, Err(GameError::NotFound(_))));
+    }
+
+    #[test]
+    fn validated_input_that_fails_to_be_handled_is_a_violation_and_changes_nothing() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let game = controller.get_game_by_id(game_id).unwrap();
+        let mut legal_movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        legal_movement.related_node_id = Some(game.legal_nodes[0]);
+        let mut illegal_movement = legal_movement.clone();
+        illegal_movement.related_node_id = Some(NodeID::MAX);
+
+        let legal_violations = controller.validate_player_input(legal_movement).unwrap();
+        let illegal_violations = controller.validate_player_input(illegal_movement).unwrap();
+
+        assert!(legal_violations.is_empty());
+        assert_eq!(illegal_violations.len(), 1);
+        assert_eq!(illegal_violations[0].rule, HANDLE_INPUT_RULE_NAME);
+        assert_eq!(controller.get_game_by_id(game_id).unwrap().state_hash(), game.state_hash());
+    }
+
+    #[test]
+    fn only_the_token_of_the_player_authenticates_them() {
+        let controller = test_controller(MemoryStorage::default());
+        let player = controller.generate_player_credentials().unwrap();
+        let other_player = controller.generate_player_credentials().unwrap();
+
+        assert!(controller.authenticate(player.player_id, Some(&player.token)).is_ok());
+        assert!(matches!(controller.authenticate(player.player_id, None), Err(GameError::Unauthorized(_))));
+        assert!(matches!(controller.authenticate(player.player_id, Some("wrong token")), Err(GameError::Unauthorized(_))));
+        assert!(matches!(controller.authenticate(player.player_id, Some(&other_player.token)), Err(GameError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn player_keeps_their_token_when_the_game_is_restored() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host = controller.generate_player_credentials().unwrap();
+        controller.create_new_game(new_game_info(host.player_id)).unwrap();
+
+        let restored_controller = test_controller(storage);
+        restored_controller.restore_stored_games().unwrap();
+
+        assert!(restored_controller.authenticate(host.player_id, Some(&host.token)).is_ok());
+    }
+
+    #[test]
+    fn new_game_gets_its_settings_on_top_of_the_default_settings() {
+        let controller = test_controller(MemoryStorage::default());
+        let host_id = controller.generate_player_id().unwrap();
+        let mut new_game = new_game_info(host_id);
+        new_game.settings = GameSettingsChanges { max_round_count: Some(3), ..GameSettingsChanges::default() };
+
+        let game = controller.create_new_game(new_game).unwrap();
+
+        assert_eq!(game.settings, GameSettings { max_round_count: Some(3), ..GameSettings::default() });
+    }
+
+    #[test]
+    fn new_game_with_invalid_settings_is_not_created() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let host_id = controller.generate_player_id().unwrap();
+        let mut new_game = new_game_info(host_id);
+        new_game.settings = GameSettingsChanges { max_player_count: Some(1), ..GameSettingsChanges::default() };
+
+        let result = controller.create_new_game(new_game);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::InvalidGameSettings, _))));
+        assert!(controller.get_created_games().is_empty());
+        assert!(storage.games.lock().unwrap().is_empty());
+    }
+
+    /// Sets when the current turn of the game ends.
+    fn set_turn_deadline(controller: &GameController, game_id: GameID, turn_deadline: Option<Instant>) {
+        let shared_game = controller.get_shared_game(game_id).unwrap();
+        let mut game = GameController::lock_game(&shared_game).unwrap();
+        game.settings.turn_time_limit_seconds = Some(60);
+        game.turn_deadline = turn_deadline;
+    }
+
+    #[test]
+    fn expired_turn_is_ended_for_the_player() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        set_turn_deadline(&controller, game_id, Instant::now().checked_sub(Duration::from_secs(1)));
+
+        controller.end_expired_turns();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert_eq!(game.current_players_turn, InGameID::Orchestrator);
+        assert!(!game.is_turn_time_up());
+        let action_log = controller.get_action_log(game_id).unwrap();
+        assert!(matches!(action_log.last(), Some(entry) if entry.input.player_id == player_ids[1] && entry.input.input_type == PlayerInputType::NextTurn));
+    }
+
+    fn player_position(controller: &GameController, game_id: GameID, player_id: PlayerID) -> Option<NodeID> {
+        controller.get_game_by_id(game_id).unwrap().get_player_with_unique_id(player_id).unwrap().vehicles[0].position_node_id
+    }
+
+    /// A rule checker that only lets the orchestrator end their turn, so that the turns of the players can only be ended without their actions.
+    struct OrchestratorEndsTurnsRuleChecker;
+
+    impl RuleChecker for OrchestratorEndsTurnsRuleChecker {
+        fn is_input_valid(&self, game: &GameState, input: &PlayerInput) -> Option<GameError> {
+            let is_orchestrator = matches!(game.get_player_with_unique_id(input.player_id), Ok(player) if player.in_game_id == InGameID::Orchestrator);
+            match input.input_type == PlayerInputType::NextTurn && !is_orchestrator {
+                true => Some(GameError::Forbidden("Only the orchestrator can end their turn".to_string())),
+                false => None,
+            }
+        }
+
+        fn get_rule_violations(&self, _game: &GameState, _input: &PlayerInput) -> Vec<RuleViolation> {
+            Vec::new()
+        }
+
+        fn get_rules_info(&self) -> Vec<RuleInfo> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn expired_turn_that_can_not_be_ended_as_normal_is_ended_without_the_actions_and_logged() {
+        let settings = GameSettings { turn_time_limit_seconds: Some(60), ..GameSettings::default() };
+        let controller = GameController::with_settings(Arc::new(RwLock::new(NoLogger)), Box::new(OrchestratorEndsTurnsRuleChecker), Box::new(MemoryStorage::default()), settings, PLAYER_TIMEOUT, DISCONNECT_GRACE_PERIOD);
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let start_position = player_position(&controller, game_id, player_ids[1]);
+        let mut movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        movement.related_node_id = Some(controller.get_game_by_id(game_id).unwrap().legal_nodes[0]);
+        controller.handle_player_input(movement).unwrap();
+        set_turn_deadline(&controller, game_id, Instant::now().checked_sub(Duration::from_secs(1)));
+
+        controller.end_expired_turns();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert_eq!(game.current_players_turn, InGameID::Orchestrator);
+        assert!(game.actions.is_empty());
+        assert_eq!(player_position(&controller, game_id, player_ids[1]), start_position);
+        let action_log = controller.get_action_log(game_id).unwrap();
+        let logged_inputs: Vec<PlayerInputType> = action_log[action_log.len() - 2..].iter().map(|entry| entry.input.input_type.clone()).collect();
+        assert_eq!(logged_inputs, vec![PlayerInputType::UndoAction, PlayerInputType::NextTurn]);
+        let replayed_game = controller.get_game_state_at_turn(game_id, 2).unwrap();
+        assert_eq!(action_log.last().map(|entry| entry.state_hash), Some(replayed_game.state_hash()));
+    }
+
+    #[test]
+    fn turn_with_time_left_is_not_ended() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = start_game(&controller, 1);
+        set_turn_deadline(&controller, game_id, Instant::now().checked_add(Duration::from_secs(60)));
+
+        controller.end_expired_turns();
+
+        assert_eq!(controller.get_game_by_id(game_id).unwrap().current_players_turn, InGameID::PlayerOne);
+    }
+
+    #[test]
+    fn turn_timer_is_started_for_a_game_without_one() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = start_game(&controller, 1);
+        set_turn_deadline(&controller, game_id, None);
+
+        controller.end_expired_turns();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert_eq!(game.current_players_turn, InGameID::PlayerOne);
+        assert!(game.turn_deadline.is_some());
+    }
+
+    #[test]
+    fn reconnect_needs_the_reconnect_code_of_the_player() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let token = controller.player_tokens.lock().unwrap()[&player_ids[1]].clone();
+
+        let without_code = controller.reconnect_player(game_id, player_ids[1], None);
+        let with_wrong_code = controller.reconnect_player(game_id, player_ids[1], Some("wrong code"));
+        let with_code_of_other_player = controller.reconnect_player(game_id, player_ids[1], Some(&reconnect_code(&controller, player_ids[0])));
+
+        assert!(matches!(without_code, Err(GameError::Unauthorized(_))));
+        assert!(matches!(with_wrong_code, Err(GameError::Unauthorized(_))));
+        assert!(matches!(with_code_of_other_player, Err(GameError::Unauthorized(_))));
+        assert!(controller.authenticate(player_ids[1], Some(&token)).is_ok());
+    }
+
+    #[test]
+    fn reconnect_replaces_the_token_of_the_player() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let old_token = controller.player_tokens.lock().unwrap()[&player_ids[1]].clone();
+        let code = reconnect_code(&controller, player_ids[1]);
+
+        let credentials = controller.reconnect_player(game_id, player_ids[1], Some(&code)).unwrap();
+
+        assert_ne!(credentials.token, old_token);
+        assert_eq!(credentials.reconnect_code, code);
+        assert!(controller.authenticate(player_ids[1], Some(&credentials.token)).is_ok());
+        assert!(matches!(controller.authenticate(player_ids[1], Some(&old_token)), Err(GameError::Unauthorized(_))));
+        assert_eq!(storage.games.lock().unwrap()[&game_id].player_tokens.get(&player_ids[1]), Some(&credentials.token));
+    }
+
+    #[test]
+    fn reconnect_to_a_game_without_the_player_is_forbidden() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = start_game(&controller, 1);
+        let outsider = controller.generate_player_credentials().unwrap();
+
+        let result = controller.reconnect_player(game_id, outsider.player_id, Some(&outsider.reconnect_code));
+
+        assert!(matches!(result, Err(GameError::Forbidden(_))));
+    }
+
+    #[test]
+    fn disconnected_player_keeps_their_seat_during_the_grace_period() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 2);
+        disconnect_player_id(&controller, player_ids[2]);
+
+        controller.update_check_in_and_remove_inactive(player_ids[0]).unwrap();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert!(game.get_player_with_unique_id(player_ids[2]).unwrap().is_disconnected);
+        controller.reconnect_player(game_id, player_ids[2], Some(&reconnect_code(&controller, player_ids[2]))).unwrap();
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert!(!game.get_player_with_unique_id(player_ids[2]).unwrap().is_disconnected);
+    }
+
+    #[test]
+    fn player_is_removed_when_the_grace_period_is_over_and_a_new_orchestrator_is_chosen() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let code = reconnect_code(&controller, player_ids[0]);
+        let token = controller.player_tokens.lock().unwrap()[&player_ids[0]].clone();
+        expire_player_id(&controller, player_ids[0]);
+
+        controller.update_check_in_and_remove_inactive(player_ids[1]).unwrap();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert!(game.get_player_with_unique_id(player_ids[0]).is_err());
+        assert_eq!(game.get_player_with_unique_id(player_ids[1]).unwrap().in_game_id, InGameID::Orchestrator);
+        assert!(controller.reconnect_player(game_id, player_ids[0], Some(&code)).is_err());
+        assert!(controller.authenticate(player_ids[0], Some(&token)).is_err());
+    }
+
+    #[test]
+    fn skipped_turn_of_disconnected_player_is_logged_and_skipped_in_the_replay() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 2);
+        disconnect_player_id(&controller, player_ids[2]);
+        controller.update_check_in_and_remove_inactive(player_ids[1]).unwrap();
+
+        controller.handle_player_input(input(player_ids[1], game_id, PlayerInputType::NextTurn)).unwrap();
+
+        let action_log = controller.get_action_log(game_id).unwrap();
+        let skipped_turn = action_log.last().unwrap();
+        assert_eq!((skipped_turn.input.player_id, skipped_turn.input.input_type.clone()), (player_ids[2], PlayerInputType::NextTurn));
+        let replayed_game = controller.get_game_state_at_turn(game_id, skipped_turn.turn + 1).unwrap();
+        assert_eq!(replayed_game.current_players_turn, InGameID::Orchestrator);
+        assert_eq!(replayed_game.state_hash(), skipped_turn.state_hash);
+        assert_eq!(controller.get_game_by_id(game_id).unwrap().current_players_turn, InGameID::Orchestrator);
+    }
+
+    #[test]
+    fn turn_of_player_that_disconnects_is_ended_with_their_actions() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 2);
+        let target_node_id = controller.get_game_by_id(game_id).unwrap().legal_nodes[0];
+        let mut movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        movement.related_node_id = Some(target_node_id);
+        controller.handle_player_input(movement).unwrap();
+        disconnect_player_id(&controller, player_ids[1]);
+
+        controller.update_check_in_and_remove_inactive(player_ids[0]).unwrap();
+
+        assert_eq!(controller.get_game_by_id(game_id).unwrap().current_players_turn, InGameID::PlayerTwo);
+        assert_eq!(player_position(&controller, game_id, player_ids[1]), Some(target_node_id));
+    }
+
+    #[test]
+    fn spectator_follows_a_running_game_without_taking_a_seat() {
+        let storage = MemoryStorage::default();
+        let controller = test_controller(storage.clone());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let spectator_id = controller.generate_player_credentials().unwrap().player_id;
+
+        let game = controller.join_game_as_spectator(game_id, Spectator::new(spectator_id, "Spectator".to_string())).unwrap();
+
+        assert_eq!(game.players.len(), player_ids.len());
+        assert!(game.can_be_followed_by(spectator_id));
+        assert!(!game.contains_player_with_unique_id(spectator_id));
+        assert_eq!(storage.games.lock().unwrap()[&game_id].spectators.len(), 1);
+        assert!(matches!(controller.join_game_as_spectator(game_id, Spectator::new(spectator_id, "Spectator".to_string())), Err(GameError::Conflict(_))));
+    }
+
+    #[test]
+    fn spectator_can_only_leave_the_game() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, _) = start_game(&controller, 1);
+        let spectator_id = controller.generate_player_credentials().unwrap().player_id;
+        controller.join_game_as_spectator(game_id, Spectator::new(spectator_id, "Spectator".to_string())).unwrap();
+        let action_log_length = controller.get_action_log(game_id).unwrap().len();
+
+        let next_turn = controller.handle_player_input(input(spectator_id, game_id, PlayerInputType::NextTurn));
+        let leave_game = controller.handle_player_input(input(spectator_id, game_id, PlayerInputType::LeaveGame));
+
+        assert!(matches!(next_turn, Err(GameError::Forbidden(_))));
+        assert!(leave_game.unwrap().spectators.is_empty());
+        assert_eq!(controller.get_action_log(game_id).unwrap().len(), action_log_length);
+    }
+
+    #[test]
+    fn inactive_spectator_is_removed_without_an_input_in_the_action_log() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let spectator_id = controller.generate_player_credentials().unwrap().player_id;
+        controller.join_game_as_spectator(game_id, Spectator::new(spectator_id, "Spectator".to_string())).unwrap();
+        let action_log_length = controller.get_action_log(game_id).unwrap().len();
+        expire_player_id(&controller, spectator_id);
+
+        controller.update_check_in_and_remove_inactive(player_ids[0]).unwrap();
+
+        let game = controller.get_game_by_id(game_id).unwrap();
+        assert!(game.spectators.is_empty());
+        assert_eq!(game.players.len(), player_ids.len());
+        assert_eq!(controller.get_action_log(game_id).unwrap().len(), action_log_length);
+    }
+
+    #[test]
+    fn every_vehicle_of_the_fleet_gets_its_own_legal_nodes_and_moves_on_its_own() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game_with_settings(&controller, 1, GameSettingsChanges { vehicles_per_player: Some(2), ..GameSettingsChanges::default() });
+        let game = controller.get_game_by_id(game_id).unwrap();
+        let start_node_id = game.get_player_with_unique_id(player_ids[1]).unwrap().vehicles[0].position_node_id;
+        assert_eq!(game.legal_nodes_per_vehicle.len(), 2);
+        let mut movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        movement.vehicle_id = 1;
+        movement.related_node_id = Some(game.legal_nodes_per_vehicle[1][0]);
+
+        let game = controller.handle_player_input(movement).unwrap();
+
+        let vehicles = game.get_player_with_unique_id(player_ids[1]).unwrap().vehicles;
+        assert_eq!(vehicles[0].position_node_id, start_node_id);
+        assert_ne!(vehicles[1].position_node_id, start_node_id);
+        assert_eq!(game.legal_nodes, game.legal_nodes_per_vehicle[1]);
+    }
+
+    #[test]
+    fn movement_of_a_vehicle_the_player_does_not_have_is_an_error() {
+        let controller = test_controller(MemoryStorage::default());
+        let (game_id, player_ids) = start_game(&controller, 1);
+        let game = controller.get_game_by_id(game_id).unwrap();
+        let mut movement = input(player_ids[1], game_id, PlayerInputType::Movement);
+        movement.vehicle_id = 1;
+        movement.related_node_id = Some(game.legal_nodes[0]);
+
+        let result = controller.handle_player_input(movement);
+
+        assert!(matches!(result, Err(GameError::NotFound(_))));
+    }
+}
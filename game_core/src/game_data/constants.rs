@@ -1,11 +1,16 @@
 use std::time::Duration;
 
-use super::custom_types::MovementValue;
+use super::custom_types::{MovementValue, Money};
 
 pub const MAX_PLAYER_COUNT: usize = 7;
 pub const MAX_TOLL_MODIFIER_COUNT: usize = 1;
 pub const MAX_ACCESS_MODIFIER_COUNT: usize = 2;
 pub const MAX_PRIORITY_MODIFIER_COUNT: usize = 2;
 pub const START_MOVEMENT_AMOUNT: MovementValue = 8;
+pub const START_MONEY_AMOUNT: Money = 20;
 pub const HEAVY_VEHICLE_INCLUSIVE_THRESHOLD: u32 = 5;
-pub const PLAYER_TIMEOUT: Duration = Duration::from_secs(90);
\ No newline at end of file
+pub const MAX_TURN_TIME_LIMIT_SECONDS: u64 = 24 * 60 * 60;
+pub const VEHICLE_COUNT: usize = 1;
+pub const MAX_VEHICLE_COUNT: usize = 4;
+pub const PLAYER_TIMEOUT: Duration = Duration::from_secs(90);
+pub const DISCONNECT_GRACE_PERIOD: Duration = Duration::from_secs(300);
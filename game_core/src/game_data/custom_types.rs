@@ -10,4 +10,6 @@ pub type MovesRemaining = MovementCost;
 pub type Money = i32;
 pub type SituationCardID = u8;
 pub type VehicleType = RestrictionType;
-pub type ErrorData = String;
\ No newline at end of file
+pub type VehicleID = u8;
+pub type ErrorData = String;
+pub type PlayerToken = String;
\ No newline at end of file
@@ -4,6 +4,10 @@
 pub mod district_modifier_type;
 /// The district module contains the District enum which contains all the districts.
 pub mod district;
+/// The game_error module contains the GameError enum which describes why something could not be done in a game, and the InvalidInputReason enum which tells which rule an invalid input breaks.
+pub mod game_error;
+/// The game_end_reason module contains the GameEndReason enum which contains all the reasons a game can end.
+pub mod game_end_reason;
 /// The in_game_id module contains the InGameID enum which contains all the in game ids. An in game id is an id that is used in the game to identify which player's turn it is and who is the orchestrator.
 pub mod in_game_id;
 /// The player_input_type module contains the PlayerInputType enum which contains all the player input types.
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GameEndReason {
+    AllObjectivesDelivered,
+    RoundLimitReached,
+    DeclaredByOrchestrator,
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The GameError enum describes why something could not be done in a game.
+///
+/// Every error has a message that describes what went wrong, and a machine readable code (see [`GameError::code`]) that a client can use to tell the errors apart, for example to show the error in the language of the player.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameError {
+    /// A game, player, node or something else that was asked for does not exist.
+    NotFound(String),
+    /// The request does not have the token of the player it is made for, so it can not be known that the request is made by the player.
+    Unauthorized(String),
+    /// The player is not allowed to do this, for example because only the orchestrator can do it.
+    Forbidden(String),
+    /// It is not the player's turn.
+    NotYourTurn(String),
+    /// The input breaks a rule of the game, the reason tells which.
+    InvalidInput(InvalidInputReason, String),
+    /// The input can not be done in the current state of the game, for example because the game has not started yet or is full.
+    Conflict(String),
+    /// Something went wrong on the server.
+    Internal(String),
+}
+
+/// The InvalidInputReason enum tells which rule an invalid input breaks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvalidInputReason {
+    /// A value the input type needs is not set in the input.
+    MissingData,
+    NoPosition,
+    NotNeighbour,
+    NotEnoughMoves,
+    NotEnoughMoney,
+    /// The player is not allowed to move along the edge, because of a restriction on it, the railway or an access modifier on the district.
+    MovementNotAllowed,
+    BusNotAllowed,
+    EdgeModificationNotAllowed,
+    DistrictModificationNotAllowed,
+    InvalidSituationCard,
+    InvalidMap,
+    InvalidRuleSet,
+    InvalidGameSettings,
+}
+
+impl GameError {
+    /// Returns the machine readable code of the error, for example `not_found` or `invalid_input.not_enough_moves`.
+    pub fn code(&self) -> String {
+        match self {
+            Self::NotFound(_) => "not_found".to_string(),
+            Self::Unauthorized(_) => "unauthorized".to_string(),
+            Self::Forbidden(_) => "forbidden".to_string(),
+            Self::NotYourTurn(_) => "not_your_turn".to_string(),
+            Self::InvalidInput(reason, _) => format!("invalid_input.{}", reason.code()),
+            Self::Conflict(_) => "conflict".to_string(),
+            Self::Internal(_) => "internal".to_string(),
+        }
+    }
+
+    /// Returns the message that describes the error.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::Unauthorized(message)
+            | Self::Forbidden(message)
+            | Self::NotYourTurn(message)
+            | Self::InvalidInput(_, message)
+            | Self::Conflict(message)
+            | Self::Internal(message) => message,
+        }
+    }
+
+    /// Returns the same error with a new message made from the old one, so that more can be said about where the error happened without losing what kind of error it is.
+    #[must_use]
+    pub fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            Self::NotFound(message) => Self::NotFound(f(message)),
+            Self::Unauthorized(message) => Self::Unauthorized(f(message)),
+            Self::Forbidden(message) => Self::Forbidden(f(message)),
+            Self::NotYourTurn(message) => Self::NotYourTurn(f(message)),
+            Self::InvalidInput(reason, message) => Self::InvalidInput(reason, f(message)),
+            Self::Conflict(message) => Self::Conflict(f(message)),
+            Self::Internal(message) => Self::Internal(f(message)),
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl InvalidInputReason {
+    /// Returns the machine readable code of the reason, for example `not_enough_moves`.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::MissingData => "missing_data",
+            Self::NoPosition => "no_position",
+            Self::NotNeighbour => "not_neighbour",
+            Self::NotEnoughMoves => "not_enough_moves",
+            Self::NotEnoughMoney => "not_enough_money",
+            Self::MovementNotAllowed => "movement_not_allowed",
+            Self::BusNotAllowed => "bus_not_allowed",
+            Self::EdgeModificationNotAllowed => "edge_modification_not_allowed",
+            Self::DistrictModificationNotAllowed => "district_modification_not_allowed",
+            Self::InvalidSituationCard => "invalid_situation_card",
+            Self::InvalidMap => "invalid_map",
+            Self::InvalidRuleSet => "invalid_rule_set",
+            Self::InvalidGameSettings => "invalid_game_settings",
+        }
+    }
+}
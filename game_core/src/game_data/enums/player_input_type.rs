@@ -12,4 +12,5 @@ pub enum PlayerInputType {
     LeaveGame,
     ModifyEdgeRestrictions,
     SetPlayerBusBool,
+    EndGame,
 }
\ No newline at end of file
@@ -1,13 +1,23 @@
 //! Contains most the structs used in the game.
 
+/// The action_log module contains the ActionLog struct which keeps every accepted player input of a game.
+pub mod action_log;
 /// The cost_tuple module contains the CostTuple struct which describes the Traffic in a District.
 pub mod cost_tuple;
 /// The district_modifier module contains the DistrictModifier struct which describes a DistrictModifier.
 pub mod district_modifier;
 /// The edge_restriction module contains the EdgeRestriction struct which describes an EdgeRestriction.
 pub mod edge_restriction;
+/// The game_results module contains the GameResults struct which describes the final results of a game.
+pub mod game_results;
+/// The game_settings module contains the GameSettings struct which describes the game balance values of a game.
+pub mod game_settings;
+/// The game_snapshot module contains the GameSnapshot struct which is used to store a game and restore it later.
+pub mod game_snapshot;
 /// The game_state module contains the GameState struct which describes the state of the game.
 pub mod gamestate;
+/// The map_definition module contains the MapDefinition struct which describes a board map as it is written in a map file.
+pub mod map_definition;
 /// The neighbour_relationship module contains the NeighbourRelationship struct which describes the relationship between two nodes.
 pub mod neighbour_relationship;
 /// The new_game_info module contains the NewGameInfo struct which describes the information needed to create a new game.
@@ -16,15 +26,35 @@ pub mod new_game_info;
 pub mod node_map;
 /// The node module contains the Node struct which describes a node.
 pub mod node;
+/// The player_credentials module contains the PlayerCredentials struct which contains a player id and the token that belongs to it.
+pub mod player_credentials;
 /// The player_input module contains the PlayerInput struct which describes the input of a player.
 pub mod player_input;
 /// The player_objective_card module contains the PlayerObjectiveCard struct which describes a player objective card.
 pub mod player_objective_card;
 /// The player module contains the Player struct which describes a player.
 pub mod player;
+/// The player_score module contains the PlayerScore struct which describes how well a player did in a finished game.
+pub mod player_score;
+/// The player_statistics module contains the PlayerStatistics struct which keeps track of what a player has done during a game.
+pub mod player_statistics;
+/// The route module contains the Route struct which describes the cheapest way for a player to move to a node.
+pub mod route;
+/// The rule_info module contains the RuleInfo struct which describes a rule of the rule checker.
+pub mod rule_info;
+/// The rule_set module contains the RuleSet struct which describes which rules are used in a game.
+pub mod rule_set;
+/// The rule_violation module contains the RuleViolation struct which describes a rule of the game that a player input breaks.
+pub mod rule_violation;
 /// The situation_card_list module contains the SituationCardList struct which describes a list of situation cards.
 pub mod situation_card_list;
+/// The situation_card_definition module contains the SituationCardDefinition struct which describes a situation card as it is written in a data file.
+pub mod situation_card_definition;
 /// The situation_card module contains the SituationCard struct which describes a situation card for the game, it also includes [`PlayerObjectiveCard`].
 /// 
 /// [`PlayerObjectiveCard`]: ./player_objective_card/struct.PlayerObjectiveCard.html
-pub mod situation_card;
\ No newline at end of file
+pub mod situation_card;
+/// The spectator module contains the Spectator struct which describes someone that follows a game without playing it.
+pub mod spectator;
+/// The vehicle module contains the Vehicle struct which describes one of the vehicles in the fleet of a player.
+pub mod vehicle;
\ No newline at end of file
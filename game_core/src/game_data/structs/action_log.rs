@@ -0,0 +1,65 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::enums::player_input_type::PlayerInputType;
+
+use super::{game_snapshot::GameSnapshot, gamestate::GameState, player_input::PlayerInput};
+
+/// The ActionLogEntry struct describes an accepted player input in the action log of a game.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ActionLogEntry {
+    /// The turn the input was made in, counted from when the game was started. Inputs made before the game was started have turn 0.
+    pub turn: u32,
+    /// The time the input was accepted, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub input: PlayerInput,
+    /// A hash of the game state after the input was handled. It is used to check that a replay of the game gives the same result.
+    pub state_hash: u64,
+}
+
+/// The ActionLog struct keeps every accepted player input of a game, so that the game can be looked through and replayed after it is played.
+/// 
+/// The game state right after the game was last started is kept as well, since the game can only be replayed from there. The objective cards are given out randomly when the game starts, so the inputs before that can not be replayed.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct ActionLog {
+    pub entries: Vec<ActionLogEntry>,
+    /// The game right after it was last started.
+    pub start_state: Option<Box<GameSnapshot>>,
+    /// The index of the first entry after the game was last started.
+    pub start_index: usize,
+    /// The amount of turns that have been played since the game was last started.
+    pub turns_played: u32,
+}
+
+impl ActionLog {
+    /// Adds the input to the log. The game should be the game right after the input was handled, and the state hash the hash of the game state the players see. Use [`GameState::record_action`] to add inputs to the log of a game.
+    /// 
+    /// [`GameState::record_action`]: ../gamestate/struct.GameState.html#method.record_action
+    pub fn record(&mut self, input: PlayerInput, game: &GameState, state_hash: u64) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        let input_type = input.input_type.clone();
+        self.entries.push(ActionLogEntry {
+            turn: self.turns_played,
+            timestamp,
+            input,
+            state_hash,
+        });
+        match input_type {
+            PlayerInputType::StartGame => {
+                self.start_state = Some(Box::new(GameSnapshot::from_game(game)));
+                self.start_index = self.entries.len();
+                self.turns_played = 0;
+            }
+            PlayerInputType::NextTurn => self.turns_played += 1,
+            _ => (),
+        }
+    }
+
+    /// Returns the entries of the inputs made after the game was last started.
+    pub fn entries_since_start(&self) -> &[ActionLogEntry] {
+        &self.entries[self.start_index..]
+    }
+}
@@ -2,11 +2,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::game_data::{custom_types::NodeID, enums::restriction_type::RestrictionType};
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct EdgeRestriction {
     pub node_one: NodeID,
     pub node_two: NodeID,
     pub edge_restriction: RestrictionType,
+    #[serde(default)]
     pub delete: bool,
 }
 
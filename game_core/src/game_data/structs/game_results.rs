@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{custom_types::GameID, enums::game_end_reason::GameEndReason};
+
+use super::player_score::PlayerScore;
+
+/// The GameResults struct describes the final results of a game that has ended.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct GameResults {
+    pub game_id: GameID,
+    pub reason: GameEndReason,
+    pub rounds_played: u32,
+    pub player_scores: Vec<PlayerScore>,
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{enums::game_error::{GameError, InvalidInputReason}, custom_types::{MovementValue, Money}, constants::{MAX_PLAYER_COUNT, MAX_TOLL_MODIFIER_COUNT, MAX_ACCESS_MODIFIER_COUNT, MAX_PRIORITY_MODIFIER_COUNT, START_MOVEMENT_AMOUNT, START_MONEY_AMOUNT, MAX_TURN_TIME_LIMIT_SECONDS, VEHICLE_COUNT, MAX_VEHICLE_COUNT}};
+
+/// The GameSettings struct describes the game balance values of a game. The default values are the values in [`constants`](../../constants/index.html), but the server can be configured with other default values and every game can be given its own values when it is created.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct GameSettings {
+    /// The max amount of players in the game, including the orchestrator.
+    pub max_player_count: usize,
+    pub max_toll_modifier_count: usize,
+    pub max_access_modifier_count: usize,
+    pub max_priority_modifier_count: usize,
+    /// The amount of moves the players get at the start of every round.
+    pub start_movement_amount: MovementValue,
+    pub start_money_amount: Money,
+    /// The amount of rounds after which the game ends. If it is `None`, the game has no round limit.
+    pub max_round_count: Option<u32>,
+    /// How many seconds a player has to do their turn, at most `MAX_TURN_TIME_LIMIT_SECONDS`. There is no limit if it's not set.
+    pub turn_time_limit_seconds: Option<u64>,
+    /// If the players can see the objective cards of each other. The orchestrator can always see every objective card, and every objective card is shown when the game is over.
+    pub reveal_objective_cards: bool,
+    /// The amount of vehicles every player gets when the game starts.
+    pub vehicles_per_player: usize,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            max_player_count: MAX_PLAYER_COUNT,
+            max_toll_modifier_count: MAX_TOLL_MODIFIER_COUNT,
+            max_access_modifier_count: MAX_ACCESS_MODIFIER_COUNT,
+            max_priority_modifier_count: MAX_PRIORITY_MODIFIER_COUNT,
+            start_movement_amount: START_MOVEMENT_AMOUNT,
+            start_money_amount: START_MONEY_AMOUNT,
+            max_round_count: None,
+            turn_time_limit_seconds: None,
+            reveal_objective_cards: false,
+            vehicles_per_player: VEHICLE_COUNT,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Checks that a game can be played with the settings. The situation cards only have objective cards for `MAX_PLAYER_COUNT` players, so there can not be more players than that. Will return an error if the settings are not valid.
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.max_player_count < 2 || self.max_player_count > MAX_PLAYER_COUNT {
+            return Err(invalid_game_settings(format!("The max amount of players must be between 2 and {}, but got {}!", MAX_PLAYER_COUNT, self.max_player_count)));
+        }
+        if self.start_movement_amount <= 0 {
+            return Err(invalid_game_settings(format!("The players must start with more than 0 moves, but got {}!", self.start_movement_amount)));
+        }
+        if self.start_money_amount < 0 {
+            return Err(invalid_game_settings(format!("The players can not start with a negative amount of money, but got {}!", self.start_money_amount)));
+        }
+        if self.vehicles_per_player == 0 || self.vehicles_per_player > MAX_VEHICLE_COUNT {
+            return Err(invalid_game_settings(format!("The players must have between 1 and {} vehicles, but got {}!", MAX_VEHICLE_COUNT, self.vehicles_per_player)));
+        }
+        match self.turn_time_limit_seconds {
+            Some(0) => return Err(invalid_game_settings("The turn time limit must be more than 0 seconds!".to_string())),
+            Some(seconds) if seconds > MAX_TURN_TIME_LIMIT_SECONDS => {
+                return Err(invalid_game_settings(format!("The turn time limit can be at most {} seconds, but got {}!", MAX_TURN_TIME_LIMIT_SECONDS, seconds)));
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// The GameSettingsChanges struct describes changes to some of the game settings. The settings that are left out keep the values they already have.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameSettingsChanges {
+    pub max_player_count: Option<usize>,
+    pub max_toll_modifier_count: Option<usize>,
+    pub max_access_modifier_count: Option<usize>,
+    pub max_priority_modifier_count: Option<usize>,
+    pub start_movement_amount: Option<MovementValue>,
+    pub start_money_amount: Option<Money>,
+    /// The new amount of rounds after which the game ends. A round count of 0 removes the round limit.
+    pub max_round_count: Option<u32>,
+    /// The new turn time limit in seconds. A limit of 0 seconds removes the turn time limit.
+    pub turn_time_limit_seconds: Option<u64>,
+    pub reveal_objective_cards: Option<bool>,
+    pub vehicles_per_player: Option<usize>,
+}
+
+impl GameSettingsChanges {
+    /// Returns the given settings with the changes made to them.
+    #[must_use]
+    pub fn apply_to(&self, settings: GameSettings) -> GameSettings {
+        GameSettings {
+            max_player_count: self.max_player_count.unwrap_or(settings.max_player_count),
+            max_toll_modifier_count: self.max_toll_modifier_count.unwrap_or(settings.max_toll_modifier_count),
+            max_access_modifier_count: self.max_access_modifier_count.unwrap_or(settings.max_access_modifier_count),
+            max_priority_modifier_count: self.max_priority_modifier_count.unwrap_or(settings.max_priority_modifier_count),
+            start_movement_amount: self.start_movement_amount.unwrap_or(settings.start_movement_amount),
+            start_money_amount: self.start_money_amount.unwrap_or(settings.start_money_amount),
+            max_round_count: match self.max_round_count {
+                Some(0) => None,
+                Some(rounds) => Some(rounds),
+                None => settings.max_round_count,
+            },
+            turn_time_limit_seconds: match self.turn_time_limit_seconds {
+                Some(0) => None,
+                Some(seconds) => Some(seconds),
+                None => settings.turn_time_limit_seconds,
+            },
+            reveal_objective_cards: self.reveal_objective_cards.unwrap_or(settings.reveal_objective_cards),
+            vehicles_per_player: self.vehicles_per_player.unwrap_or(settings.vehicles_per_player),
+        }
+    }
+}
+
+fn invalid_game_settings(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::InvalidGameSettings, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_invalid(settings: GameSettings) {
+        assert!(
+            matches!(settings.validate(), Err(GameError::InvalidInput(InvalidInputReason::InvalidGameSettings, _))),
+            "{:?} should not be valid",
+            settings
+        );
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert!(GameSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn settings_a_game_can_not_be_played_with_are_not_valid() {
+        let default = GameSettings::default();
+
+        assert_invalid(GameSettings { max_player_count: 1, ..default });
+        assert_invalid(GameSettings { max_player_count: MAX_PLAYER_COUNT + 1, ..default });
+        assert_invalid(GameSettings { start_movement_amount: 0, ..default });
+        assert_invalid(GameSettings { start_money_amount: -1, ..default });
+        assert_invalid(GameSettings { turn_time_limit_seconds: Some(0), ..default });
+        assert_invalid(GameSettings { turn_time_limit_seconds: Some(MAX_TURN_TIME_LIMIT_SECONDS + 1), ..default });
+    }
+
+    #[test]
+    fn the_limits_themselves_are_valid() {
+        let settings = GameSettings {
+            max_player_count: MAX_PLAYER_COUNT,
+            turn_time_limit_seconds: Some(MAX_TURN_TIME_LIMIT_SECONDS),
+            start_money_amount: 0,
+            ..GameSettings::default()
+        };
+
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn only_the_changed_settings_are_changed() {
+        let settings = GameSettings { max_round_count: Some(10), ..GameSettings::default() };
+        let changes = GameSettingsChanges { start_money_amount: Some(5), max_player_count: Some(4), ..GameSettingsChanges::default() };
+
+        let changed_settings = changes.apply_to(settings);
+
+        assert_eq!(changed_settings, GameSettings { start_money_amount: 5, max_player_count: 4, ..settings });
+    }
+
+    #[test]
+    fn a_max_round_count_of_zero_removes_the_round_limit() {
+        let settings = GameSettings { max_round_count: Some(10), ..GameSettings::default() };
+        let changes = GameSettingsChanges { max_round_count: Some(0), ..GameSettingsChanges::default() };
+
+        assert_eq!(changes.apply_to(settings).max_round_count, None);
+    }
+
+    #[test]
+    fn a_turn_time_limit_of_zero_seconds_removes_the_limit() {
+        let settings = GameSettings { turn_time_limit_seconds: Some(60), ..GameSettings::default() };
+        let changes = GameSettingsChanges { turn_time_limit_seconds: Some(0), ..GameSettingsChanges::default() };
+
+        assert_eq!(changes.apply_to(settings).turn_time_limit_seconds, None);
+    }
+
+    #[test]
+    fn unknown_settings_are_not_accepted() {
+        let result = serde_json::from_str::<GameSettingsChanges>(r#"{ "max_players": 4 }"#);
+
+        assert!(result.is_err());
+    }
+}
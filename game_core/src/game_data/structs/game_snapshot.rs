@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::game_data::{custom_types::{PlayerID, PlayerToken, VehicleID}, enums::{district::District, game_error::GameError}};
+
+use super::{action_log::ActionLog, gamestate::GameState, player_input::PlayerInput};
+
+/// The GameSnapshot struct contains everything needed to store a [`GameState`] and restore it later, including the data that is not sent to the clients.
+/// 
+/// [`GameState`]: ../gamestate/struct.GameState.html
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GameSnapshot {
+    pub game: GameState,
+    pub actions: Vec<PlayerInput>,
+    #[serde(deserialize_with = "deserialize_accessed_districts")]
+    pub accessed_districts: Vec<(VehicleID, District)>,
+    #[serde(default)]
+    pub action_log: ActionLog,
+    #[serde(default)]
+    pub player_tokens: HashMap<PlayerID, PlayerToken>,
+    #[serde(default)]
+    pub reconnect_codes: HashMap<PlayerID, PlayerToken>,
+}
+
+impl GameSnapshot {
+    /// Creates a new GameSnapshot of the given game.
+    #[must_use]
+    pub fn from_game(game: &GameState) -> Self {
+        Self {
+            game: game.clone(),
+            actions: game.actions.clone(),
+            accessed_districts: game.accessed_districts.clone(),
+            action_log: game.action_log.clone(),
+            player_tokens: game.player_tokens.clone(),
+            reconnect_codes: game.reconnect_codes.clone(),
+        }
+    }
+
+    /// Turns the snapshot back into a GameState and rebuilds its node map. Will return an error if the node map could not be rebuilt.
+    pub fn into_game(self) -> Result<GameState, GameError> {
+        let mut game = self.game;
+        game.actions = self.actions;
+        game.accessed_districts = self.accessed_districts;
+        game.action_log = self.action_log;
+        game.player_tokens = self.player_tokens;
+        game.reconnect_codes = self.reconnect_codes;
+        game.rebuild_node_map()?;
+        Ok(game)
+    }
+}
+
+/// An accessed district as it is stored. Games stored before the players had a fleet of vehicles only have the district, which was accessed by the only vehicle of the player.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredAccessedDistrict {
+    Vehicle((VehicleID, District)),
+    Legacy(District),
+}
+
+fn deserialize_accessed_districts<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(VehicleID, District)>, D::Error> {
+    let accessed_districts: Vec<StoredAccessedDistrict> = Vec::deserialize(deserializer)?;
+    Ok(accessed_districts
+        .into_iter()
+        .map(|accessed_district| match accessed_district {
+            StoredAccessedDistrict::Vehicle(accessed_district) => accessed_district,
+            StoredAccessedDistrict::Legacy(district) => (0, district),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_utils::small_game;
+
+    #[test]
+    fn accessed_districts_stored_before_fleets_belong_to_the_first_vehicle() {
+        let mut snapshot = json!(GameSnapshot::from_game(&small_game()));
+        snapshot["accessed_districts"] = json!(["CityCentre", [1, "Suburbs"]]);
+
+        let snapshot: GameSnapshot = serde_json::from_value(snapshot).unwrap();
+
+        assert_eq!(snapshot.accessed_districts, vec![(0, District::CityCentre), (1, District::Suburbs)]);
+    }
+
+    #[test]
+    fn snapshot_keeps_the_data_that_is_not_sent_to_the_clients() {
+        let mut game = small_game();
+        game.accessed_districts = vec![(1, District::Port)];
+        game.player_tokens.insert(2, "token".to_string());
+        game.reconnect_codes.insert(2, "reconnect code".to_string());
+
+        let stored_snapshot: GameSnapshot = serde_json::from_value(json!(GameSnapshot::from_game(&game))).unwrap();
+        let restored_game = stored_snapshot.into_game().unwrap();
+
+        assert_eq!(restored_game.accessed_districts, game.accessed_districts);
+        assert_eq!(restored_game.player_tokens, game.player_tokens);
+        assert_eq!(restored_game.reconnect_codes, game.reconnect_codes);
+    }
+}
@@ -1,11 +1,22 @@
-use std::{cmp, mem};
+use std::{cmp, collections::HashMap, mem, time::{Duration, Instant}};
 
 use rand::Rng;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::{game_data::{custom_types::{GameID, NodeID, PlayerID, MovementCost, MovementValue}, enums::{in_game_id::InGameID, district::District, restriction_type::RestrictionType, district_modifier_type::DistrictModifierType, traffic::Traffic}, constants::{MAX_PLAYER_COUNT, START_MOVEMENT_AMOUNT, MAX_ACCESS_MODIFIER_COUNT, MAX_PRIORITY_MODIFIER_COUNT, MAX_TOLL_MODIFIER_COUNT}}, situation_card_list::situation_card_list};
+use crate::{game_data::{custom_types::{GameID, NodeID, PlayerID, PlayerToken, MovementValue, Money, MovesRemaining, SituationCardID, VehicleID}, enums::{in_game_id::InGameID, game_end_reason::GameEndReason, game_error::{GameError, InvalidInputReason}, district::District, restriction_type::RestrictionType, district_modifier_type::DistrictModifierType, traffic::Traffic}}, map_list::{self, DEFAULT_MAP_NAME}};
 
-use super::{player::Player, player_input::PlayerInput, situation_card::SituationCard, edge_restriction::EdgeRestriction, node_map::NodeMap, neighbour_relationship::NeighbourRelationship, district_modifier::DistrictModifier};
+use super::{player::Player, player_input::PlayerInput, situation_card::SituationCard, edge_restriction::EdgeRestriction, node_map::NodeMap, neighbour_relationship::NeighbourRelationship, district_modifier::DistrictModifier, game_results::GameResults, player_score::PlayerScore, player_statistics::PlayerStatistics, situation_card_list::SituationCardList, action_log::ActionLog, rule_set::RuleSet, game_settings::GameSettings, spectator::Spectator, vehicle::Vehicle};
+
+/// The offset basis and prime of the 64 bit FNV-1a hash used for the state hashes.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The name the time left of the current turn has when the game is serialized.
+const TURN_TIME_REMAINING_KEY: &str = "turn_time_remaining_seconds";
+/// The names the players, whether a player is disconnected and the spectators have when the game is serialized.
+const PLAYERS_KEY: &str = "players";
+const IS_DISCONNECTED_KEY: &str = "is_disconnected";
+const SPECTATORS_KEY: &str = "spectators";
 
 /// The GameState struct describes the state of the game.
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -13,18 +24,50 @@ pub struct GameState {
     pub id: GameID,
     pub name: String,
     pub players: Vec<Player>,
+    /// The people following the game without playing it. They are not part of the turn order and are not given objective cards.
+    #[serde(default)]
+    pub spectators: Vec<Spectator>,
     pub is_lobby: bool,
     pub current_players_turn: InGameID,
     pub district_modifiers: Vec<DistrictModifier>,
     #[serde(skip)]
     pub actions: Vec<PlayerInput>,
+    /// The districts the vehicles of the current player have entered this turn. Every vehicle pays the cost of entering a district the first time it enters it in a turn.
     #[serde(skip)]
-    pub accessed_districts: Vec<District>,
+    pub accessed_districts: Vec<(VehicleID, District)>,
     #[serde(skip)]
     pub map: NodeMap,
+    #[serde(default = "GameState::default_map_name")]
+    pub map_name: String,
     pub situation_card: Option<SituationCard>,
+    /// The situation card uploaded by the orchestrator for this game, if any. It can be assigned like the situation cards of the server.
+    #[serde(default)]
+    pub custom_situation_card: Option<SituationCard>,
     pub edge_restrictions: Vec<EdgeRestriction>,
+    /// The nodes a vehicle of the player whose turn it is can move to. It's the first vehicle of the player, or the vehicle of the input when the game is returned after a player input.
     pub legal_nodes: Vec<NodeID>,
+    /// The nodes every vehicle of the same player can move to, in the same order as the vehicles of the player.
+    #[serde(default)]
+    pub legal_nodes_per_vehicle: Vec<Vec<NodeID>>,
+    pub rounds_played: u32,
+    pub game_results: Option<GameResults>,
+    /// The rules that are turned on or off in this game, chosen when the game is created.
+    #[serde(default)]
+    pub rule_set: RuleSet,
+    /// The game balance values of this game, chosen when the game is created.
+    #[serde(default)]
+    pub settings: GameSettings,
+    /// When the current turn ends, if the game has a turn time limit. It's sent to the clients as the amount of seconds left of the turn, so that they can show a countdown.
+    #[serde(rename = "turn_time_remaining_seconds", serialize_with = "serialize_turn_deadline", skip_deserializing)]
+    pub turn_deadline: Option<Instant>,
+    #[serde(skip)]
+    pub action_log: ActionLog,
+    /// The tokens of the players in the game. They are kept with the game so that the players can keep using them when the game is restored, but are never sent to the clients.
+    #[serde(skip)]
+    pub player_tokens: HashMap<PlayerID, PlayerToken>,
+    /// The reconnect codes of the players in the game. They are kept and hidden the same way as the tokens.
+    #[serde(skip)]
+    pub reconnect_codes: HashMap<PlayerID, PlayerToken>,
 }
 
 impl GameState {
@@ -35,25 +78,104 @@ impl GameState {
             id: game_id,
             name,
             players: Vec::new(),
+            spectators: Vec::new(),
             is_lobby: true,
             actions: Vec::new(),
             current_players_turn: InGameID::Orchestrator,
             district_modifiers: Vec::new(),
             accessed_districts: Vec::new(),
             map: NodeMap::new_default(),
+            map_name: Self::default_map_name(),
             situation_card: None,
+            custom_situation_card: None,
             edge_restrictions: Vec::new(),
             legal_nodes: Vec::new(),
+            legal_nodes_per_vehicle: Vec::new(),
+            rounds_played: 0,
+            game_results: None,
+            rule_set: RuleSet::default(),
+            settings: GameSettings::default(),
+            turn_deadline: None,
+            action_log: ActionLog::default(),
+            player_tokens: HashMap::new(),
+            reconnect_codes: HashMap::new(),
+        }
+    }
+
+    /// Adds the handled input to the action log of the game, together with the hash of the resulting game state.
+    pub fn record_action(&mut self, input: PlayerInput, state_hash: u64) {
+        // The log is taken out while recording so that a game that is stored in the log, when the game starts, does not contain the log itself.
+        let mut action_log = mem::take(&mut self.action_log);
+        action_log.record(input, self, state_hash);
+        self.action_log = action_log;
+    }
+
+    /// Returns a hash of the game state as it is sent to the clients. Two game states with the same hash can be seen as the same.
+    /// 
+    /// The hash is the FNV-1a hash of the game state as JSON, which does not change between Rust versions, so the hashes stored in the action log can still be compared after the server is updated.
+    pub fn state_hash(&self) -> u64 {
+        let mut state = serde_json::to_value(self).unwrap_or_default();
+        // The time left of the turn changes all the time, and players can disconnect and connect again and spectators come and go at any time, so two game states are the same no matter how much time is left, which players are connected or who is spectating.
+        if let Some(state) = state.as_object_mut() {
+            state.remove(TURN_TIME_REMAINING_KEY);
+            state.remove(SPECTATORS_KEY);
+            if let Some(players) = state.get_mut(PLAYERS_KEY).and_then(|players| players.as_array_mut()) {
+                for player in players.iter_mut().filter_map(|player| player.as_object_mut()) {
+                    player.remove(IS_DISCONNECTED_KEY);
+                }
+            }
+        }
+        state
+            .to_string()
+            .bytes()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+    }
+
+    /// Starts the time limit of the current turn again. The turn has no time limit if the game is in the lobby or has no turn time limit. A time limit too long to be represented as an [`Instant`] is treated as no time limit, instead of panicking.
+    pub fn restart_turn_timer(&mut self) {
+        self.turn_deadline = match (self.is_lobby, self.settings.turn_time_limit_seconds) {
+            (false, Some(seconds)) => Instant::now().checked_add(Duration::from_secs(seconds)),
+            _ => None,
+        };
+    }
+
+    /// Returns `true` if the game has a turn time limit and the current turn has run out of time, else it will return `false`.
+    pub fn is_turn_time_up(&self) -> bool {
+        matches!(self.turn_deadline, Some(deadline) if deadline <= Instant::now())
+    }
+
+    /// Returns the game as the player or spectator with the given unique id is allowed to see it. The objective cards of the other players are hidden, unless the game reveals them in its settings, the viewer is the orchestrator or the game is over. Every objective card is hidden if there is no viewer. The vehicle types of the vehicles are hidden together with the objective card they come from.
+    #[must_use]
+    pub fn view_for(mut self, viewer_id: Option<PlayerID>) -> Self {
+        let viewer_is_orchestrator = self
+            .players
+            .iter()
+            .any(|p| Some(p.unique_id) == viewer_id && p.in_game_id == InGameID::Orchestrator);
+        if self.settings.reveal_objective_cards || viewer_is_orchestrator || self.is_game_over() {
+            return self;
+        }
+        for player in self.players.iter_mut() {
+            if Some(player.unique_id) != viewer_id {
+                player.objective_card = None;
+                player.vehicles.iter_mut().for_each(|vehicle| vehicle.vehicle_types.clear());
+            }
         }
+        self
+    }
+
+    fn default_map_name() -> String {
+        DEFAULT_MAP_NAME.to_string()
     }
 
-    /// Set's the player with the given unique_id to a bus. If there is no player in the game with the given unique_id, nothing happens.
-    pub fn set_player_bus_bool(&mut self, player_id: PlayerID, boolean: bool) {
+    /// Set's the vehicle with the given id of the player with the given unique_id to a bus. If there is no player in the game with the given unique_id or the player has no vehicle with the given id, nothing happens.
+    pub fn set_player_bus_bool(&mut self, player_id: PlayerID, vehicle_id: VehicleID, boolean: bool) {
         for player in self.players.iter_mut() {
             if player.unique_id != player_id {
                 continue;
             }
-            player.is_bus = boolean;
+            if let Ok(vehicle) = player.get_vehicle_mut(vehicle_id) {
+                vehicle.is_bus = boolean;
+            }
         }
     }
 
@@ -67,24 +189,56 @@ impl GameState {
         false
     }
 
-    /// Assigns a player to the game. It will return an error string representing an error if something went wrong while assigning the player to the game.
-    pub fn assign_player_to_game(&mut self, mut player: Player) -> Result<(), String> {
-        if self.players.len() >= MAX_PLAYER_COUNT {
-            return Err("The game is full".to_string());
+    /// Returns `true` if the given unique_id belongs to a spectator of the game, else it will return `false`.
+    pub fn contains_spectator_with_unique_id(&self, unique_id: PlayerID) -> bool {
+        self.spectators.iter().any(|spectator| spectator.unique_id == unique_id)
+    }
+
+    /// Returns `true` if the given unique_id belongs to a player or a spectator of the game, which means that they are allowed to follow the game, else it will return `false`.
+    pub fn can_be_followed_by(&self, unique_id: PlayerID) -> bool {
+        self.contains_player_with_unique_id(unique_id) || self.contains_spectator_with_unique_id(unique_id)
+    }
+
+    /// Assigns a player to the game. A spectator of the game that joins it as a player stops being a spectator. It will return an error if something went wrong while assigning the player to the game.
+    pub fn assign_player_to_game(&mut self, mut player: Player) -> Result<(), GameError> {
+        if self.players.len() >= self.settings.max_player_count {
+            return Err(GameError::Conflict("The game is full".to_string()));
         }
 
         if self.contains_player_with_unique_id(player.unique_id) {
-            return Err(
+            return Err(GameError::Conflict(
                 "A player that is already assigned to a game cannot be assigned again".to_string(),
-            );
+            ));
         }
 
+        self.spectators.retain(|spectator| spectator.unique_id != player.unique_id);
         player.in_game_id = InGameID::Undecided;
         player.connected_game_id = Some(self.id);
         self.players.push(player);
         Ok(())
     }
 
+    /// Adds a spectator to the game. Spectators can follow both lobbies and games that have started, and do not count against the max amount of players. It will return an error if the spectator is already a player or a spectator in the game.
+    pub fn add_spectator(&mut self, spectator: Spectator) -> Result<(), GameError> {
+        if self.can_be_followed_by(spectator.unique_id) {
+            return Err(GameError::Conflict(
+                "A player or spectator that is already in the game cannot spectate it again".to_string(),
+            ));
+        }
+        self.spectators.push(spectator);
+        Ok(())
+    }
+
+    /// Removes the spectator with the given unique id from the game. If the spectator does not exist in the game, nothing will happen.
+    pub fn remove_spectator_with_id(&mut self, spectator_id: PlayerID) {
+        if !self.contains_spectator_with_unique_id(spectator_id) {
+            return;
+        }
+        self.spectators.retain(|spectator| spectator.unique_id != spectator_id);
+        self.player_tokens.remove(&spectator_id);
+        self.reconnect_codes.remove(&spectator_id);
+    }
+
     fn node_is_in_district (neighbour_list: Vec<NeighbourRelationship>, district: District) -> bool {
         let mut node_is_in_district = false;
         neighbour_list.into_iter().for_each(|edge|{
@@ -95,81 +249,95 @@ impl GameState {
         node_is_in_district
     }
 
-    /// Moves the player to the given node id. Will return an error string if something went wrong while trying to move the player.
+    /// Moves the vehicle with the given id of the player to the given node id. Will return an error if something went wrong while trying to move the vehicle.
+    /// 
+    /// The district cost and the tolls are paid per vehicle, the first time each vehicle enters a district in a turn. The tolls are paid from the money of the player, and are paid whether the player has an objective card or not. A priority lane waives the district cost, and does not give bonus moves on top of that.
     pub fn move_player_with_id(
         &mut self,
         player_id: PlayerID,
+        vehicle_id: VehicleID,
         to_node_id: NodeID,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         for player in self.players.iter_mut() {
             if player.unique_id != player_id {
                 continue;
             }
 
-            let Some(current_node_id) = player.position_node_id else {
-                return Err("The player is not at any node!".to_string());
+            let vehicle = player.get_vehicle(vehicle_id)?.clone();
+            let is_bus = vehicle.is_bus;
+            let Some(current_node_id) = vehicle.position_node_id else {
+                return Err(GameError::InvalidInput(InvalidInputReason::NoPosition, "The vehicle is not at any node!".to_string()));
             };
 
             let Some(neighbours) = self.map.get_neighbour_relationships_of_node_with_id(current_node_id) else {
-                return Err(format!("There was no node with id {}!", current_node_id));
+                return Err(GameError::NotFound(format!("There was no node with id {}!", current_node_id)));
             };
 
             let Some(neighbour_relationship) = neighbours.iter().find(|relationship| relationship.to == to_node_id) else {
-                return Err(format!("The node you are trying to go to is not a neighbour. From node with id {} to {}", current_node_id, to_node_id));
+                return Err(GameError::InvalidInput(InvalidInputReason::NotNeighbour, format!("The node you are trying to go to is not a neighbour. From node with id {} to {}", current_node_id, to_node_id)));
             };
 
             if neighbour_relationship.is_connected_through_rail {
-                Self::move_player_to_node(player, to_node_id, 1);
+                player.get_vehicle_mut(vehicle_id)?.move_to_node(to_node_id, 1);
                 return Ok(());
             }
 
-            if player.is_bus {
+            if is_bus {
                 let Some(edge_restriction) = neighbour_relationship.restriction else {
-                    return Err(format!("The node (with id {}) you are trying to go to does not have a restriction and you can therefore not move there as a bus!", to_node_id));
+                    return Err(GameError::InvalidInput(InvalidInputReason::BusNotAllowed, format!("The node (with id {}) you are trying to go to does not have a restriction and you can therefore not move there as a bus!", to_node_id)));
                 };
 
                 if edge_restriction != RestrictionType::ParkAndRide {
-                    return Err(format!("The node (with id {}) you are trying to go to is not a part of the park & ride roads and you can therefore not move there as a bus!", to_node_id));
+                    return Err(GameError::InvalidInput(InvalidInputReason::BusNotAllowed, format!("The node (with id {}) you are trying to go to is not a part of the park & ride roads and you can therefore not move there as a bus!", to_node_id)));
                 }
                 
-                Self::move_player_to_node(player, to_node_id, 1);
+                player.get_vehicle_mut(vehicle_id)?.move_to_node(to_node_id, 1);
                 return Ok(());
             }
 
             if let Some(restriction) = neighbour_relationship.restriction {
                 if restriction == RestrictionType::ParkAndRide {
-                    return Err(format!("The node (with id {}) you are trying to go to is a part of the park & ride roads and you can therefore not move there unless you are a buss!", to_node_id));
+                    return Err(GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, format!("The node (with id {}) you are trying to go to is a part of the park & ride roads and you can therefore not move there unless you are a buss!", to_node_id)));
                 }
-                Self::move_player_to_node(player, to_node_id, 1);
+                player.get_vehicle_mut(vehicle_id)?.move_to_node(to_node_id, 1);
                 return Ok(());
             }
 
+            let mut toll: Money = 0;
+            let mut cost = neighbour_relationship.movement_cost;
+
             if !self
                 .accessed_districts
-                .contains(&neighbour_relationship.neighbourhood)
+                .contains(&(vehicle_id, neighbour_relationship.neighbourhood))
             {
                 self.accessed_districts
-                    .push(neighbour_relationship.neighbourhood);
-                player.remaining_moves -= match self
+                    .push((vehicle_id, neighbour_relationship.neighbourhood));
+                let district_cost = self
                     .map
-                    .first_time_in_district_cost(neighbour_relationship.clone())
-                {
-                    Ok(cost) => cost,
-                    Err(e) => return Err(e),
-                };
+                    .first_time_in_district_cost(neighbour_relationship.clone())?;
+                if !Self::vehicle_has_priority_in_district(&self.district_modifiers, &self.map, player, &vehicle, neighbour_relationship.neighbourhood) {
+                    cost += district_cost;
+                }
+
+                for modifier in self.district_modifiers.iter() {
+                    if modifier.modifier == DistrictModifierType::Toll && modifier.district == neighbour_relationship.neighbourhood {
+                        toll += Self::get_toll_cost(modifier, &vehicle.vehicle_types)?;
+                    }
+                }
 
                 let mut bonus_moves = 0;
 
-                if let Some(obj_card) = player.objective_card.clone() {
+                if player.objective_card.is_some() {
                     for modifier in self.district_modifiers.iter() {
-                        if modifier.modifier == DistrictModifierType::Toll {
-                            continue; //TODO: Implement toll
+                        // The effect of a priority lane is that the district cost is waived, so it does not give bonus moves as well.
+                        if modifier.modifier == DistrictModifierType::Toll || modifier.modifier == DistrictModifierType::Priority {
+                            continue;
                         }
 
                         let player_has_objective_in_district = Self::player_has_objective_in_district(&self.map, player, modifier.district);
                         
                         let Some(restriction_vehicle_type) = modifier.vehicle_type else {
-                            return Err("The vehicle type can not be determined, and bonus moves can not be applied".to_string());
+                            return Err(GameError::Internal("The vehicle type can not be determined, and bonus moves can not be applied".to_string()));
                         };
 
                         if modifier.district != neighbour_relationship.neighbourhood {
@@ -186,7 +354,7 @@ impl GameState {
                             continue;
                         };
 
-                        if !obj_card.special_vehicle_types.contains(&vehicle_type) {
+                        if !vehicle.has_vehicle_type(vehicle_type) {
                             continue;
                         }
 
@@ -195,13 +363,37 @@ impl GameState {
                         }
                     }
                 }
-                player.remaining_moves += bonus_moves;
+                cost -= bonus_moves;
+                player.money -= toll;
             }
-            player.remaining_moves -= neighbour_relationship.movement_cost;
-            player.position_node_id = Some(to_node_id);
+            player.get_vehicle_mut(vehicle_id)?.move_to_node(to_node_id, cost);
+            self.give_money_to_orchestrator(toll);
             return Ok(());
         }
-        Err("There were no players in this game that match the player to update".to_string())
+        Err(GameError::NotFound("There were no players in this game that match the player to update".to_string()))
+    }
+
+    /// Returns how much money a vehicle with the given vehicle types has to pay when entering the district of the given toll modifier. Vehicles of the modifier's vehicle type (if any) can pass without paying. Will return an error if the modifier is not a valid toll modifier.
+    pub fn get_toll_cost(modifier: &DistrictModifier, vehicle_types: &[RestrictionType]) -> Result<Money, GameError> {
+        if modifier.modifier != DistrictModifierType::Toll {
+            return Err(GameError::Internal(format!("The modifier {:?} is not a toll modifier and can therefore not have a toll cost!", modifier.modifier)));
+        }
+        let Some(money_value) = modifier.associated_money_value else {
+            return Err(GameError::Internal(format!("The toll modifier in district {:?} does not have a money value and the toll can therefore not be charged!", modifier.district)));
+        };
+        if let Some(vehicle_type) = modifier.vehicle_type {
+            if vehicle_types.contains(&vehicle_type) {
+                return Ok(0);
+            }
+        }
+        Ok(money_value)
+    }
+
+    /// Gives the given amount of money to the orchestrator. If there is no orchestrator in the game, nothing happens.
+    pub fn give_money_to_orchestrator(&mut self, amount: Money) {
+        if let Some(orchestrator) = self.players.iter_mut().find(|p| p.in_game_id == InGameID::Orchestrator) {
+            orchestrator.money += amount;
+        }
     }
 
     /// Checks if the player has an objective card in the given district.
@@ -218,21 +410,33 @@ impl GameState {
         Self::node_is_in_district(player_pickup_node_neighbours, district) || Self::node_is_in_district(player_drop_off_node_neighbours, district)
     }
 
-    /// Moves the player to a node without checking if the move is valid.
-    pub fn move_player_to_node(player: &mut Player, to_node_id: NodeID, cost: MovementCost) {
-        player.remaining_moves -= cost;
-        player.position_node_id = Some(to_node_id);
+    /// Checks if the given vehicle of the player can use a priority lane in the given district, which means that it does not have to pay the district cost when entering it. A vehicle can use a priority lane if it has the vehicle type of a priority modifier in the district, or if the modifier is for destination traffic and the player has an objective in the district.
+    pub fn vehicle_has_priority_in_district(district_modifiers: &[DistrictModifier], map: &NodeMap, player: &Player, vehicle: &Vehicle, district: District) -> bool {
+        if player.objective_card.is_none() {
+            return false;
+        }
+        district_modifiers.iter().any(|modifier| {
+            if modifier.district != district || modifier.modifier != DistrictModifierType::Priority {
+                return false;
+            }
+            let Some(vehicle_type) = modifier.vehicle_type else {
+                return false;
+            };
+            vehicle.has_vehicle_type(vehicle_type)
+                || (vehicle_type == RestrictionType::Destination
+                && Self::player_has_objective_in_district(map, player, district))
+        })
     }
 
     /// Tries to assign the player to the role specified in the change_info tuple. Will return an error if something went wrong.
-    pub fn assign_player_role(&mut self, change_info: (PlayerID, InGameID)) -> Result<(), &str> {
+    pub fn assign_player_role(&mut self, change_info: (PlayerID, InGameID)) -> Result<(), GameError> {
         let (related_player_id, change_to_role) = change_info;
         if self
             .players
             .iter()
             .any(|p| p.in_game_id == change_to_role && change_to_role != InGameID::Undecided)
         {
-            return Err("There is already a player with this role");
+            return Err(GameError::Conflict("There is already a player with this role".to_string()));
         }
 
         for player in self.players.iter_mut() {
@@ -242,16 +446,16 @@ impl GameState {
             player.in_game_id = change_to_role;
             return Ok(());
         }
-        Err("There were no players in this game that match the player to update")
+        Err(GameError::NotFound("There were no players in this game that match the player to update".to_string()))
     }
 
     /// Tries to get the player with the given unique id. Will return an error if something went wrong.
-    pub fn get_player_with_unique_id(&self, player_id: PlayerID) -> Result<Player, &str> {
+    pub fn get_player_with_unique_id(&self, player_id: PlayerID) -> Result<Player, GameError> {
         self.players
             .iter()
             .find(|p| p.unique_id == player_id)
             .map_or(
-                Err("There is no player in the game with the given id"),
+                Err(GameError::NotFound("There is no player in the game with the given id".to_string())),
                 |player| Ok(player.clone()),
             )
     }
@@ -265,12 +469,14 @@ impl GameState {
         let player_with_turn_removed = self.current_players_turn == player.in_game_id;
         let mut its_the_next_players_turn = false;
         self.players.retain(|player| player.unique_id != player_id);
+        self.player_tokens.remove(&player_id);
+        self.reconnect_codes.remove(&player_id);
         if self
             .players
             .iter()
             .all(|player| player.in_game_id != InGameID::Orchestrator)
         {
-            if let Some(mut p) = self.players.first_mut() {
+            if let Some(p) = self.players.first_mut() {
                 if p.in_game_id == self.current_players_turn {
                     its_the_next_players_turn = true;
                 }
@@ -287,8 +493,9 @@ impl GameState {
         }
     }
 
-    /// Sets the current players turn to the next player in the list of players. This function will also set the is_lobby bool to true if the orchestrator is the next player. 
+    /// Sets the current players turn to the next player in the list of players. This function will also end the game if all the objectives are delivered, and end the round if the orchestrator is the next player.
     pub fn next_player_turn(&mut self) {
+        self.update_turn_statistics_of_current_player();
         let mut next_player_turn = self.current_players_turn.next();
         let mut counter = 0;
         while !self
@@ -305,35 +512,165 @@ impl GameState {
         }
         self.accessed_districts.clear();
         self.current_players_turn = next_player_turn;
+        self.restart_turn_timer();
+        if self.is_lobby {
+            return;
+        }
+        if self.all_objectives_delivered() {
+            self.end_game(GameEndReason::AllObjectivesDelivered);
+            return;
+        }
         if self.current_players_turn == InGameID::Orchestrator {
-            self.is_lobby = true;
+            self.end_round();
+        }
+    }
+
+    /// Returns `true` if the turn of the current player should be skipped because they are disconnected, else it will return `false`. The turn is only skipped in a running game, and as long as there is a connected player left to give the turn to.
+    pub fn is_current_turn_skipped(&self) -> bool {
+        !self.is_lobby
+            && !self.is_game_over()
+            && self.is_current_player_disconnected()
+            && self.players.iter().any(|p| !p.is_disconnected && p.in_game_id != InGameID::Undecided)
+    }
+
+    /// Returns `true` if the player whose turn it is is disconnected, else it will return `false`.
+    pub fn is_current_player_disconnected(&self) -> bool {
+        self.players
+            .iter()
+            .any(|p| p.in_game_id == self.current_players_turn && p.is_disconnected)
+    }
+
+    /// Marks the player with the given unique id as disconnected or connected again. Returns `true` if the player is in the game and was not already marked like that, else it will return `false`.
+    pub fn set_player_disconnected(&mut self, player_id: PlayerID, is_disconnected: bool) -> bool {
+        match self.players.iter_mut().find(|p| p.unique_id == player_id) {
+            Some(player) if player.is_disconnected != is_disconnected => {
+                player.is_disconnected = is_disconnected;
+                true
+            }
+            _ => false,
         }
     }
 
-    /// Returns the starting movement value for the players.
-    pub const fn get_starting_player_movement_value() -> MovementValue {
-        START_MOVEMENT_AMOUNT
+    /// Returns `true` if the game has ended and has final results, else it will return `false`.
+    pub const fn is_game_over(&self) -> bool {
+        self.game_results.is_some()
+    }
+
+    /// Returns `true` if every player (that is not the orchestrator) has delivered their objective, else it will return `false`.
+    pub fn all_objectives_delivered(&self) -> bool {
+        let mut players = self
+            .players
+            .iter()
+            .filter(|p| p.in_game_id != InGameID::Orchestrator)
+            .peekable();
+        players.peek().is_some()
+            && players.all(|p| matches!(&p.objective_card, Some(card) if card.dropped_package_off))
+    }
+
+    /// Ends the game with the given reason. This stores the final results of the players in the game and puts the game back into the lobby so that the orchestrator can start a new game.
+    pub fn end_game(&mut self, reason: GameEndReason) {
+        self.game_results = Some(GameResults {
+            game_id: self.id,
+            reason,
+            rounds_played: self.rounds_played,
+            player_scores: self.players.iter().map(PlayerScore::from_player).collect(),
+        });
+        self.is_lobby = true;
+        self.current_players_turn = InGameID::Orchestrator;
+        self.turn_deadline = None;
     }
 
-    /// Assigns a random objective card to all the players in the game, based on the chosen situation card. Will return an error if something went wrong.
-    pub fn assign_random_objective_card_to_players(&mut self) -> Result<(), String> {
+    fn end_round(&mut self) {
+        self.rounds_played += 1;
+        self.reset_player_movement_values();
+        if matches!(self.settings.max_round_count, Some(max_round_count) if self.rounds_played >= max_round_count) {
+            self.end_game(GameEndReason::RoundLimitReached);
+        }
+    }
+
+    fn update_turn_statistics_of_current_player(&mut self) {
+        if self.is_lobby || self.current_players_turn == InGameID::Orchestrator {
+            return;
+        }
+        let current_players_turn = self.current_players_turn;
+        for player in self.players.iter_mut().filter(|p| p.in_game_id == current_players_turn) {
+            if !player.statistics.has_finished {
+                player.statistics.turns_used += 1;
+            }
+            player.statistics.has_finished =
+                matches!(&player.objective_card, Some(card) if card.dropped_package_off);
+        }
+    }
+
+    /// Updates the statistics of the player with the given unique id after the vehicle with the given id has moved from the given node. The spent moves are found by comparing the remaining moves of the vehicle before the movement with its current remaining moves. Will return an error if something went wrong.
+    pub fn record_movement_statistics(
+        &mut self,
+        player_id: PlayerID,
+        vehicle_id: VehicleID,
+        from_node_id: NodeID,
+        remaining_moves_before: MovesRemaining,
+    ) -> Result<(), GameError> {
+        let player = self.get_player_with_unique_id(player_id)?;
+        let Some(to_node_id) = player.get_vehicle(vehicle_id)?.position_node_id else {
+            return Err(GameError::Internal("The vehicle is not at any node and the movement can therefore not be recorded!".to_string()));
+        };
+        let is_rail_movement = self
+            .map
+            .get_neighbour_relationships_of_node_with_id(from_node_id)
+            .unwrap_or_default()
+            .iter()
+            .any(|relationship| relationship.to == to_node_id && relationship.is_connected_through_rail);
+
+        for player in self.players.iter_mut() {
+            if player.unique_id != player_id {
+                continue;
+            }
+            let vehicle = player.get_vehicle(vehicle_id)?;
+            let moves_spent = remaining_moves_before - vehicle.remaining_moves;
+            let emissions_per_move = vehicle.get_emissions_per_move();
+            player.statistics.moves_spent += moves_spent;
+            if is_rail_movement || moves_spent <= 0 {
+                return Ok(());
+            }
+            player.statistics.emissions += moves_spent.unsigned_abs() as u32 * emissions_per_move;
+            return Ok(());
+        }
+        Err(GameError::NotFound("There were no players in this game that match the player to update".to_string()))
+    }
+
+    /// Returns the starting movement value for the players in this game.
+    pub const fn get_starting_player_movement_value(&self) -> MovementValue {
+        self.settings.start_movement_amount
+    }
+
+    /// Returns the starting amount of money for the players in this game.
+    pub const fn get_starting_player_money_value(&self) -> Money {
+        self.settings.start_money_amount
+    }
+
+    /// Assigns a random objective card to all the players in the game, based on the chosen situation card, and gives every player their vehicles at the start node of the objective card. Will return an error if something went wrong.
+    pub fn assign_random_objective_card_to_players(&mut self) -> Result<(), GameError> {
+        let vehicle_count = self.settings.vehicles_per_player;
+        let starting_movement_value = self.get_starting_player_movement_value();
         let Some(situation_card) = self.situation_card.clone() else {
-            return Err("The game does not have a situation card and can therefore not assign objective cards to the players!".to_string());
+            return Err(GameError::Conflict("The game does not have a situation card and can therefore not assign objective cards to the players!".to_string()));
         };
         let mut objective_cards = situation_card.objective_cards;
         let mut rng = rand::thread_rng();
-        for mut player in self.players.iter_mut() {
+        for player in self.players.iter_mut() {
             if player.in_game_id == InGameID::Orchestrator {
                 continue;
             }
             if objective_cards.is_empty() {
-                return Err(
+                return Err(GameError::Conflict(
                     "There were not enough objective cards for all the players!".to_string()
-                );
+                ));
             }
             let index = rng.gen_range(0..objective_cards.len());
             let objective_card = objective_cards.remove(index);
-            player.position_node_id = Some(objective_card.start_node_id);
+            player.vehicles = (0..vehicle_count)
+                .map(|id| Vehicle::new(id as VehicleID, objective_card.start_node_id, starting_movement_value, objective_card.special_vehicle_types.clone()))
+                .collect();
             player.objective_card = Some(objective_card);
         }
         Ok(())
@@ -345,36 +682,44 @@ impl GameState {
     }
 
     /// Updates the objective card of the players in the game. Will return an error if something went wrong. This mainly concerns if the "package" of the player has been picked up and dropped off.
-    pub fn update_objective_status(&mut self) -> Result<(), String> {
+    /// 
+    /// The package is picked up by the first vehicle of the player that is at the pick up node, and is dropped off when that vehicle is at the drop off node.
+    pub fn update_objective_status(&mut self) -> Result<(), GameError> {
         for player in self.players.iter_mut() {
             if player.in_game_id == InGameID::Orchestrator {
                 continue;
             }
-            let Some(player_position_id) = player.position_node_id else {
-                return Err("The player did not have a position on the gameboard!".to_string());
-            };
+            if player.vehicles.is_empty() || player.vehicles.iter().any(|vehicle| vehicle.position_node_id.is_none()) {
+                return Err(GameError::Internal("The player did not have a vehicle with a position on the gameboard!".to_string()));
+            }
             let Some(mut objective_card) = player.objective_card.clone() else {
-                return Err("The player did not have an objective card!".to_string());
+                return Err(GameError::Internal("The player did not have an objective card!".to_string()));
             };
-            if player_position_id == objective_card.pick_up_node_id {
-                objective_card.picked_package_up = true;
+            if !objective_card.picked_package_up {
+                if let Some(vehicle) = player.vehicles.iter_mut().find(|vehicle| vehicle.position_node_id == Some(objective_card.pick_up_node_id)) {
+                    vehicle.has_package = true;
+                    objective_card.picked_package_up = true;
+                }
             }
-            if player_position_id == objective_card.drop_off_node_id
-            && objective_card.picked_package_up
-            {
-                objective_card.dropped_package_off = true;
+            if !objective_card.dropped_package_off {
+                if let Some(vehicle) = player.vehicles.iter_mut().find(|vehicle| vehicle.has_package && vehicle.position_node_id == Some(objective_card.drop_off_node_id)) {
+                    vehicle.has_package = false;
+                    objective_card.dropped_package_off = true;
+                }
             }
-            mem::swap(&mut player.objective_card, &mut Some(objective_card));
+            player.objective_card = Some(objective_card);
         }
         Ok(())
     }
 
     /// Starts the game, which means it goes from lobby to in game. Will return an error if something went wrong.
-    pub fn start_game(&mut self) -> Result<(), String> {
+    pub fn start_game(&mut self) -> Result<(), GameError> {
         let mut can_start_game = false;
         let mut errormessage =
             String::from("Unable to start game because lobby does not have an orchestrator");
         self.reset_player_in_game_data();
+        self.rounds_played = 0;
+        self.game_results = None;
         self.edge_restrictions.clear();
         self.district_modifiers.clear();
         match self.update_node_map_with_situation_card() {
@@ -384,7 +729,7 @@ impl GameState {
         for player in self.players.clone() {
             if player.in_game_id == InGameID::Undecided {
                 errormessage = format!("Unable to start game because player with id {} and name {} is neither player, nor orchestrator (Undecided)", player.unique_id, player.name);
-                return Err(errormessage);
+                return Err(GameError::Conflict(errormessage));
             }
         }
         for player in self.players.clone() {
@@ -402,14 +747,14 @@ impl GameState {
                 match self.assign_random_objective_card_to_players() {
                     Ok(_) => (),
                     Err(e) => {
-                        errormessage = e;
+                        errormessage = e.to_string();
                         break;
                     }
                 }
                 match self.update_objective_status() {
                     Ok(_) => (),
                     Err(e) => {
-                        errormessage = e;
+                        errormessage = e.to_string();
                         break;
                     }
                 }
@@ -421,79 +766,148 @@ impl GameState {
         match can_start_game {
             true => {
                 self.reset_player_movement_values();
+                self.restart_turn_timer();
                 Ok(())
             }
-            false => Err(errormessage),
+            false => Err(GameError::Conflict(errormessage)),
         }
     }
 
     /// Resets the players to default values defined in the function.
     pub fn reset_player_in_game_data(&mut self) {
+        let starting_money_value = self.get_starting_player_money_value();
         for player in self.players.iter_mut() {
-            player.position_node_id = None;
-            player.remaining_moves = Self::get_starting_player_movement_value();
+            player.vehicles.clear();
             player.objective_card = None;
-            player.is_bus = false;
-        }
-    }
-
-    /// Updates the node map based on the situation card. Will return an error if something went wrong.
-    pub fn update_node_map_with_situation_card(&mut self) -> Result<(), String> {
-        self.map.reset();
-        match &self.situation_card {
-            Some(card) => {
-                self.map.update_neighbourhood_cost(card);
-                match card.card_id {
-                    0 => {
-                        return Err("Error: Situation card with ID 0 does not exist".to_string());
-                    },
-                    1 => {},
-                    2 => {},
-                    3 => {},
-                    4 => {
-                        match self.add_edge_restriction(&EdgeRestriction { node_one: 19, node_two: 20, edge_restriction: RestrictionType::OneWay, delete: false }, false) {
-                            Ok(_) => (),
-                            Err(e) => return Err(e),
-                        }
-                    },
-                    5 => {
-                        match self.map.toggle_rail_connection_on_node_with_id(24) {
-                            Ok(_) => (),
-                            Err(e) => return Err(e),
-                        };
-                        match self.map.toggle_rail_connection_on_node_with_id(27) {
-                            Ok(_) => (),
-                            Err(e) => return Err(e),
-                        }
-                    },
-                    _ => {
-                        return Err("Error: Situation card with IDs 6 and up do not exist".to_string());
-                    },
-                }
-                Ok(())
-            },
-            None => Err("Error: No situation card was assigned to the game, and therefore can not update nodemap costs".to_string()),
+            player.statistics = PlayerStatistics::new();
+            player.money = match player.in_game_id {
+                InGameID::Orchestrator => 0,
+                _ => starting_money_value,
+            };
+        }
+    }
+
+    /// Sets the map the game is played on to the loaded map with the given name. Will return an error if there is no map with the given name.
+    pub fn set_map(&mut self, map_name: String) -> Result<(), GameError> {
+        self.map = map_list::get_map(&map_name)?;
+        self.map_name = map_name;
+        Ok(())
+    }
+
+    /// Replaces the node map of the game with the unchanged version of the map the game is played on. Will return an error if the map is no longer loaded.
+    fn reset_map(&mut self) -> Result<(), GameError> {
+        self.map = map_list::get_map(&self.map_name)?;
+        Ok(())
+    }
+
+    /// Returns the situation card with the given ID, which is either the custom situation card of the game or one of the situation cards of the server. Will return an error if there is no situation card with the given ID.
+    pub fn get_situation_card_by_id(&self, id: SituationCardID) -> Result<SituationCard, GameError> {
+        match &self.custom_situation_card {
+            Some(card) if card.card_id == id => Ok(card.clone()),
+            _ => SituationCardList::get_default_situation_card_by_id(id),
+        }
+    }
+
+    /// Assigns the situation card to the game and updates the node map based on it. The situation card must be usable on the map of the game and have an objective card for every player that is not the orchestrator. Will return an error if the situation card could not be assigned, and the game is then not changed.
+    pub fn assign_situation_card(&mut self, card: SituationCard) -> Result<(), GameError> {
+        let needed_objective_cards = self
+            .players
+            .iter()
+            .filter(|player| player.in_game_id != InGameID::Orchestrator)
+            .count();
+        match card.validate(&self.map, needed_objective_cards) {
+            Ok(_) => (),
+            Err(e) => return Err(e.map_message(|message| format!("The situation card can not be used on the map {} because: {}", self.map_name, message))),
+        }
+        let mut game = self.clone();
+        game.situation_card = Some(card);
+        match game.update_node_map_with_situation_card() {
+            Ok(_) => (),
+            Err(e) => return Err(e),
         }
+        *self = game;
+        Ok(())
+    }
+
+    /// Updates the node map based on the situation card, including the edge restrictions and rail toggles the situation card puts on the map. Will return an error if something went wrong.
+    pub fn update_node_map_with_situation_card(&mut self) -> Result<(), GameError> {
+        self.reset_map()?;
+        let Some(card) = self.situation_card.clone() else {
+            return Err(GameError::Conflict("Error: No situation card was assigned to the game, and therefore can not update nodemap costs".to_string()));
+        };
+        if card.card_id == 0 {
+            return Err(GameError::NotFound("Error: Situation card with ID 0 does not exist".to_string()));
+        }
+        self.map.update_neighbourhood_cost(&card);
+        for edge_restriction in card.edge_restrictions.iter() {
+            match self.add_edge_restriction(edge_restriction, false) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        for node_id in card.rail_toggles {
+            match self.map.toggle_rail_connection_on_node_with_id(node_id) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the node map from the situation card and the edge restrictions of the game. This is used when a game is restored, since the node map is not stored. Will return an error if something went wrong.
+    pub fn rebuild_node_map(&mut self) -> Result<(), GameError> {
+        if self.situation_card.is_none() {
+            self.reset_map()?;
+            for edge_restriction in self.edge_restrictions.clone() {
+                self.map.set_restriction_on_edge(&edge_restriction, true)?;
+            }
+            return Ok(());
+        }
+        let edge_restrictions = mem::take(&mut self.edge_restrictions);
+        self.update_node_map_with_situation_card()?;
+        for edge_restriction in edge_restrictions {
+            if self.edge_restrictions.contains(&edge_restriction) {
+                continue;
+            }
+            self.add_edge_restriction(&edge_restriction, true)?;
+        }
+        Ok(())
     }
 
-    /// Resets the players movement values to the starting value.
+    /// Resets the movement values of the vehicles of the players to the starting value.
     pub fn reset_player_movement_values(&mut self) {
+        let starting_movement_value = self.get_starting_player_movement_value();
         self.players
             .iter_mut()
-            .for_each(|player| player.remaining_moves = Self::get_starting_player_movement_value());
+            .flat_map(|player| player.vehicles.iter_mut())
+            .for_each(|vehicle| vehicle.remaining_moves = starting_movement_value);
     }
 
     /// Adds the wanted district modifier to the game. Will return an error if something went wrong
     pub fn add_district_modifier(
         &mut self,
         district_modifier: DistrictModifier,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let max_amount: usize = match district_modifier.modifier {
-            DistrictModifierType::Access => MAX_ACCESS_MODIFIER_COUNT,
-            DistrictModifierType::Priority => MAX_PRIORITY_MODIFIER_COUNT,
-            DistrictModifierType::Toll => MAX_TOLL_MODIFIER_COUNT,
+            DistrictModifierType::Access => self.settings.max_access_modifier_count,
+            DistrictModifierType::Priority => self.settings.max_priority_modifier_count,
+            DistrictModifierType::Toll => self.settings.max_toll_modifier_count,
         };
 
+        match district_modifier.modifier {
+            DistrictModifierType::Access => (),
+            DistrictModifierType::Priority => {
+                if district_modifier.vehicle_type.is_none() {
+                    return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "A priority modifier needs a vehicle type so that it's known who can use the priority lane!".to_string()));
+                }
+            },
+            DistrictModifierType::Toll => match district_modifier.associated_money_value {
+                Some(money_value) if money_value >= 0 => (),
+                Some(money_value) => return Err(GameError::InvalidInput(InvalidInputReason::DistrictModificationNotAllowed, format!("A toll cannot have a negative money value, but got {}!", money_value))),
+                None => return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "A toll modifier needs a money value so that it's known how much the players have to pay!".to_string())),
+            },
+        }
+
         if max_amount
             <= self
                 .district_modifiers
@@ -504,7 +918,7 @@ impl GameState {
                 })
                 .count()
         {
-            return Err(format!("Cannot add more modifiers of type {:?} because there are already {} modifiers of that type!", district_modifier.modifier, max_amount));
+            return Err(GameError::InvalidInput(InvalidInputReason::DistrictModificationNotAllowed, format!("Cannot add more modifiers of type {:?} because there are already {} modifiers of that type!", district_modifier.modifier, max_amount)));
         }
 
         self.district_modifiers.push(district_modifier);
@@ -515,26 +929,25 @@ impl GameState {
     pub fn remove_district_modifier(
         &mut self,
         district_modifier: DistrictModifier,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         let mut distr_mod = district_modifier;
         distr_mod.delete = false;
         let Some(mod_pos) = self.district_modifiers.iter().position(|d_m| d_m == &distr_mod) else {
-            return Err("There is no modifier like the given one in the game!".to_string());
+            return Err(GameError::NotFound("There is no modifier like the given one in the game!".to_string()));
         };
         self.district_modifiers.remove(mod_pos);
         self.update_traffic_levels()
     }
 
-    fn update_traffic_levels(&mut self) -> Result<(), String> {
+    fn update_traffic_levels(&mut self) -> Result<(), GameError> {
         let Some(mut situation_card) = self.situation_card.clone() else {
-            return Err("There is no situation card in this game and it's therefore not possible to update the traffic levels!".to_string());
+            return Err(GameError::Conflict("There is no situation card in this game and it's therefore not possible to update the traffic levels!".to_string()));
         };
 
         let mut new_cost_tuples = Vec::new();
 
-        let situation_cards = situation_card_list();
-        let Some(original_card) = situation_cards.iter().find(|c| c.card_id == situation_card.card_id) else {
-            return Err("The situation card in the game has an ID was not found in the list of situation cards!".to_string());
+        let Ok(original_card) = self.get_situation_card_by_id(situation_card.card_id) else {
+            return Err(GameError::Internal("The situation card in the game has an ID was not found in the list of situation cards!".to_string()));
         };
         let original_costs = original_card.costs.clone();
 
@@ -550,7 +963,7 @@ impl GameState {
                 }
 
                 let Some(vehicle_type) = modifier.vehicle_type else {
-                    return Err("There was no vehicle type associated with the access modifier and can therefore not update the traffic levels!".to_string());
+                    return Err(GameError::InvalidInput(InvalidInputReason::MissingData, "There was no vehicle type associated with the access modifier and can therefore not update the traffic levels!".to_string()));
                 };
 
                 if !is_access_modifier_used {
@@ -585,7 +998,7 @@ impl GameState {
         &mut self,
         edge_restriction: &EdgeRestriction,
         modifiable: bool,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         match self.map.set_restriction_on_edge(edge_restriction, modifiable) {
             Ok(_) => (),
             Err(e) => return Err(e),
@@ -599,7 +1012,7 @@ impl GameState {
     pub fn remove_restriction_from_edge(
         &mut self,
         edge_restriction: &EdgeRestriction,
-    ) -> Result<(), String> {
+    ) -> Result<(), GameError> {
         match self
             .map
             .remove_restriction_from_edge(edge_restriction)
@@ -613,4 +1026,487 @@ impl GameState {
         });
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Serializes when the turn ends as the amount of seconds left of the turn, rounded up.
+fn serialize_turn_deadline<S: Serializer>(turn_deadline: &Option<Instant>, serializer: S) -> Result<S::Ok, S::Error> {
+    turn_deadline
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64)
+        .serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{player, small_game, ORCHESTRATOR_ID, PLAYER_ID};
+
+    fn priority(district: District, vehicle_type: RestrictionType, movement_value: Option<MovementValue>) -> DistrictModifier {
+        DistrictModifier {
+            district,
+            modifier: DistrictModifierType::Priority,
+            vehicle_type: Some(vehicle_type),
+            associated_movement_value: movement_value,
+            associated_money_value: None,
+            delete: false,
+        }
+    }
+
+    fn toll(district: District, vehicle_type: Option<RestrictionType>, money_value: Option<Money>) -> DistrictModifier {
+        DistrictModifier {
+            district,
+            modifier: DistrictModifierType::Toll,
+            vehicle_type,
+            associated_movement_value: None,
+            associated_money_value: money_value,
+            delete: false,
+        }
+    }
+
+    fn remaining_moves(game: &GameState) -> MovesRemaining {
+        player(game).vehicles[0].remaining_moves
+    }
+
+    fn money_of(game: &GameState, player_id: PlayerID) -> Money {
+        game.get_player_with_unique_id(player_id).unwrap().money
+    }
+
+    #[test]
+    fn toll_is_paid_once_per_turn_to_the_orchestrator() {
+        let mut game = small_game();
+        game.district_modifiers.push(toll(District::CityCentre, None, Some(3)));
+        let start_money = money_of(&game, PLAYER_ID);
+
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        assert_eq!(money_of(&game, PLAYER_ID), start_money);
+        game.move_player_with_id(PLAYER_ID, 0, 2).unwrap();
+        game.move_player_with_id(PLAYER_ID, 0, 3).unwrap();
+
+        assert_eq!(money_of(&game, PLAYER_ID), start_money - 3);
+        assert_eq!(money_of(&game, ORCHESTRATOR_ID), 3);
+    }
+
+    #[test]
+    fn toll_is_paid_without_an_objective_card() {
+        let mut game = small_game();
+        game.district_modifiers.push(toll(District::IndustryPark, None, Some(2)));
+        game.players[1].objective_card = None;
+        let start_money = money_of(&game, PLAYER_ID);
+
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+
+        assert_eq!(money_of(&game, PLAYER_ID), start_money - 2);
+    }
+
+    #[test]
+    fn toll_is_waived_for_the_vehicle_type_of_the_modifier() {
+        let modifier = toll(District::CityCentre, Some(RestrictionType::Electric), Some(4));
+
+        assert_eq!(GameState::get_toll_cost(&modifier, &[RestrictionType::Electric]).unwrap(), 0);
+        assert_eq!(GameState::get_toll_cost(&modifier, &[RestrictionType::Heavy]).unwrap(), 4);
+        assert_eq!(GameState::get_toll_cost(&modifier, &[]).unwrap(), 4);
+    }
+
+    #[test]
+    fn toll_cost_of_an_invalid_toll_modifier_is_an_error() {
+        let mut not_a_toll = toll(District::CityCentre, None, Some(4));
+        not_a_toll.modifier = DistrictModifierType::Priority;
+        let toll_without_money = toll(District::CityCentre, None, None);
+
+        assert!(matches!(GameState::get_toll_cost(&not_a_toll, &[]), Err(GameError::Internal(_))));
+        assert!(matches!(GameState::get_toll_cost(&toll_without_money, &[]), Err(GameError::Internal(_))));
+    }
+
+    #[test]
+    fn toll_with_a_negative_or_missing_money_value_can_not_be_added() {
+        let mut game = small_game();
+
+        assert!(game.add_district_modifier(toll(District::CityCentre, None, Some(-1))).is_err());
+        assert!(game.add_district_modifier(toll(District::CityCentre, None, None)).is_err());
+        assert!(game.district_modifiers.is_empty());
+    }
+
+    #[test]
+    fn players_start_with_money_and_the_orchestrator_without() {
+        let mut game = small_game();
+        game.players[0].money = 5;
+        game.players[1].money = 1;
+
+        game.reset_player_in_game_data();
+
+        assert_eq!(money_of(&game, ORCHESTRATOR_ID), 0);
+        assert_eq!(money_of(&game, PLAYER_ID), game.get_starting_player_money_value());
+    }
+
+    #[test]
+    fn district_cost_is_paid_the_first_time_a_vehicle_enters_a_district_in_a_turn() {
+        let mut game = small_game();
+        game.map.change_neighbourhood_cost(District::CityCentre, 2);
+        let start_moves = remaining_moves(&game);
+
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        game.move_player_with_id(PLAYER_ID, 0, 2).unwrap();
+        game.move_player_with_id(PLAYER_ID, 0, 3).unwrap();
+
+        assert_eq!(remaining_moves(&game), start_moves - 3 - 2);
+    }
+
+    #[test]
+    fn priority_lane_waives_the_district_cost_without_bonus_moves() {
+        let mut game = small_game();
+        game.map.change_neighbourhood_cost(District::CityCentre, 2);
+        game.district_modifiers.push(priority(District::CityCentre, RestrictionType::Destination, Some(2)));
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        let moves_before = remaining_moves(&game);
+
+        game.move_player_with_id(PLAYER_ID, 0, 2).unwrap();
+
+        assert_eq!(remaining_moves(&game), moves_before - 1);
+    }
+
+    #[test]
+    fn priority_lane_only_applies_to_its_vehicle_type() {
+        let mut game = small_game();
+        let modifiers = vec![priority(District::Suburbs, RestrictionType::Electric, None)];
+        let mut vehicle = player(&game).vehicles[0].clone();
+
+        assert!(!GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::Suburbs));
+        vehicle.vehicle_types.push(RestrictionType::Electric);
+        assert!(GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::Suburbs));
+        assert!(!GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::CityCentre));
+
+        game.players[1].objective_card = None;
+        assert!(!GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::Suburbs));
+    }
+
+    #[test]
+    fn destination_priority_only_applies_to_players_with_an_objective_in_the_district() {
+        let game = small_game();
+        let vehicle = player(&game).vehicles[0].clone();
+        let modifiers = vec![
+            priority(District::CityCentre, RestrictionType::Destination, None),
+            priority(District::Airport, RestrictionType::Destination, None),
+        ];
+
+        assert!(GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::CityCentre));
+        assert!(!GameState::vehicle_has_priority_in_district(&modifiers, &game.map, &player(&game), &vehicle, District::Airport));
+    }
+
+    #[test]
+    fn priority_without_a_vehicle_type_can_not_be_added() {
+        let mut game = small_game();
+        let mut modifier = priority(District::CityCentre, RestrictionType::Electric, None);
+        modifier.vehicle_type = None;
+
+        assert!(game.add_district_modifier(modifier).is_err());
+    }
+
+    fn deliver_objective(game: &mut GameState) {
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        game.update_objective_status().unwrap();
+        game.move_player_with_id(PLAYER_ID, 0, 2).unwrap();
+        game.move_player_with_id(PLAYER_ID, 0, 3).unwrap();
+        game.update_objective_status().unwrap();
+    }
+
+    #[test]
+    fn game_ends_with_scores_when_every_objective_is_delivered() {
+        let mut game = small_game();
+        assert!(!game.all_objectives_delivered());
+
+        deliver_objective(&mut game);
+        assert!(game.all_objectives_delivered());
+        game.next_player_turn();
+
+        let results = game.game_results.clone().expect("The game is over");
+        assert_eq!(results.reason, GameEndReason::AllObjectivesDelivered);
+        let score = results.player_scores.iter().find(|score| score.player_id == PLAYER_ID).unwrap();
+        assert!(score.delivered_objective);
+        assert_eq!(score.statistics.turns_used, 1);
+        assert!(score.statistics.has_finished);
+        assert!(game.is_lobby);
+        assert_eq!(game.current_players_turn, InGameID::Orchestrator);
+    }
+
+    #[test]
+    fn game_ends_when_the_round_limit_is_reached() {
+        let mut game = small_game();
+        game.settings.max_round_count = Some(2);
+
+        // The round ends when the turn goes back to the orchestrator.
+        game.next_player_turn();
+        assert_eq!(game.rounds_played, 1);
+        assert!(!game.is_game_over());
+        game.next_player_turn();
+        game.next_player_turn();
+
+        assert_eq!(game.game_results.map(|results| results.reason), Some(GameEndReason::RoundLimitReached));
+        assert_eq!(game.rounds_played, 2);
+    }
+
+    #[test]
+    fn game_without_a_round_limit_goes_on_after_many_rounds() {
+        let mut game = small_game();
+
+        for _ in 0..20 {
+            game.next_player_turn();
+        }
+
+        assert_eq!(game.rounds_played, 10);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn game_without_players_has_not_delivered_every_objective() {
+        let mut game = small_game();
+        game.players.retain(|player| player.in_game_id == InGameID::Orchestrator);
+
+        assert!(!game.all_objectives_delivered());
+    }
+
+    #[test]
+    fn emissions_depend_on_the_vehicle_types_of_the_moving_vehicle() {
+        for (vehicle_types, expected_emissions) in [(vec![], 1), (vec![RestrictionType::Electric], 0), (vec![RestrictionType::Heavy], 2)] {
+            let mut game = small_game();
+            game.players[1].vehicles[0].vehicle_types = vehicle_types;
+            let moves_before = remaining_moves(&game);
+
+            game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+            game.record_movement_statistics(PLAYER_ID, 0, 0, moves_before).unwrap();
+
+            let statistics = player(&game).statistics;
+            assert_eq!(statistics.moves_spent, 1);
+            assert_eq!(statistics.emissions, expected_emissions);
+        }
+    }
+
+    #[test]
+    fn rail_movement_makes_no_emissions() {
+        let mut game = small_game();
+        for relationship in game.map.edges.values_mut().flatten() {
+            relationship.is_connected_through_rail = true;
+        }
+        let moves_before = remaining_moves(&game);
+
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        game.record_movement_statistics(PLAYER_ID, 0, 0, moves_before).unwrap();
+
+        assert_eq!(player(&game).statistics.emissions, 0);
+    }
+
+    #[test]
+    fn state_hash_is_the_same_for_the_same_game_state() {
+        let game = small_game();
+
+        assert_eq!(game.state_hash(), small_game().state_hash());
+    }
+
+    #[test]
+    fn state_hash_changes_when_the_game_state_changes() {
+        let game = small_game();
+        let mut moved_game = game.clone();
+        moved_game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+
+        assert_ne!(game.state_hash(), moved_game.state_hash());
+    }
+
+    fn game_with_turn_time_limit(seconds: u64) -> GameState {
+        let mut game = small_game();
+        game.settings.turn_time_limit_seconds = Some(seconds);
+        game
+    }
+
+    #[test]
+    fn turn_timer_starts_when_the_turn_starts() {
+        let mut game = game_with_turn_time_limit(60);
+
+        game.next_player_turn();
+
+        assert!(matches!(game.turn_deadline, Some(deadline) if deadline > Instant::now()));
+        assert!(!game.is_turn_time_up());
+    }
+
+    #[test]
+    fn turn_timer_is_not_started_without_a_limit_or_in_the_lobby() {
+        let mut game_without_limit = small_game();
+        let mut lobby = game_with_turn_time_limit(60);
+        lobby.is_lobby = true;
+
+        game_without_limit.restart_turn_timer();
+        lobby.restart_turn_timer();
+
+        assert_eq!(game_without_limit.turn_deadline, None);
+        assert_eq!(lobby.turn_deadline, None);
+        assert!(!lobby.is_turn_time_up());
+    }
+
+    #[test]
+    fn turn_time_limit_too_long_for_an_instant_is_no_limit() {
+        let mut game = game_with_turn_time_limit(u64::MAX);
+
+        game.restart_turn_timer();
+
+        assert_eq!(game.turn_deadline, None);
+    }
+
+    #[test]
+    fn turn_time_is_up_when_the_deadline_has_passed() {
+        let mut game = game_with_turn_time_limit(60);
+        game.turn_deadline = Instant::now().checked_sub(Duration::from_secs(1));
+
+        assert!(game.is_turn_time_up());
+    }
+
+    #[test]
+    fn turn_deadline_is_sent_as_the_seconds_left_of_the_turn() {
+        let mut game = game_with_turn_time_limit(60);
+        game.restart_turn_timer();
+
+        let json = serde_json::to_value(&game).unwrap();
+
+        assert_eq!(json["turn_time_remaining_seconds"], 60);
+    }
+
+    #[test]
+    fn state_hash_does_not_depend_on_the_time_left_of_the_turn() {
+        let mut game = game_with_turn_time_limit(60);
+        game.restart_turn_timer();
+        let mut later_game = game.clone();
+        later_game.turn_deadline = Instant::now().checked_add(Duration::from_secs(10));
+
+        assert_eq!(game.state_hash(), later_game.state_hash());
+    }
+
+    #[test]
+    fn spectator_that_joins_as_a_player_stops_being_a_spectator() {
+        let mut game = GameState::new("Game".to_string(), 1);
+        game.add_spectator(Spectator::new(PLAYER_ID, "Spectator".to_string())).unwrap();
+
+        game.assign_player_to_game(Player::new(PLAYER_ID, "Player".to_string())).unwrap();
+
+        assert!(game.spectators.is_empty());
+        assert!(game.contains_player_with_unique_id(PLAYER_ID));
+        assert!(matches!(game.add_spectator(Spectator::new(PLAYER_ID, "Spectator".to_string())), Err(GameError::Conflict(_))));
+    }
+
+    #[test]
+    fn state_hash_does_not_depend_on_the_spectators_or_disconnected_players() {
+        let game = small_game();
+        let mut followed_game = game.clone();
+        followed_game.add_spectator(Spectator::new(ORCHESTRATOR_ID + PLAYER_ID + 1, "Spectator".to_string())).unwrap();
+        followed_game.set_player_disconnected(PLAYER_ID, true);
+
+        assert_eq!(game.state_hash(), followed_game.state_hash());
+    }
+
+    const OTHER_PLAYER_ID: PlayerID = PLAYER_ID + 1;
+
+    /// Returns the small game with a second player, whose vehicle is an emergency vehicle.
+    fn game_with_two_players() -> GameState {
+        let mut game = small_game();
+        let mut other_player = player(&game);
+        other_player.unique_id = OTHER_PLAYER_ID;
+        other_player.in_game_id = InGameID::PlayerTwo;
+        other_player.vehicles[0].vehicle_types = vec![RestrictionType::Emergency];
+        game.players.push(other_player);
+        game.players[1].vehicles[0].vehicle_types = vec![RestrictionType::Heavy];
+        game
+    }
+
+    fn is_hidden(game: &GameState, player_id: PlayerID) -> bool {
+        let player = game.get_player_with_unique_id(player_id).unwrap();
+        player.objective_card.is_none() && player.vehicles.iter().all(|vehicle| vehicle.vehicle_types.is_empty())
+    }
+
+    #[test]
+    fn player_only_sees_their_own_objective_card_and_vehicle_types() {
+        let game = game_with_two_players();
+
+        let view = game.view_for(Some(PLAYER_ID));
+
+        assert!(player(&view).objective_card.is_some());
+        assert_eq!(player(&view).vehicles[0].vehicle_types, vec![RestrictionType::Heavy]);
+        assert!(is_hidden(&view, OTHER_PLAYER_ID));
+    }
+
+    #[test]
+    fn nothing_is_shown_without_a_viewer() {
+        let game = game_with_two_players();
+
+        let view = game.view_for(None);
+
+        assert!(is_hidden(&view, PLAYER_ID));
+        assert!(is_hidden(&view, OTHER_PLAYER_ID));
+    }
+
+    #[test]
+    fn everything_is_shown_to_the_orchestrator_in_a_revealed_game_and_in_a_finished_game() {
+        let game = game_with_two_players();
+        let mut revealed_game = game.clone();
+        revealed_game.settings.reveal_objective_cards = true;
+        let mut finished_game = game.clone();
+        finished_game.end_game(GameEndReason::RoundLimitReached);
+
+        let views = [game.view_for(Some(ORCHESTRATOR_ID)), revealed_game.view_for(Some(PLAYER_ID)), finished_game.view_for(None)];
+
+        for view in views {
+            assert!(!is_hidden(&view, PLAYER_ID));
+            assert!(!is_hidden(&view, OTHER_PLAYER_ID));
+        }
+    }
+
+    /// Returns the small game where the player has a second vehicle at the same node as the first one.
+    fn game_with_two_vehicles() -> GameState {
+        let mut game = small_game();
+        let second_vehicle = Vehicle { id: 1, ..game.players[1].vehicles[0].clone() };
+        game.players[1].vehicles.push(second_vehicle);
+        game
+    }
+
+    #[test]
+    fn every_vehicle_pays_the_district_cost_the_first_time_it_enters_the_district() {
+        let mut game = game_with_two_vehicles();
+        game.map.change_neighbourhood_cost(District::IndustryPark, 2);
+        let start_moves = remaining_moves(&game);
+
+        game.move_player_with_id(PLAYER_ID, 0, 1).unwrap();
+        game.move_player_with_id(PLAYER_ID, 1, 1).unwrap();
+
+        let vehicles = player(&game).vehicles;
+        assert_eq!(vehicles[0].remaining_moves, start_moves - 1 - 2);
+        assert_eq!(vehicles[1].remaining_moves, start_moves - 1 - 2);
+    }
+
+    #[test]
+    fn only_the_vehicle_that_picked_up_the_package_can_drop_it_off() {
+        let mut game = game_with_two_vehicles();
+        game.move_player_with_id(PLAYER_ID, 1, 1).unwrap();
+        game.update_objective_status().unwrap();
+        for node_id in [1, 4, 3] {
+            game.move_player_with_id(PLAYER_ID, 0, node_id).unwrap();
+        }
+
+        game.update_objective_status().unwrap();
+
+        assert!(player(&game).vehicles[1].has_package);
+        assert!(!player(&game).objective_card.unwrap().dropped_package_off);
+
+        game.move_player_with_id(PLAYER_ID, 1, 2).unwrap();
+        game.move_player_with_id(PLAYER_ID, 1, 3).unwrap();
+        game.update_objective_status().unwrap();
+
+        assert!(!player(&game).vehicles[1].has_package);
+        assert!(player(&game).objective_card.unwrap().dropped_package_off);
+    }
+
+    #[test]
+    fn every_player_gets_their_fleet_at_the_start_node_of_their_objective_card() {
+        let mut game = small_game();
+        game.settings.vehicles_per_player = 3;
+
+        game.assign_random_objective_card_to_players().unwrap();
+
+        let vehicles = player(&game).vehicles;
+        assert_eq!(vehicles.iter().map(|vehicle| vehicle.id).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(vehicles.iter().all(|vehicle| vehicle.position_node_id == Some(0)));
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{custom_types::{NodeID, MovementCost}, enums::{district::District, restriction_type::RestrictionType}};
+
+use super::{edge_restriction::EdgeRestriction, node::Node, node_map::NodeMap};
+
+/// The MapDefinition struct describes a board map as it is written in a map file.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MapDefinition {
+    pub name: String,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<EdgeDefinition>,
+}
+
+/// The EdgeDefinition struct describes an edge between two nodes as it is written in a map file. The `restriction` is put on the edge when the map is made and can not be removed by the orchestrator.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EdgeDefinition {
+    pub node_one: NodeID,
+    pub node_two: NodeID,
+    pub district: District,
+    #[serde(default = "EdgeDefinition::default_movement_cost")]
+    pub movement_cost: MovementCost,
+    #[serde(default)]
+    pub is_connected_through_rail: bool,
+    #[serde(default)]
+    pub restriction: Option<RestrictionType>,
+}
+
+impl EdgeDefinition {
+    const fn default_movement_cost() -> MovementCost {
+        1
+    }
+
+    fn connects(&self, node_one: NodeID, node_two: NodeID) -> bool {
+        (self.node_one == node_one && self.node_two == node_two)
+            || (self.node_one == node_two && self.node_two == node_one)
+    }
+}
+
+impl MapDefinition {
+    /// Checks that the map is valid and turns it into a NodeMap. The map is not valid if it has no nodes, has more than one node with the same ID, has edges to nodes that do not exist or has nodes that can not be reached from the other nodes. Returns an error describing the first problem found.
+    pub fn into_node_map(self) -> Result<NodeMap, String> {
+        let Some(first_node) = self.nodes.first() else {
+            return Err(format!("The map {} has no nodes!", self.name));
+        };
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if self.nodes[..index].iter().any(|n| n.id == node.id) {
+                return Err(format!("The map {} has more than one node with ID {}!", self.name, node.id));
+            }
+        }
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            for node_id in [edge.node_one, edge.node_two] {
+                if self.nodes.iter().all(|node| node.id != node_id) {
+                    return Err(format!("The map {} has an edge between node {} and {}, but there is no node with ID {}!", self.name, edge.node_one, edge.node_two, node_id));
+                }
+            }
+            if edge.node_one == edge.node_two {
+                return Err(format!("The map {} has an edge from node {} to itself!", self.name, edge.node_one));
+            }
+            if self.edges[..index].iter().any(|e| e.connects(edge.node_one, edge.node_two)) {
+                return Err(format!("The map {} has more than one edge between node {} and {}!", self.name, edge.node_one, edge.node_two));
+            }
+        }
+
+        let mut reached_node_ids = vec![first_node.id];
+        let mut nodes_to_visit = vec![first_node.id];
+        while let Some(node_id) = nodes_to_visit.pop() {
+            for edge in self.edges.iter() {
+                let neighbour_id = if edge.node_one == node_id {
+                    edge.node_two
+                } else if edge.node_two == node_id {
+                    edge.node_one
+                } else {
+                    continue;
+                };
+                if !reached_node_ids.contains(&neighbour_id) {
+                    reached_node_ids.push(neighbour_id);
+                    nodes_to_visit.push(neighbour_id);
+                }
+            }
+        }
+        let disconnected_node_ids: Vec<NodeID> = self
+            .nodes
+            .iter()
+            .map(|node| node.id)
+            .filter(|id| !reached_node_ids.contains(id))
+            .collect();
+        if !disconnected_node_ids.is_empty() {
+            return Err(format!("The map {} has nodes that can not be reached from node {}: {:?}", self.name, first_node.id, disconnected_node_ids));
+        }
+
+        let mut map = NodeMap::new();
+        map.nodes = self.nodes;
+        for edge in self.edges.iter() {
+            map.add_relationship(edge.node_one, edge.node_two, edge.district, edge.movement_cost, edge.is_connected_through_rail);
+        }
+        for edge in self.edges.iter() {
+            let Some(restriction) = edge.restriction else {
+                continue;
+            };
+            if let Err(e) = map.set_restriction_on_edge(&EdgeRestriction::new(edge.node_one, edge.node_two, restriction), false) {
+                return Err(format!("Failed to put the restriction on the edge between node {} and {} in the map {} because: {}", edge.node_one, edge.node_two, self.name, e));
+            }
+        }
+
+        let mut neighbourhood = District::first();
+        map.change_neighbourhood_cost(neighbourhood, 1);
+        while let Some(n) = neighbourhood.next() {
+            neighbourhood = n;
+            map.change_neighbourhood_cost(n, 1);
+        }
+
+        Ok(map)
+    }
+}
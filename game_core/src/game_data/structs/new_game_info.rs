@@ -1,10 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-use super::player::Player;
+use super::{player::Player, rule_set::RuleSet, game_settings::GameSettingsChanges};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct NewGameInfo {
     pub host: Player,
     pub name: String,
+    /// The name of the map the game is played on. The default map is used if no map is given.
+    #[serde(default)]
+    pub map_name: Option<String>,
+    /// The rules that are turned on or off in the game compared to the rules that are used by default.
+    #[serde(default)]
+    pub rule_set: RuleSet,
+    /// The game balance values of the game that are different from the default settings of the server.
+    #[serde(default)]
+    pub settings: GameSettingsChanges,
 }
 
@@ -6,7 +6,9 @@ use crate::game_data::custom_types::NodeID;
 pub struct Node {
     pub id: NodeID,
     pub name: String,
+    #[serde(default)]
     pub is_connected_to_rail: bool,
+    #[serde(default)]
     pub is_parking_spot: bool,
 }
 
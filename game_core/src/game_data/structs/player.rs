@@ -1,46 +1,183 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_data::{custom_types::{GameID, PlayerID, MovesRemaining, NodeID}, enums::in_game_id::InGameID};
+use crate::{bots::BotStrategy, game_data::{custom_types::{GameID, PlayerID, Money, VehicleID, NodeID, MovesRemaining}, enums::{in_game_id::InGameID, game_error::GameError}}};
 
-use super::player_objective_card::PlayerObjectiveCard;
+use super::{player_objective_card::PlayerObjectiveCard, player_statistics::PlayerStatistics, vehicle::Vehicle};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(from = "StoredPlayer")]
 pub struct Player {
     pub connected_game_id: Option<GameID>,
     pub in_game_id: InGameID,
     pub unique_id: PlayerID,
     pub name: String,
-    pub position_node_id: Option<NodeID>,
-    pub remaining_moves: MovesRemaining,
+    /// The vehicles the player moves around the map. The players get their vehicles at the start node of their objective card when the game starts, and the orchestrator has no vehicles.
+    #[serde(default)]
+    pub vehicles: Vec<Vehicle>,
     pub objective_card: Option<PlayerObjectiveCard>,
-    pub is_bus: bool,
+    #[serde(default)]
+    pub money: Money,
+    #[serde(default)]
+    pub statistics: PlayerStatistics,
+    /// The strategy of the player if it's a bot played by the server. It's stored with the game, so that the bot keeps playing after the server is restarted.
+    #[serde(default)]
+    pub bot_strategy: Option<BotStrategy>,
+    /// If the player has not checked in within the player timeout. The player keeps their seat until the grace period for disconnected players is over, but their turns are skipped.
+    #[serde(default)]
+    pub is_disconnected: bool,
 }
 
 impl Player {
     /// Creates a new Player with default values defined in the function.
     #[must_use]
     pub const fn new(unique_id: PlayerID, name: String) -> Self {
-        let is_bus = false;
         Self {
             connected_game_id: None,
             in_game_id: InGameID::Undecided,
             unique_id,
             name,
-            position_node_id: None,
-            remaining_moves: 0,
+            vehicles: Vec::new(),
             objective_card: None,
-            is_bus,
+            money: 0,
+            statistics: PlayerStatistics::new(),
+            bot_strategy: None,
+            is_disconnected: false,
         }
     }
 
-    /// Sets the is_bus field to true.
-    pub fn transform_to_bus(&mut self) {
-        self.is_bus = true;
+    /// Gets the vehicle of the player with the given id. Will return an error if the player has no vehicle with the id.
+    pub fn get_vehicle(&self, vehicle_id: VehicleID) -> Result<&Vehicle, GameError> {
+        self.vehicles
+            .iter()
+            .find(|vehicle| vehicle.id == vehicle_id)
+            .ok_or_else(|| GameError::NotFound(format!("The player {} does not have a vehicle with id {}!", self.name, vehicle_id)))
     }
 
-    /// Sets the is_bus field to false.
-    pub fn transform_to_car(&mut self) {
-        self.is_bus = false;
+    /// Gets the vehicle of the player with the given id so that it can be changed. Will return an error if the player has no vehicle with the id.
+    pub fn get_vehicle_mut(&mut self, vehicle_id: VehicleID) -> Result<&mut Vehicle, GameError> {
+        let name = &self.name;
+        self.vehicles
+            .iter_mut()
+            .find(|vehicle| vehicle.id == vehicle_id)
+            .ok_or_else(|| GameError::NotFound(format!("The player {} does not have a vehicle with id {}!", name, vehicle_id)))
+    }
+}
+
+/// The StoredPlayer struct is what a [`Player`] is deserialized from. Games stored before the players had a fleet of vehicles have the position, the remaining moves and the bus state on the player itself, and those players get a single vehicle with them.
+#[derive(Deserialize)]
+struct StoredPlayer {
+    connected_game_id: Option<GameID>,
+    in_game_id: InGameID,
+    unique_id: PlayerID,
+    name: String,
+    #[serde(default)]
+    vehicles: Vec<Vehicle>,
+    objective_card: Option<PlayerObjectiveCard>,
+    #[serde(default)]
+    money: Money,
+    #[serde(default)]
+    statistics: PlayerStatistics,
+    #[serde(default)]
+    is_disconnected: bool,
+    #[serde(default)]
+    bot_strategy: Option<BotStrategy>,
+    #[serde(default)]
+    position_node_id: Option<NodeID>,
+    #[serde(default)]
+    remaining_moves: MovesRemaining,
+    #[serde(default)]
+    is_bus: bool,
+}
+
+impl From<StoredPlayer> for Player {
+    fn from(stored_player: StoredPlayer) -> Self {
+        let mut vehicles = stored_player.vehicles;
+        if let (true, Some(position_node_id)) = (vehicles.is_empty(), stored_player.position_node_id) {
+            let objective_card = stored_player.objective_card.as_ref();
+            vehicles.push(Vehicle {
+                id: 0,
+                position_node_id: Some(position_node_id),
+                remaining_moves: stored_player.remaining_moves,
+                is_bus: stored_player.is_bus,
+                vehicle_types: objective_card.map(|card| card.special_vehicle_types.clone()).unwrap_or_default(),
+                has_package: matches!(objective_card, Some(card) if card.picked_package_up && !card.dropped_package_off),
+            });
+        }
+        Self {
+            connected_game_id: stored_player.connected_game_id,
+            in_game_id: stored_player.in_game_id,
+            unique_id: stored_player.unique_id,
+            name: stored_player.name,
+            vehicles,
+            objective_card: stored_player.objective_card,
+            money: stored_player.money,
+            statistics: stored_player.statistics,
+            is_disconnected: stored_player.is_disconnected,
+            bot_strategy: stored_player.bot_strategy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::game_data::enums::{restriction_type::RestrictionType, type_entities_to_transport::TypeEntitiesToTransport};
+
+    fn objective_card_with_package() -> PlayerObjectiveCard {
+        let mut objective_card = PlayerObjectiveCard::new("Delivery".to_string(), 0, 1, 3, vec![RestrictionType::Emergency], TypeEntitiesToTransport::Packages, 1);
+        objective_card.picked_package_up = true;
+        objective_card
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn player_stored_before_fleets_gets_a_single_vehicle() {
+        let stored_player = json!({
+            "connected_game_id": 1,
+            "in_game_id": "PlayerOne",
+            "unique_id": 2,
+            "name": "Player",
+            "objective_card": objective_card_with_package(),
+            "position_node_id": 4,
+            "remaining_moves": 3,
+            "is_bus": true,
+        });
+
+        let player: Player = serde_json::from_value(stored_player).unwrap();
+
+        assert_eq!(player.vehicles, vec![Vehicle {
+            id: 0,
+            position_node_id: Some(4),
+            remaining_moves: 3,
+            is_bus: true,
+            vehicle_types: vec![RestrictionType::Emergency],
+            has_package: true,
+        }]);
+    }
+
+    #[test]
+    fn player_with_a_fleet_keeps_their_vehicles() {
+        let mut player = Player::new(2, "Player".to_string());
+        player.vehicles = vec![Vehicle::new(0, 1, 3, Vec::new()), Vehicle::new(1, 2, 3, vec![RestrictionType::Electric])];
+
+        let stored_player: Player = serde_json::from_value(json!(player)).unwrap();
+
+        assert_eq!(stored_player.vehicles, player.vehicles);
+    }
+
+    #[test]
+    fn player_stored_without_a_position_gets_no_vehicles() {
+        let stored_player = json!({
+            "connected_game_id": null,
+            "in_game_id": "Undecided",
+            "unique_id": 2,
+            "name": "Player",
+            "objective_card": null,
+        });
+
+        let player: Player = serde_json::from_value(stored_player).unwrap();
+
+        assert!(player.vehicles.is_empty());
+    }
+}
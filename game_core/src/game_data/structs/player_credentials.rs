@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::custom_types::{PlayerID, PlayerToken};
+
+/// The PlayerCredentials struct contains a new player id and the secret token that proves that a request is made by the player with that id.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PlayerCredentials {
+    pub player_id: PlayerID,
+    pub token: PlayerToken,
+    /// The secret code that lets the player take their seat back and get a new token, if the token is lost. Unlike the token it does not change when the player reconnects.
+    pub reconnect_code: PlayerToken,
+}
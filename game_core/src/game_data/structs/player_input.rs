@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_data::{custom_types::{PlayerID, GameID, NodeID, SituationCardID}, enums::{player_input_type::PlayerInputType, in_game_id::InGameID}};
+use crate::game_data::{custom_types::{PlayerID, GameID, NodeID, SituationCardID, VehicleID}, enums::{player_input_type::PlayerInputType, in_game_id::InGameID}};
 
 use super::{district_modifier::DistrictModifier, edge_restriction::EdgeRestriction};
 
 /// The PlayerInput struct describes the input of a player.
 /// 
 /// The option values should be set to something based on the input_type.
+/// Movements and bus changes are done by the vehicle with the id `vehicle_id`, which is the first vehicle of the player if it's not given.
+/// A movement is either a single step to `related_node_id`, or a whole path through `related_path` which is checked and applied as one action.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct PlayerInput {
     pub player_id: PlayerID,
@@ -18,4 +20,9 @@ pub struct PlayerInput {
     pub situation_card_id: Option<SituationCardID>,
     pub edge_modifier: Option<EdgeRestriction>,
     pub related_bool: Option<bool>,
+    /// The nodes to move through in order, not including the node the player is at. Used instead of `related_node_id` to move several steps at once.
+    #[serde(default)]
+    pub related_path: Option<Vec<NodeID>>,
+    #[serde(default)]
+    pub vehicle_id: VehicleID,
 }
\ No newline at end of file
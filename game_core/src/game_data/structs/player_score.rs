@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{custom_types::{PlayerID, Money}, enums::in_game_id::InGameID};
+
+use super::{player::Player, player_statistics::PlayerStatistics};
+
+/// The PlayerScore struct describes how well a player did in a finished game.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PlayerScore {
+    pub player_id: PlayerID,
+    pub name: String,
+    pub in_game_id: InGameID,
+    pub delivered_objective: bool,
+    pub money: Money,
+    pub statistics: PlayerStatistics,
+}
+
+impl PlayerScore {
+    /// Creates a new PlayerScore based on the current state of the given player.
+    #[must_use]
+    pub fn from_player(player: &Player) -> Self {
+        Self {
+            player_id: player.unique_id,
+            name: player.name.clone(),
+            in_game_id: player.in_game_id,
+            delivered_objective: matches!(&player.objective_card, Some(card) if card.dropped_package_off),
+            money: player.money,
+            statistics: player.statistics.clone(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::custom_types::MovementCost;
+
+/// The PlayerStatistics struct keeps track of what a player has done during a game, and is used to score the player when the game ends.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct PlayerStatistics {
+    /// The amount of turns the player used before delivering their objective.
+    pub turns_used: u32,
+    /// The total amount of movement points the player has spent.
+    pub moves_spent: MovementCost,
+    /// The emissions made by the player's vehicle, based on the vehicle types on the player's objective card.
+    pub emissions: u32,
+    /// Whether the player had delivered their objective when their last turn ended.
+    pub has_finished: bool,
+}
+
+impl PlayerStatistics {
+    /// Creates new empty PlayerStatistics.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            turns_used: 0,
+            moves_spent: 0,
+            emissions: 0,
+            has_finished: false,
+        }
+    }
+}
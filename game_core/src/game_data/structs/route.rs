@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::custom_types::{MovementCost, MovesRemaining, NodeID};
+
+/// The Route struct describes the cheapest way for a player to move from the node they are at to another node.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub to: NodeID,
+    /// The nodes the player moves through in order, not including the node the player starts at, but including the node the route goes to.
+    pub path: Vec<NodeID>,
+    /// How many moves the route costs in total, including the cost of entering new districts and any bonus moves gained on the way.
+    pub moves_spent: MovementCost,
+    /// How many moves the player has left after following the route.
+    pub remaining_moves: MovesRemaining,
+}
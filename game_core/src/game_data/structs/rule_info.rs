@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// The RuleInfo struct describes a rule of the rule checker, so that the client can show which rules can be turned on and off for a game.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RuleInfo {
+    pub name: String,
+    pub description: String,
+    /// If the rule is used in a game where the rule set does not say anything about it.
+    pub enabled_by_default: bool,
+    /// If the rule can be turned off. The rules the game can not be played without can not be turned off.
+    pub can_be_disabled: bool,
+}
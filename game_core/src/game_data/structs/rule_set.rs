@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::enums::game_error::{GameError, InvalidInputReason};
+
+use super::rule_info::RuleInfo;
+
+/// The RuleSet struct describes which rules of the rule checker are used in a game, as changes from the rules that are used by default.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    /// The names of the rules that are used in the game even though they are not used by default.
+    #[serde(default)]
+    pub enabled_rules: Vec<String>,
+    /// The names of the rules that are not used in the game even though they are used by default.
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+}
+
+impl RuleSet {
+    /// Returns true if the rule with the given name is used in a game with this rule set.
+    pub fn is_rule_enabled(&self, name: &str, enabled_by_default: bool) -> bool {
+        match enabled_by_default {
+            true => !self.disabled_rules.iter().any(|rule| rule == name),
+            false => self.enabled_rules.iter().any(|rule| rule == name),
+        }
+    }
+
+    /// Checks that every rule in the rule set is one of the given rules, that no rule is both enabled and disabled and that only rules that can be disabled are disabled. Will return an error if the rule set is not valid.
+    pub fn validate(&self, rules: &[RuleInfo]) -> Result<(), GameError> {
+        for name in self.enabled_rules.iter().chain(self.disabled_rules.iter()) {
+            if rules.iter().all(|rule| &rule.name != name) {
+                return Err(invalid_rule_set(format!("There is no rule with the name {}!", name)));
+            }
+        }
+        for name in self.disabled_rules.iter() {
+            if self.enabled_rules.contains(name) {
+                return Err(invalid_rule_set(format!("The rule {} can not be both enabled and disabled!", name)));
+            }
+            if matches!(rules.iter().find(|rule| &rule.name == name), Some(rule) if !rule.can_be_disabled) {
+                return Err(invalid_rule_set(format!("The rule {} is needed to play the game and can not be disabled!", name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_rule_set(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::InvalidRuleSet, message)
+}
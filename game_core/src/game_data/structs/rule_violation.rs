@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::enums::game_error::GameError;
+
+/// The RuleViolation struct describes a rule of the game that a player input breaks.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RuleViolation {
+    /// The name of the broken rule.
+    pub rule: String,
+    /// The machine readable code of the error the rule gives, see [`GameError::code`].
+    pub code: String,
+    pub message: String,
+}
+
+impl RuleViolation {
+    pub fn new(rule: &str, error: &GameError) -> Self {
+        Self {
+            rule: rule.to_string(),
+            code: error.code(),
+            message: error.message().to_string(),
+        }
+    }
+}
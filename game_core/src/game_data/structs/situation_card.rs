@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_data::custom_types::SituationCardID;
+use crate::game_data::{custom_types::{NodeID, SituationCardID}, enums::{district::District, game_error::{GameError, InvalidInputReason}, restriction_type::RestrictionType}};
 
-use super::{cost_tuple::CostTuple, player_objective_card::PlayerObjectiveCard};
+use super::{cost_tuple::CostTuple, edge_restriction::EdgeRestriction, node_map::NodeMap, player_objective_card::PlayerObjectiveCard};
+
+/// The vehicle types that can be named in the goal of a situation card.
+const GOAL_VEHICLE_TYPES: [RestrictionType; 4] = [RestrictionType::Electric, RestrictionType::Emergency, RestrictionType::Hazard, RestrictionType::Heavy];
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct SituationCard {
@@ -13,6 +16,18 @@ pub struct SituationCard {
     /// Contains the movement penalty cost for moving within a district for the first time.
     pub costs: Vec<CostTuple>,
     pub objective_cards: Vec<PlayerObjectiveCard>,
+    /// Contains the edge restrictions that are put on the map when the situation card is used. These can not be removed by the orchestrator.
+    #[serde(default)]
+    pub edge_restrictions: Vec<EdgeRestriction>,
+    /// Contains the IDs of the nodes that get their rail connection toggled when the situation card is used.
+    #[serde(default)]
+    pub rail_toggles: Vec<NodeID>,
+    /// The districts the goal is about, e.g. the city centre during a concert. The traffic that is not part of the goal should be kept out of them. The districts named in the goal are used if it's empty.
+    #[serde(default)]
+    pub goal_districts: Vec<District>,
+    /// The vehicle types the goal wants to help, e.g. emergency vehicles after an accident. The vehicle types named in the goal are used if it's empty.
+    #[serde(default)]
+    pub goal_vehicle_types: Vec<RestrictionType>,
 }
 
 impl SituationCard {
@@ -32,6 +47,185 @@ impl SituationCard {
             goal,
             costs,
             objective_cards,
+            edge_restrictions: Vec::new(),
+            rail_toggles: Vec::new(),
+            goal_districts: Vec::new(),
+            goal_vehicle_types: Vec::new(),
+        }
+    }
+
+    /// Returns the districts the goal of the situation card is about. If the situation card does not have any goal districts, the districts named in the goal are returned, e.g. the city centre for "Limit other traffic in city centre".
+    pub fn get_goal_districts(&self) -> Vec<District> {
+        if !self.goal_districts.is_empty() {
+            return self.goal_districts.clone();
+        }
+        let goal_words = goal_words(&self.goal);
+        let mut goal_districts = Vec::new();
+        let mut district = Some(District::first());
+        while let Some(d) = district {
+            if names(&goal_words, &format!("{:?}", d)) {
+                goal_districts.push(d);
+            }
+            district = d.next();
+        }
+        goal_districts
+    }
+
+    /// Returns the vehicle types the goal of the situation card wants to help. If the situation card does not have any goal vehicle types, the vehicle types named in the goal are returned, e.g. emergency vehicles for "Support emergency services".
+    pub fn get_goal_vehicle_types(&self) -> Vec<RestrictionType> {
+        if !self.goal_vehicle_types.is_empty() {
+            return self.goal_vehicle_types.clone();
+        }
+        let goal_words = goal_words(&self.goal);
+        GOAL_VEHICLE_TYPES
+            .into_iter()
+            .filter(|vehicle_type| names(&goal_words, &format!("{:?}", vehicle_type)))
+            .collect()
+    }
+
+    /// Checks that the situation card can be used on the given map. This means that every district has a cost, that there are at least `needed_objective_cards` objective cards, and that all the nodes and edges the card refers to exist. Returns an error describing the first problem found.
+    pub fn validate(&self, map: &NodeMap, needed_objective_cards: usize) -> Result<(), GameError> {
+        if self.card_id == 0 {
+            return Err(invalid_situation_card(format!("The situation card \"{}\" has the ID 0, which is not allowed!", self.title)));
+        }
+
+        let mut district = Some(District::first());
+        while let Some(d) = district {
+            match self.costs.iter().filter(|cost| cost.neighbourhood == d).count() {
+                0 => return Err(invalid_situation_card(format!("The situation card with ID {} has no cost for the district {:?}!", self.card_id, d))),
+                1 => (),
+                _ => return Err(invalid_situation_card(format!("The situation card with ID {} has more than one cost for the district {:?}!", self.card_id, d))),
+            }
+            district = d.next();
+        }
+
+        if self.objective_cards.len() < needed_objective_cards {
+            return Err(invalid_situation_card(format!("The situation card with ID {} has {} objective cards, but needs at least {} so that every player can get one!", self.card_id, self.objective_cards.len(), needed_objective_cards)));
         }
+
+        for objective_card in self.objective_cards.iter() {
+            for node_id in [objective_card.start_node_id, objective_card.pick_up_node_id, objective_card.drop_off_node_id] {
+                if let Err(e) = map.get_node_by_id(node_id) {
+                    return Err(invalid_situation_card(format!("The objective card \"{}\" on the situation card with ID {} is not valid because: {}", objective_card.name, self.card_id, e)));
+                }
+            }
+        }
+
+        let mut map = map.clone();
+        for edge_restriction in self.edge_restrictions.iter() {
+            if let Err(e) = map.set_restriction_on_edge(edge_restriction, false) {
+                return Err(invalid_situation_card(format!("The edge restriction between node {} and {} on the situation card with ID {} is not valid because: {}", edge_restriction.node_one, edge_restriction.node_two, self.card_id, e)));
+            }
+        }
+        for node_id in self.rail_toggles.iter() {
+            if let Err(e) = map.toggle_rail_connection_on_node_with_id(*node_id) {
+                return Err(invalid_situation_card(format!("The rail toggle on the situation card with ID {} is not valid because: {}", self.card_id, e)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the words of the goal in lower case, without punctuation.
+fn goal_words(goal: &str) -> Vec<String> {
+    goal.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Checks if the name is one or more whole words next to each other in the goal, so that e.g. "City Centre" names the district `CityCentre` but "transport" does not name the district `Port`.
+fn names(goal_words: &[String], name: &str) -> bool {
+    let name = name.to_lowercase();
+    (1..=goal_words.len()).any(|length| goal_words.windows(length).any(|words| words.concat() == name))
+}
+
+fn invalid_situation_card(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::InvalidSituationCard, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game_data::constants::MAX_PLAYER_COUNT, situation_card_list::situation_card_list};
+
+    fn valid_card() -> SituationCard {
+        situation_card_list().into_iter().next().unwrap()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn default_situation_cards_are_valid_on_the_default_map() {
+        let map = NodeMap::new_default();
+
+        assert!(situation_card_list().iter().all(|card| card.validate(&map, MAX_PLAYER_COUNT - 1).is_ok()));
+    }
+
+    #[test]
+    fn situation_card_with_id_zero_is_not_valid() {
+        let mut card = valid_card();
+        card.card_id = 0;
+
+        assert!(card.validate(&NodeMap::new_default(), MAX_PLAYER_COUNT - 1).is_err());
+    }
+
+    #[test]
+    fn situation_card_without_a_cost_for_every_district_is_not_valid() {
+        let mut card = valid_card();
+        card.costs.pop();
+
+        assert!(card.validate(&NodeMap::new_default(), MAX_PLAYER_COUNT - 1).is_err());
+    }
+
+    #[test]
+    fn situation_card_without_an_objective_card_for_every_player_is_not_valid() {
+        let mut card = valid_card();
+        card.objective_cards.truncate(MAX_PLAYER_COUNT - 2);
+
+        assert!(card.validate(&NodeMap::new_default(), MAX_PLAYER_COUNT - 1).is_err());
+    }
+
+    #[test]
+    fn situation_card_with_an_edge_restriction_on_a_missing_edge_is_not_valid() {
+        let mut card = valid_card();
+        card.edge_restrictions.push(EdgeRestriction { node_one: 0, node_two: 200, edge_restriction: RestrictionType::Electric, delete: false });
+
+        assert!(card.validate(&NodeMap::new_default(), MAX_PLAYER_COUNT - 1).is_err());
+    }
+
+    fn card_with_goal(goal: &str) -> SituationCard {
+        SituationCard::new(1, "Test".to_string(), "A test situation".to_string(), goal.to_string(), Vec::new(), Vec::new())
+    }
+
+    #[test]
+    fn goal_districts_are_found_in_the_goal() {
+        let card = card_with_goal("Limit other traffic in City Centre");
+
+        assert_eq!(card.get_goal_districts(), vec![District::CityCentre]);
+        assert!(card.get_goal_vehicle_types().is_empty());
+    }
+
+    #[test]
+    fn goal_vehicle_types_are_found_in_the_goal() {
+        let card = card_with_goal("Support emergency services");
+
+        assert_eq!(card.get_goal_vehicle_types(), vec![RestrictionType::Emergency]);
+        assert!(card.get_goal_districts().is_empty());
+    }
+
+    #[test]
+    fn goal_districts_are_only_found_as_whole_words() {
+        let card = card_with_goal("Facilitate transport operations.");
+
+        assert!(card.get_goal_districts().is_empty());
+    }
+
+    #[test]
+    fn explicit_goal_lists_are_used_instead_of_the_goal() {
+        let mut card = card_with_goal("Limit other traffic in City Centre");
+        card.goal_districts = vec![District::Airport];
+        card.goal_vehicle_types = vec![RestrictionType::Electric];
+
+        assert_eq!(card.get_goal_districts(), vec![District::Airport]);
+        assert_eq!(card.get_goal_vehicle_types(), vec![RestrictionType::Electric]);
+    }
+}
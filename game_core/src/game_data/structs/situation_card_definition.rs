@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{custom_types::{NodeID, SituationCardID, VehicleType}, enums::{type_entities_to_transport::TypeEntitiesToTransport, district::District, restriction_type::RestrictionType}};
+
+use super::{cost_tuple::CostTuple, edge_restriction::EdgeRestriction, player_objective_card::PlayerObjectiveCard, situation_card::SituationCard};
+
+/// The SituationCardFile struct describes the content of a situation card data file.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SituationCardFile {
+    pub situation_cards: Vec<SituationCardDefinition>,
+}
+
+/// The SituationCardDefinition struct describes a situation card as it is written in a data file.
+/// 
+/// The map side effects (`edge_restrictions` and `rail_toggles`) can be left out of the file if the situation card does not change the map.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SituationCardDefinition {
+    pub card_id: SituationCardID,
+    pub title: String,
+    pub description: String,
+    pub goal: String,
+    pub costs: Vec<CostTuple>,
+    pub objective_cards: Vec<ObjectiveCardDefinition>,
+    #[serde(default)]
+    pub edge_restrictions: Vec<EdgeRestriction>,
+    #[serde(default)]
+    pub rail_toggles: Vec<NodeID>,
+    #[serde(default)]
+    pub goal_districts: Vec<District>,
+    #[serde(default)]
+    pub goal_vehicle_types: Vec<RestrictionType>,
+}
+
+/// The ObjectiveCardDefinition struct describes a player objective card as it is written in a data file. The Heavy vehicle type is added automatically based on the amount of entities, like in [`PlayerObjectiveCard::new`].
+/// 
+/// [`PlayerObjectiveCard::new`]: ../player_objective_card/struct.PlayerObjectiveCard.html#method.new
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ObjectiveCardDefinition {
+    pub name: String,
+    pub start_node_id: NodeID,
+    pub pick_up_node_id: NodeID,
+    pub drop_off_node_id: NodeID,
+    #[serde(default)]
+    pub vehicle_types: Vec<VehicleType>,
+    pub type_of_entities_to_transport: TypeEntitiesToTransport,
+    pub amount_of_entities: u32,
+}
+
+impl SituationCardDefinition {
+    /// Turns the definition into the situation card used by the game.
+    #[must_use]
+    pub fn into_situation_card(self) -> SituationCard {
+        let objective_cards = self
+            .objective_cards
+            .into_iter()
+            .map(ObjectiveCardDefinition::into_objective_card)
+            .collect();
+        let mut situation_card = SituationCard::new(self.card_id, self.title, self.description, self.goal, self.costs, objective_cards);
+        situation_card.edge_restrictions = self.edge_restrictions;
+        situation_card.rail_toggles = self.rail_toggles;
+        situation_card.goal_districts = self.goal_districts;
+        situation_card.goal_vehicle_types = self.goal_vehicle_types;
+        situation_card
+    }
+}
+
+impl ObjectiveCardDefinition {
+    /// Turns the definition into the objective card used by the game.
+    #[must_use]
+    pub fn into_objective_card(self) -> PlayerObjectiveCard {
+        PlayerObjectiveCard::new(
+            self.name,
+            self.start_node_id,
+            self.pick_up_node_id,
+            self.drop_off_node_id,
+            self.vehicle_types,
+            self.type_of_entities_to_transport,
+            self.amount_of_entities,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_data::custom_types::VehicleType;
+
+    fn objective_card_definition(amount_of_entities: u32) -> ObjectiveCardDefinition {
+        ObjectiveCardDefinition {
+            name: "Packages".to_string(),
+            start_node_id: 1,
+            pick_up_node_id: 2,
+            drop_off_node_id: 3,
+            vehicle_types: vec![VehicleType::Electric],
+            type_of_entities_to_transport: TypeEntitiesToTransport::Packages,
+            amount_of_entities,
+        }
+    }
+
+    #[test]
+    fn objective_card_with_many_entities_gets_the_heavy_vehicle_type() {
+        let objective_card = objective_card_definition(5).into_objective_card();
+
+        assert_eq!(objective_card.special_vehicle_types, vec![VehicleType::Electric, VehicleType::Heavy]);
+    }
+
+    #[test]
+    fn objective_card_with_few_entities_keeps_its_vehicle_types() {
+        let objective_card = objective_card_definition(4).into_objective_card();
+
+        assert_eq!(objective_card.special_vehicle_types, vec![VehicleType::Electric]);
+    }
+
+    #[test]
+    fn map_changes_can_be_left_out_of_a_situation_card_definition() {
+        let content = r#"{ "card_id": 7, "title": "Title", "description": "Description", "goal": "Goal", "costs": [], "objective_cards": [] }"#;
+
+        let situation_card = serde_json::from_str::<SituationCardDefinition>(content).unwrap().into_situation_card();
+
+        assert_eq!(situation_card.card_id, 7);
+        assert!(situation_card.edge_restrictions.is_empty());
+        assert!(situation_card.rail_toggles.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::game_data::custom_types::SituationCardID;
+use crate::game_data::{custom_types::SituationCardID, enums::game_error::GameError};
 
 use super::situation_card::SituationCard;
 
@@ -18,14 +18,14 @@ impl SituationCardList {
     }
 
     /// Returns a situation card by its ID. Returns an error if there is no situation card with the given ID.
-    pub fn get_default_situation_card_by_id(id: SituationCardID) -> Result<SituationCard, String> {
+    pub fn get_default_situation_card_by_id(id: SituationCardID) -> Result<SituationCard, GameError> {
         let situation_cards = crate::situation_card_list::situation_card_list_wrapper();
         situation_cards
             .situation_cards
             .iter()
             .find(|card| card.card_id == id)
             .map_or_else(
-                || Err(format!("There was no card with the ID: {}", id)),
+                || Err(GameError::NotFound(format!("There was no card with the ID: {}", id))),
                 |card| Ok(card.clone()),
             )
     }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::custom_types::PlayerID;
+
+/// The Spectator struct describes someone that follows a game without playing it. A spectator can see the game state and get its updates, but has no role, takes no turns and does not count against the max amount of players.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Spectator {
+    pub unique_id: PlayerID,
+    pub name: String,
+}
+
+impl Spectator {
+    /// Creates a new Spectator with the given unique id and name.
+    #[must_use]
+    pub const fn new(unique_id: PlayerID, name: String) -> Self {
+        Self { unique_id, name }
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_data::{custom_types::{MovementCost, MovesRemaining, NodeID, VehicleID}, enums::restriction_type::RestrictionType};
+
+/// The Vehicle struct describes one of the vehicles in the fleet of a player. Every vehicle has its own position, vehicle types and remaining moves, and can be a car or a bus.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Vehicle {
+    /// The id of the vehicle, which is unique among the vehicles of the player and is used by [`PlayerInput`] to say which vehicle the input is for.
+    ///
+    /// [`PlayerInput`]: ../player_input/struct.PlayerInput.html
+    pub id: VehicleID,
+    pub position_node_id: Option<NodeID>,
+    pub remaining_moves: MovesRemaining,
+    pub is_bus: bool,
+    /// The special vehicle types of the vehicle, e.g. electric or emergency. They decide which access, priority and toll modifiers apply to the vehicle when it moves. The vehicles get the vehicle types of the objective card of the player when the game starts.
+    #[serde(default)]
+    pub vehicle_types: Vec<RestrictionType>,
+    /// If the vehicle carries the package of the objective card of the player. The package is picked up by the first vehicle that reaches the pick up node, and only that vehicle can drop it off.
+    pub has_package: bool,
+}
+
+impl Vehicle {
+    /// Creates a new car with the given vehicle types at the given node with the given amount of moves.
+    #[must_use]
+    pub const fn new(id: VehicleID, position_node_id: NodeID, remaining_moves: MovesRemaining, vehicle_types: Vec<RestrictionType>) -> Self {
+        Self {
+            id,
+            position_node_id: Some(position_node_id),
+            remaining_moves,
+            is_bus: false,
+            vehicle_types,
+            has_package: false,
+        }
+    }
+
+    /// Returns `true` if the vehicle has the given vehicle type, else it will return `false`.
+    pub fn has_vehicle_type(&self, vehicle_type: RestrictionType) -> bool {
+        self.vehicle_types.contains(&vehicle_type)
+    }
+
+    /// Returns the emissions the vehicle makes for each movement point spent. Electric vehicles make no emissions and heavy vehicles make twice as much as other vehicles.
+    pub fn get_emissions_per_move(&self) -> u32 {
+        if self.has_vehicle_type(RestrictionType::Electric) {
+            return 0;
+        }
+        if self.has_vehicle_type(RestrictionType::Heavy) {
+            return 2;
+        }
+        1
+    }
+
+    /// Moves the vehicle to a node without checking if the move is valid.
+    pub fn move_to_node(&mut self, to_node_id: NodeID, cost: MovementCost) {
+        self.remaining_moves -= cost;
+        self.position_node_id = Some(to_node_id);
+    }
+
+    /// Sets the is_bus field to true.
+    pub fn transform_to_bus(&mut self) {
+        self.is_bus = true;
+    }
+
+    /// Sets the is_bus field to false.
+    pub fn transform_to_car(&mut self) {
+        self.is_bus = false;
+    }
+}
@@ -0,0 +1,17 @@
+use crate::game_data::{structs::gamestate::GameState, custom_types::{ErrorData, GameID}};
+
+/// A trait that defines the interface for storing games used by the [`GameController`], so that games survive a server restart.
+/// 
+/// [`GameController`]: ../game_controller/struct.GameController.html
+/// 
+/// The storage is used by many threads at the same time, but the game controller never stores or removes the same game from two threads at the same time.
+pub trait GameStorage {
+    /// Stores the given game, replacing any earlier stored version of it.
+    fn save_game(&self, game: &GameState) -> Result<(), ErrorData>;
+    /// Removes the stored game with the given id. Removing a game that is not stored is not an error.
+    fn remove_game(&self, game_id: GameID) -> Result<(), ErrorData>;
+    /// Loads all the stored games. The games are returned with their node maps rebuilt and ready to be used.
+    /// 
+    /// A stored game that can not be loaded is returned as an error in the list, so that the other games can still be loaded. The whole load only fails if the stored games can not be found at all.
+    fn load_games(&self) -> Result<Vec<Result<GameState, ErrorData>>, ErrorData>;
+}
@@ -3,11 +3,24 @@
 //! The game_core library is the core of the game. It contains all the data structures for the game and some of the game logic.
 //! The GameController struct in the game_controller module is the game manager and is what should be used to control all of the games on the server. It has all the neccessary functions to create and handle games.
 
+/// The advisor module gives the orchestrator suggestions for district modifiers and park & ride placements, by simulating how the players would move after them.
+pub mod advisor;
+/// The bots module contains the computer controlled players that can fill the empty player slots of a game, and how they choose where to move.
+pub mod bots;
 /// The game_controller module contains the game controller struct and its methods related to controlling all the games of the server. And can be thought of as the server's game manager.
 pub mod game_controller;
 /// The game_data module contains all the data structures for the game and some of the game logic.
 pub mod game_data;
+/// The game_storage module contains the trait for storing games so that they can be restored after a server restart.
+pub mod game_storage;
+/// The map_list module loads the board maps that can be chosen for a game from the map files.
+pub mod map_list;
+/// The path_finding module finds the cheapest routes a player can take on the board map by following the rules of the game.
+pub mod path_finding;
 /// The rule_checker module contains the trait for the rule checker.
 pub mod rule_checker;
-/// The situation_card_list module has the default situation cards for the game, including the objective/assignment cards for each situation card.
+/// The situation_card_list module loads the situation cards for the game, including the objective/assignment cards and map changes for each situation card, from the situation card data files.
 pub mod situation_card_list;
+/// The test_utils module contains the small game the unit tests of game_core are run on.
+#[cfg(test)]
+mod test_utils;
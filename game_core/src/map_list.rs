@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fs, path::{Path, PathBuf}, sync::RwLock};
+
+use lazy_static::lazy_static;
+
+use crate::game_data::{enums::game_error::GameError, structs::{map_definition::MapDefinition, node_map::NodeMap}};
+
+/// The name of the map that is used when a game does not choose a map.
+pub const DEFAULT_MAP_NAME: &str = "default";
+/// The map that comes with the server.
+const DEFAULT_MAP: &str = include_str!("../resources/maps/default.json");
+const MAP_FILE_EXTENSION: &str = "json";
+
+lazy_static! {
+    static ref MAPS: RwLock<HashMap<String, NodeMap>> = RwLock::new(
+        parse_map_file(DEFAULT_MAP)
+            .map(|(name, map)| HashMap::from([(name, map)]))
+            .unwrap_or_default()
+    );
+}
+
+/// Returns the map with the given name. Returns an error if there is no loaded map with the given name.
+pub fn get_map(name: &str) -> Result<NodeMap, GameError> {
+    let Ok(maps) = MAPS.read() else {
+        return Err(GameError::Internal("Failed to get the map because the maps could not be accessed!".to_string()));
+    };
+    match maps.get(name) {
+        Some(map) => Ok(map.clone()),
+        None => Err(GameError::NotFound(format!("There is no map with the name {}!", name))),
+    }
+}
+
+/// Returns the default map that comes with the server, no matter which maps have been loaded.
+pub fn embedded_default_map() -> NodeMap {
+    match parse_map_file(DEFAULT_MAP) {
+        Ok((_, map)) => map,
+        Err(_) => NodeMap::new(),
+    }
+}
+
+/// Returns the names of all the loaded maps, sorted alphabetically.
+pub fn map_names() -> Vec<String> {
+    let Ok(maps) = MAPS.read() else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = maps.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Parses and validates the content of a map file, and returns the name of the map together with the map. Returns an error if the content is not a valid map.
+pub fn parse_map_file(content: &str) -> Result<(String, NodeMap), String> {
+    let definition = match serde_json::from_str::<MapDefinition>(content) {
+        Ok(definition) => definition,
+        Err(e) => return Err(format!("Failed to parse the map because: {e}")),
+    };
+    let name = definition.name.clone();
+    match definition.into_node_map() {
+        Ok(map) => Ok((name, map)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Loads the default map and the maps in the JSON files of the given folder, and makes them the maps that can be chosen for a game. A map in the folder replaces the default map if it has the same name. If the folder does not exist only the default map is used.
+/// 
+/// Returns how many maps were loaded, or an error if a file could not be read or a map is not valid. The maps used by the server are not changed if there is an error.
+pub fn load_maps(folder: &Path) -> Result<usize, String> {
+    let (default_name, default_map) = parse_map_file(DEFAULT_MAP)?;
+    let mut maps = HashMap::from([(default_name, default_map)]);
+
+    let mut loaded_map_names = Vec::new();
+    for file_path in map_files_in_folder(folder)? {
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => return Err(format!("Failed to read the map file {} because: {e}", file_path.display())),
+        };
+        let (name, map) = match parse_map_file(&content) {
+            Ok(named_map) => named_map,
+            Err(e) => return Err(format!("The map file {} is not valid. {e}", file_path.display())),
+        };
+        if loaded_map_names.contains(&name) {
+            return Err(format!("There is more than one map with the name {} in the folder {}!", name, folder.display()));
+        }
+        loaded_map_names.push(name.clone());
+        maps.insert(name, map);
+    }
+
+    let loaded_amount = maps.len();
+    match MAPS.write() {
+        Ok(mut current_maps) => *current_maps = maps,
+        Err(_) => return Err("Failed to update the maps because they could not be accessed!".to_string()),
+    }
+    Ok(loaded_amount)
+}
+
+fn map_files_in_folder(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(e) => return Err(format!("Failed to read the map folder {} because: {e}", folder.display())),
+    };
+    let mut file_paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| matches!(path.extension(), Some(extension) if extension == MAP_FILE_EXTENSION))
+        .collect();
+    file_paths.sort();
+    Ok(file_paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    const SMALL_MAP: &str = r#"{
+        "name": "small",
+        "nodes": [{"id": 0, "name": "A"}, {"id": 1, "name": "B"}, {"id": 2, "name": "C"}],
+        "edges": [
+            {"node_one": 0, "node_two": 1, "district": "Port"},
+            {"node_one": 1, "node_two": 2, "district": "Airport", "movement_cost": 2}
+        ]
+    }"#;
+
+    #[test]
+    fn map_file_is_parsed_into_a_map_with_edges_both_ways() {
+        let (name, map) = parse_map_file(SMALL_MAP).unwrap();
+
+        assert_eq!(name, "small");
+        assert_eq!(map.nodes.len(), 3);
+        assert!(map.are_nodes_neighbours(0, 1).unwrap());
+        assert!(map.are_nodes_neighbours(1, 0).unwrap());
+        assert!(!map.are_nodes_neighbours(0, 2).unwrap());
+        let edge = map.get_neighbour_relationships_of_node_with_id(2).unwrap().remove(0);
+        assert_eq!(edge.movement_cost, 2);
+    }
+
+    #[test]
+    fn map_files_that_are_not_valid_maps_are_rejected() {
+        let unreachable_node = SMALL_MAP.replace(r#"{"node_one": 1, "node_two": 2, "district": "Airport", "movement_cost": 2}"#, r#"{"node_one": 1, "node_two": 0, "district": "Airport"}"#);
+        let missing_node = SMALL_MAP.replace(r#""node_two": 2"#, r#""node_two": 3"#);
+
+        assert!(parse_map_file("not a map").is_err());
+        assert!(parse_map_file(&unreachable_node).is_err());
+        assert!(parse_map_file(&missing_node).is_err());
+    }
+
+    #[test]
+    fn embedded_default_map_is_the_default_map() {
+        let embedded_map = embedded_default_map();
+
+        assert!(!embedded_map.nodes.is_empty());
+        assert_eq!(get_map(DEFAULT_MAP_NAME).unwrap().nodes.len(), embedded_map.nodes.len());
+        assert!(matches!(get_map("no such map"), Err(GameError::NotFound(_))));
+    }
+
+    #[test]
+    fn maps_are_loaded_from_a_folder_and_kept_if_the_next_folder_is_not_valid() {
+        let folder = env::temp_dir().join(format!("map_list_{}", process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("small.json"), SMALL_MAP).unwrap();
+        fs::write(folder.join("notes.txt"), "not a map").unwrap();
+
+        let loaded = load_maps(&folder);
+        fs::write(folder.join("broken.json"), "not a map").unwrap();
+        let loaded_after_broken_file = load_maps(&folder);
+        let _ = fs::remove_dir_all(&folder);
+
+        assert_eq!(loaded, Ok(2));
+        assert!(loaded_after_broken_file.is_err());
+        assert!(map_names().contains(&"small".to_string()));
+        assert!(get_map(DEFAULT_MAP_NAME).is_ok());
+    }
+}
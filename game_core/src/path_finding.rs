@@ -0,0 +1,293 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{
+    game_data::{
+        custom_types::{MovesRemaining, NodeID, PlayerID, VehicleID},
+        enums::{game_error::{GameError, InvalidInputReason}, player_input_type::PlayerInputType},
+        structs::{action_log::ActionLog, gamestate::GameState, player_input::PlayerInput, route::Route},
+    },
+    rule_checker::RuleChecker,
+};
+
+/// The amount of moves the player is given when looking for routes without a limit on how far the player can move.
+const UNLIMITED_MOVES: MovesRemaining = MovesRemaining::MAX / 2;
+
+/// Finds the cheapest route from the node the vehicle with the given id of the player is at to every node the vehicle can reach, sorted by node id.
+///
+/// Every step is checked with the given rule checker and done with the same movement logic as a normal movement, so the routes follow the district costs, edge restrictions, rail connections and vehicle types in the same way as the game does.
+/// The search acts as if it is the player's turn. If `limit_to_remaining_moves` is true only the nodes the vehicle can reach with the moves it has left are returned, otherwise the vehicle is given as many moves as needed.
+pub fn find_routes(
+    game: &GameState,
+    player_id: PlayerID,
+    vehicle_id: VehicleID,
+    rule_checker: &dyn RuleChecker,
+    limit_to_remaining_moves: bool,
+) -> Result<Vec<Route>, GameError> {
+    search(game, player_id, vehicle_id, rule_checker, limit_to_remaining_moves, None)
+}
+
+/// Searches for the cheapest routes. If there is a target node, the search stops as soon as the cheapest route to it is known.
+fn search(
+    game: &GameState,
+    player_id: PlayerID,
+    vehicle_id: VehicleID,
+    rule_checker: &dyn RuleChecker,
+    limit_to_remaining_moves: bool,
+    target_node_id: Option<NodeID>,
+) -> Result<Vec<Route>, GameError> {
+    let player = game.get_player_with_unique_id(player_id)?;
+    let vehicle = player.get_vehicle(vehicle_id)?.clone();
+    let Some(start_node_id) = vehicle.position_node_id else {
+        return Err(GameError::InvalidInput(InvalidInputReason::NoPosition, "The vehicle is not at any node!".to_string()));
+    };
+    let Some(game_id) = player.connected_game_id else {
+        return Err(GameError::Conflict("The player is not in a game!".to_string()));
+    };
+
+    // The other players do not change where the player can move, so they are left out to make the copies of the game made while searching smaller.
+    let mut start_game = game.clone();
+    start_game.action_log = ActionLog::default();
+    start_game.players.retain(|p| p.unique_id == player_id);
+    start_game.current_players_turn = player.in_game_id;
+    let start_moves = if limit_to_remaining_moves {
+        vehicle.remaining_moves
+    } else {
+        UNLIMITED_MOVES
+    };
+    if let Some(start_player) = start_game.players.iter_mut().find(|p| p.unique_id == player_id) {
+        start_player.get_vehicle_mut(vehicle_id)?.remaining_moves = start_moves;
+    }
+
+    let mut best_states: HashMap<(NodeID, Vec<u8>), MovesRemaining> = HashMap::new();
+    let mut best_routes: HashMap<NodeID, Route> = HashMap::new();
+    let mut queue: BinaryHeap<SearchState> = BinaryHeap::new();
+    best_states.insert(search_key(&start_game, vehicle_id, start_node_id), start_moves);
+    queue.push(SearchState { remaining_moves: start_moves, game: start_game, path: Vec::new() });
+
+    // The states with the most remaining moves are searched first, so that most states are only searched once.
+    // Without bonus moves no step can give the player more moves, so the first time the target is reached it is reached with the cheapest route.
+    let can_stop_at_target = game.district_modifiers.iter().all(|modifier| matches!(modifier.associated_movement_value, None | Some(0)));
+    while let Some(SearchState { remaining_moves: current_remaining_moves, game: current_game, path }) = queue.pop() {
+        let current_node_id = path.last().copied().unwrap_or(start_node_id);
+        if matches!(best_states.get(&search_key(&current_game, vehicle_id, current_node_id)), Some(best) if *best > current_remaining_moves) {
+            continue;
+        }
+        if can_stop_at_target && target_node_id == Some(current_node_id) && !path.is_empty() {
+            break;
+        }
+        let Some(neighbours) = current_game.map.get_neighbour_relationships_of_node_with_id(current_node_id) else {
+            continue;
+        };
+        for neighbour in neighbours {
+            let input = PlayerInput {
+                district_modifier: None,
+                player_id,
+                game_id,
+                input_type: PlayerInputType::Movement,
+                related_role: None,
+                related_node_id: Some(neighbour.to),
+                situation_card_id: None,
+                edge_modifier: None,
+                related_bool: None,
+                related_path: None,
+                vehicle_id,
+            };
+            if rule_checker.is_input_valid(&current_game, &input).is_some() {
+                continue;
+            }
+            let mut next_game = current_game.clone();
+            if next_game.move_player_with_id(player_id, vehicle_id, neighbour.to).is_err() {
+                continue;
+            }
+            let remaining_moves = next_game.get_player_with_unique_id(player_id)?.get_vehicle(vehicle_id)?.remaining_moves;
+
+            let key = search_key(&next_game, vehicle_id, neighbour.to);
+            if matches!(best_states.get(&key), Some(best) if *best >= remaining_moves) {
+                continue;
+            }
+            best_states.insert(key, remaining_moves);
+
+            let moves_spent = start_moves - remaining_moves;
+            let mut next_path = path.clone();
+            next_path.push(neighbour.to);
+            if neighbour.to != start_node_id
+                && !matches!(best_routes.get(&neighbour.to), Some(route) if route.moves_spent <= moves_spent)
+            {
+                best_routes.insert(
+                    neighbour.to,
+                    Route {
+                        to: neighbour.to,
+                        path: next_path.clone(),
+                        moves_spent,
+                        remaining_moves: vehicle.remaining_moves - moves_spent,
+                    },
+                );
+            }
+            queue.push(SearchState { remaining_moves, game: next_game, path: next_path });
+        }
+    }
+
+    let mut routes: Vec<Route> = best_routes.into_values().collect();
+    routes.sort_by_key(|route| route.to);
+    Ok(routes)
+}
+
+/// Finds the cheapest route from the node the vehicle with the given id of the player is at to the node with the given id, no matter how many moves the vehicle has left.
+pub fn find_cheapest_route(
+    game: &GameState,
+    player_id: PlayerID,
+    vehicle_id: VehicleID,
+    to_node_id: NodeID,
+    rule_checker: &dyn RuleChecker,
+) -> Result<Route, GameError> {
+    if game.map.get_node_by_id(to_node_id).is_err() {
+        return Err(GameError::NotFound(format!("There is no node with id {}!", to_node_id)));
+    }
+    let routes = search(game, player_id, vehicle_id, rule_checker, false, Some(to_node_id))?;
+    match routes.into_iter().find(|route| route.to == to_node_id) {
+        Some(route) => Ok(route),
+        None => Err(GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, format!("The player can not reach the node with id {}!", to_node_id))),
+    }
+}
+
+/// The moves it costs to go further depend on which node the vehicle is at and which districts the vehicle has already entered this turn, so these are what tells two searched states apart.
+fn search_key(game: &GameState, vehicle_id: VehicleID, node_id: NodeID) -> (NodeID, Vec<u8>) {
+    let mut districts: Vec<u8> = game
+        .accessed_districts
+        .iter()
+        .filter(|(id, _)| *id == vehicle_id)
+        .map(|(_, district)| *district as u8)
+        .collect();
+    districts.sort_unstable();
+    (node_id, districts)
+}
+
+/// A state of the game in the search, ordered by how many moves the player has left.
+struct SearchState {
+    remaining_moves: MovesRemaining,
+    game: GameState,
+    path: Vec<NodeID>,
+}
+
+impl PartialEq for SearchState {
+    fn eq(&self, other: &Self) -> bool {
+        self.remaining_moves == other.remaining_moves
+    }
+}
+
+impl Eq for SearchState {}
+
+impl PartialOrd for SearchState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SearchState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.remaining_moves.cmp(&other.remaining_moves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game_data::{enums::{district::District, restriction_type::RestrictionType}, structs::{rule_info::RuleInfo, rule_violation::RuleViolation}},
+        test_utils::{small_game, AllowAllRuleChecker, PLAYER_ID},
+    };
+
+    fn set_restriction_on_edge(game: &mut GameState, node_one: NodeID, node_two: NodeID, restriction: RestrictionType) {
+        for (from, to) in [(node_one, node_two), (node_two, node_one)] {
+            for relationship in game.map.edges.get_mut(&from).unwrap().iter_mut().filter(|relationship| relationship.to == to) {
+                relationship.restriction = Some(restriction);
+            }
+        }
+    }
+
+    #[test]
+    fn cheapest_route_avoids_expensive_districts() {
+        let mut game = small_game();
+        game.map.change_neighbourhood_cost(District::CityCentre, 3);
+
+        let route = find_cheapest_route(&game, PLAYER_ID, 0, 3, &AllowAllRuleChecker).unwrap();
+
+        assert_eq!(route.path, vec![1, 4, 3]);
+        assert_eq!(route.moves_spent, 3);
+        assert_eq!(route.remaining_moves, game.settings.start_movement_amount - 3);
+    }
+
+    #[test]
+    fn cheapest_route_pays_the_district_cost_once() {
+        let mut game = small_game();
+        game.map.change_neighbourhood_cost(District::Suburbs, 1);
+        game.map.change_neighbourhood_cost(District::CityCentre, 2);
+
+        let route = find_cheapest_route(&game, PLAYER_ID, 0, 3, &AllowAllRuleChecker).unwrap();
+
+        assert_eq!(route.path, vec![1, 4, 3]);
+        assert_eq!(route.moves_spent, 4);
+    }
+
+    /// A rule checker that only allows a vehicle to move if it has moves left, like the rule of the server does.
+    struct MovesLeftRuleChecker;
+
+    impl RuleChecker for MovesLeftRuleChecker {
+        fn is_input_valid(&self, game: &GameState, input: &PlayerInput) -> Option<GameError> {
+            let vehicle = game.get_player_with_unique_id(input.player_id).ok()?.get_vehicle(input.vehicle_id).ok()?.clone();
+            match vehicle.remaining_moves > 0 {
+                true => None,
+                false => Some(GameError::InvalidInput(InvalidInputReason::NotEnoughMoves, "The vehicle has no moves left!".to_string())),
+            }
+        }
+
+        fn get_rule_violations(&self, _game: &GameState, _input: &PlayerInput) -> Vec<RuleViolation> {
+            Vec::new()
+        }
+
+        fn get_rules_info(&self) -> Vec<RuleInfo> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn reachable_nodes_are_limited_to_the_remaining_moves() {
+        let mut game = small_game();
+        game.players[1].vehicles[0].remaining_moves = 1;
+
+        let limited_routes = find_routes(&game, PLAYER_ID, 0, &MovesLeftRuleChecker, true).unwrap();
+        let unlimited_routes = find_routes(&game, PLAYER_ID, 0, &MovesLeftRuleChecker, false).unwrap();
+
+        assert_eq!(limited_routes.iter().map(|route| route.to).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(unlimited_routes.iter().map(|route| route.to).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn cars_can_not_find_routes_along_park_and_ride() {
+        let mut game = small_game();
+        set_restriction_on_edge(&mut game, 1, 2, RestrictionType::ParkAndRide);
+        set_restriction_on_edge(&mut game, 1, 4, RestrictionType::ParkAndRide);
+
+        let result = find_cheapest_route(&game, PLAYER_ID, 0, 3, &AllowAllRuleChecker);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, _))));
+    }
+
+    #[test]
+    fn route_to_a_node_that_does_not_exist_is_an_error() {
+        let game = small_game();
+
+        assert!(matches!(find_cheapest_route(&game, PLAYER_ID, 0, 42, &AllowAllRuleChecker), Err(GameError::NotFound(_))));
+        assert!(matches!(find_cheapest_route(&game, PLAYER_ID, 1, 3, &AllowAllRuleChecker), Err(GameError::NotFound(_))));
+    }
+
+    #[test]
+    fn vehicle_without_a_position_has_no_routes() {
+        let mut game = small_game();
+        game.players[1].vehicles[0].position_node_id = None;
+
+        let result = find_routes(&game, PLAYER_ID, 0, &AllowAllRuleChecker, false);
+
+        assert!(matches!(result, Err(GameError::InvalidInput(InvalidInputReason::NoPosition, _))));
+    }
+}
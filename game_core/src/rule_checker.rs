@@ -1,8 +1,15 @@
-use crate::game_data::{structs::{player_input::PlayerInput, gamestate::GameState}, custom_types::ErrorData};
+use crate::game_data::{structs::{player_input::PlayerInput, gamestate::GameState, rule_violation::RuleViolation, rule_info::RuleInfo}, enums::game_error::GameError};
 
 /// A trait that defines the interface for a rule checker used by the [`GameController`].
 /// 
 /// [`GameController`]: ../game_controller/struct.GameController.html
 pub trait RuleChecker {
-    fn is_input_valid(&self, game: &GameState, input: &PlayerInput) -> Option<ErrorData>;
+    /// Checks if the input is valid, and returns the error of the first broken rule if it is not.
+    fn is_input_valid(&self, game: &GameState, input: &PlayerInput) -> Option<GameError>;
+    /// Checks the input against every rule instead of stopping at the first broken one, and returns every broken rule. The input is valid if the list is empty.
+    fn get_rule_violations(&self, game: &GameState, input: &PlayerInput) -> Vec<RuleViolation>;
+    /// Returns a description of every rule of the rule checker, so that the rules can be turned on and off for a game with a [`RuleSet`].
+    /// 
+    /// [`RuleSet`]: ../game_data/structs/rule_set/struct.RuleSet.html
+    fn get_rules_info(&self) -> Vec<RuleInfo>;
 }
@@ -1,120 +1,194 @@
-use crate::game_data::{structs::{situation_card_list::SituationCardList, situation_card::SituationCard, cost_tuple::CostTuple, player_objective_card::PlayerObjectiveCard}, enums::{district::District, type_entities_to_transport::TypeEntitiesToTransport, traffic::Traffic}, custom_types::VehicleType};
+use std::{fs, path::{Path, PathBuf}, sync::RwLock};
+
+use lazy_static::lazy_static;
+
+use crate::game_data::{constants::MAX_PLAYER_COUNT, structs::{situation_card_list::SituationCardList, situation_card::SituationCard, situation_card_definition::SituationCardFile, node_map::NodeMap}};
+
+/// The situation cards that come with the server. They are used when no other situation cards are loaded.
+const DEFAULT_SITUATION_CARDS: &str = include_str!("../resources/situation_cards.json");
+const SITUATION_CARD_FILE_EXTENSION: &str = "json";
+
+lazy_static! {
+    static ref SITUATION_CARDS: RwLock<Vec<SituationCard>> =
+        RwLock::new(parse_situation_card_file(DEFAULT_SITUATION_CARDS).unwrap_or_default());
+}
 
 pub fn situation_card_list_wrapper() -> SituationCardList {
     SituationCardList::new(situation_card_list())
 }
 
+/// Returns the situation cards that are currently loaded.
 pub fn situation_card_list() -> Vec<SituationCard> {
-    vec![
-        SituationCard::new(
-            1,
-            "Regular traffic".to_string(),
-            "Regular traffic in all zones.".to_string(),
-            "Facilitate transport operations. Rewards green behavior.".to_string(),
-            vec![
-                CostTuple::new(District::IndustryPark, Traffic::LevelOne),
-                CostTuple::new(District::Suburbs, Traffic::LevelOne),
-                CostTuple::new(District::Port, Traffic::LevelOne),
-                CostTuple::new(District::RingRoad, Traffic::LevelOne),
-                CostTuple::new(District::CityCentre, Traffic::LevelOne),
-                CostTuple::new(District::Airport, Traffic::LevelOne),
-            ],
-            vec![
-                PlayerObjectiveCard::new("Packages".to_string(), 13, 7, 15, Vec::new(), TypeEntitiesToTransport::Packages, 5),
-                PlayerObjectiveCard::new("Passengers".to_string(), 8, 11, 27, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 3),
-                PlayerObjectiveCard::new("Passengers".to_string(), 15, 23, 2, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 17, 22, 14, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 5, 12, 28, Vec::new(), TypeEntitiesToTransport::People, 3),
-                PlayerObjectiveCard::new("Passengers".to_string(), 11, 14, 24, Vec::new(), TypeEntitiesToTransport::People, 3),
-            ],
-        ),
-        SituationCard::new(
-            2,
-            "Concert".to_string(),
-            "City centre is crowded. Reduced capacity for traffic.".to_string(),
-            "Facilitate transport of people to concert. Limit other traffic in city centre to what is necesary.".to_string(),
-            vec![
-                CostTuple::new(District::IndustryPark, Traffic::LevelOne),
-                CostTuple::new(District::Suburbs, Traffic::LevelOne),
-                CostTuple::new(District::Port, Traffic::LevelOne),
-                CostTuple::new(District::RingRoad, Traffic::LevelThree),
-                CostTuple::new(District::CityCentre, Traffic::LevelFive),
-                CostTuple::new(District::Airport, Traffic::LevelOne),
-            ],
-            vec![
-                PlayerObjectiveCard::new("Passengers".to_string(), 8, 14, 12, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 14, 28, 12, Vec::new(), TypeEntitiesToTransport::People, 5),
-                PlayerObjectiveCard::new("Passengers".to_string(), 24, 22, 12, Vec::new(), TypeEntitiesToTransport::People, 5),
-                PlayerObjectiveCard::new("Passengers".to_string(), 22, 10, 12, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 3),
-                PlayerObjectiveCard::new("Passengers".to_string(), 5, 13, 28, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Packages".to_string(), 23, 10, 2, Vec::new(), TypeEntitiesToTransport::Packages, 5),
-            ]
-        ),
-        SituationCard::new(
-            3,
-            "Gas Leakage".to_string(),
-            "Gas leakage in Industry Park zone. Health and explosion risk.".to_string(),
-            "Evacuate people and dangerous goods from the area. Safety comes first.".to_string(),
-            vec![
-                CostTuple::new(District::IndustryPark, Traffic::LevelOne),
-                CostTuple::new(District::Suburbs, Traffic::LevelOne),
-                CostTuple::new(District::Port, Traffic::LevelOne),
-                CostTuple::new(District::RingRoad, Traffic::LevelThree),
-                CostTuple::new(District::CityCentre, Traffic::LevelOne),
-                CostTuple::new(District::Airport, Traffic::LevelOne),
-            ],
-            vec![
-                PlayerObjectiveCard::new("Evacuate".to_string(), 4, 0, 10, vec![VehicleType::Emergency], TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Dangerous goods".to_string(), 9, 0, 17, vec![VehicleType::Hazard, VehicleType::Emergency], TypeEntitiesToTransport::Packages, 4),
-                PlayerObjectiveCard::new("Ambulance".to_string(), 15, 0, 15, vec![VehicleType::Emergency], TypeEntitiesToTransport::People, 2),
-                PlayerObjectiveCard::new("Evacuate".to_string(), 5, 1, 17, vec![VehicleType::Hazard, VehicleType::Emergency], TypeEntitiesToTransport::Packages, 3),
-                PlayerObjectiveCard::new("Passengers".to_string(), 24, 22, 10, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Packages".to_string(), 5, 5, 23, Vec::new(), TypeEntitiesToTransport::Packages, 5),
-            ]
-        ),
-        SituationCard::new(
-            4,
-            "Accident".to_string(),
-            "Accident in ring road section I6 - I7. Traffic blocked in east-bound lanes".to_string(),
-            "Support emergency services. Coordinate with other zones.".to_string(),
-            vec![
-                CostTuple::new(District::IndustryPark, Traffic::LevelOne),
-                CostTuple::new(District::Suburbs, Traffic::LevelOne),
-                CostTuple::new(District::Port, Traffic::LevelThree),
-                CostTuple::new(District::RingRoad, Traffic::LevelFive),
-                CostTuple::new(District::CityCentre, Traffic::LevelThree),
-                CostTuple::new(District::Airport, Traffic::LevelOne),
-            ],
-            vec![
-                PlayerObjectiveCard::new("Ambulance".to_string(), 15, 19, 14, vec![VehicleType::Emergency], TypeEntitiesToTransport::People, 1),
-                PlayerObjectiveCard::new("Car removal".to_string(), 14, 19, 14, vec![VehicleType::Emergency], TypeEntitiesToTransport::Packages, 1),
-                PlayerObjectiveCard::new("Passengers".to_string(), 16, 16, 28, Vec::new(), TypeEntitiesToTransport::People, 5),
-                PlayerObjectiveCard::new("Passengers".to_string(), 17, 20, 28, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 3),
-                PlayerObjectiveCard::new("Passengers".to_string(), 27, 27, 15, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Packages".to_string(), 23, 24, 7, Vec::new(), TypeEntitiesToTransport::Packages, 5),
-            ]
-        ),
-        SituationCard::new(
-            5,
-            "Airport train stops".to_string(),
-            "No train from City Centre to Airport during rush hours. Delays for passengers.".to_string(),
-            "Passengers reach airport in time.".to_string(),
-            vec![
-                CostTuple::new(District::IndustryPark, Traffic::LevelOne),
-                CostTuple::new(District::Suburbs, Traffic::LevelTwo),
-                CostTuple::new(District::Port, Traffic::LevelOne),
-                CostTuple::new(District::RingRoad, Traffic::LevelFour),
-                CostTuple::new(District::CityCentre, Traffic::LevelOne),
-                CostTuple::new(District::Airport, Traffic::LevelFour),
-            ],
-            vec![
-                PlayerObjectiveCard::new("Passengers".to_string(), 23, 10, 27, vec![VehicleType::Electric], TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 0, 2, 27, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 5, 7, 28, Vec::new(), TypeEntitiesToTransport::People, 5),
-                PlayerObjectiveCard::new("Passengers".to_string(), 16, 10, 28, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Passengers".to_string(), 14, 10, 27, Vec::new(), TypeEntitiesToTransport::People, 4),
-                PlayerObjectiveCard::new("Packages".to_string(), 23, 24, 8, Vec::new(), TypeEntitiesToTransport::Packages, 5),
-            ]
-        ),
-    ]
+    match SITUATION_CARDS.read() {
+        Ok(situation_cards) => situation_cards.clone(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Parses the content of a situation card data file. Returns an error if the content is not a valid situation card file.
+pub fn parse_situation_card_file(content: &str) -> Result<Vec<SituationCard>, String> {
+    match serde_json::from_str::<SituationCardFile>(content) {
+        Ok(file) => Ok(file
+            .situation_cards
+            .into_iter()
+            .map(|definition| definition.into_situation_card())
+            .collect()),
+        Err(e) => Err(format!("Failed to parse the situation cards because: {e}")),
+    }
+}
+
+/// Loads the default situation cards and the situation cards in the JSON files of the given folder, validates them against the default map, and makes them the situation cards used by the server. A situation card in the folder replaces the default situation card with the same ID. If the folder does not exist only the default situation cards are used.
+/// 
+/// Returns how many situation cards were loaded, or an error if a file could not be read or a situation card is not valid. The situation cards used by the server are not changed if there is an error.
+pub fn load_situation_cards(folder: &Path) -> Result<usize, String> {
+    let situation_cards = read_situation_cards(folder)?;
+    let loaded_amount = situation_cards.len();
+    match SITUATION_CARDS.write() {
+        Ok(mut cards) => *cards = situation_cards,
+        Err(_) => return Err("Failed to update the situation cards because they could not be accessed!".to_string()),
+    }
+    Ok(loaded_amount)
+}
+
+/// Reads and validates the default situation cards together with the situation cards in the given folder, without making them the situation cards used by the server.
+fn read_situation_cards(folder: &Path) -> Result<Vec<SituationCard>, String> {
+    let mut situation_cards = parse_situation_card_file(DEFAULT_SITUATION_CARDS)?;
+
+    let mut loaded_card_ids = Vec::new();
+    for file_path in situation_card_files_in_folder(folder)? {
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => return Err(format!("Failed to read the situation card file {} because: {e}", file_path.display())),
+        };
+        let cards = match parse_situation_card_file(&content) {
+            Ok(cards) => cards,
+            Err(e) => return Err(format!("The situation card file {} is not valid. {e}", file_path.display())),
+        };
+        for card in cards {
+            if loaded_card_ids.contains(&card.card_id) {
+                return Err(format!("There is more than one situation card with ID {} in the folder {}!", card.card_id, folder.display()));
+            }
+            loaded_card_ids.push(card.card_id);
+            situation_cards.retain(|c| c.card_id != card.card_id);
+            situation_cards.push(card);
+        }
+    }
+    situation_cards.sort_by_key(|card| card.card_id);
+
+    let map = NodeMap::new_default();
+    for (index, card) in situation_cards.iter().enumerate() {
+        // The default situation cards can be used in any game, so they need an objective card for every player the game can have.
+        card.validate(&map, MAX_PLAYER_COUNT - 1).map_err(|e| e.to_string())?;
+        if situation_cards[..index].iter().any(|c| c.card_id == card.card_id) {
+            return Err(format!("There is more than one situation card with ID {}!", card.card_id));
+        }
+    }
+    Ok(situation_cards)
+}
+
+fn situation_card_files_in_folder(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(e) => return Err(format!("Failed to read the situation card folder {} because: {e}", folder.display())),
+    };
+    let mut file_paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| matches!(path.extension(), Some(extension) if extension == SITUATION_CARD_FILE_EXTENSION))
+        .collect();
+    file_paths.sort();
+    Ok(file_paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::game_data::custom_types::SituationCardID;
+
+    /// A folder in the temporary folder of the system that is removed again when the test is done.
+    struct TemporaryFolder {
+        path: PathBuf,
+    }
+
+    impl TemporaryFolder {
+        fn new(test_name: &str) -> Self {
+            let path = env::temp_dir().join(format!("situation_card_list_test_{}_{}", test_name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self { path }
+        }
+
+        fn write_file(&self, file_name: &str, situation_cards: &[SituationCard]) {
+            let cards: Vec<serde_json::Value> = situation_cards.iter().map(|card| serde_json::to_value(card).unwrap()).collect();
+            let content = serde_json::json!({ "situation_cards": cards });
+            fs::write(self.path.join(file_name), content.to_string()).unwrap();
+        }
+    }
+
+    impl Drop for TemporaryFolder {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn default_card(card_id: SituationCardID) -> SituationCard {
+        let cards = parse_situation_card_file(DEFAULT_SITUATION_CARDS).unwrap();
+        cards.into_iter().find(|card| card.card_id == card_id).unwrap()
+    }
+
+    #[test]
+    fn default_situation_cards_are_used_when_the_folder_does_not_exist() {
+        let folder = env::temp_dir().join("situation_card_list_test_missing_folder");
+
+        let situation_cards = read_situation_cards(&folder).unwrap();
+
+        assert_eq!(situation_cards, parse_situation_card_file(DEFAULT_SITUATION_CARDS).unwrap());
+    }
+
+    #[test]
+    fn situation_card_in_the_folder_replaces_the_default_card_with_the_same_id() {
+        let folder = TemporaryFolder::new("replace");
+        let mut card = default_card(1);
+        card.title = "Replaced".to_string();
+        folder.write_file("cards.json", &[card]);
+
+        let situation_cards = read_situation_cards(&folder.path).unwrap();
+
+        assert_eq!(situation_cards.iter().filter(|card| card.card_id == 1).count(), 1);
+        assert_eq!(situation_cards[0].title, "Replaced");
+    }
+
+    #[test]
+    fn same_card_id_in_two_files_is_not_allowed() {
+        let folder = TemporaryFolder::new("duplicate");
+        folder.write_file("first.json", &[default_card(1)]);
+        folder.write_file("second.json", &[default_card(1)]);
+
+        assert!(read_situation_cards(&folder.path).is_err());
+    }
+
+    #[test]
+    fn situation_card_that_is_not_valid_on_the_map_is_not_loaded() {
+        let folder = TemporaryFolder::new("invalid");
+        let mut card = default_card(1);
+        card.objective_cards[0].drop_off_node_id = 200;
+        folder.write_file("cards.json", &[card]);
+        let cards_before = situation_card_list();
+
+        assert!(load_situation_cards(&folder.path).is_err());
+
+        assert_eq!(situation_card_list(), cards_before);
+    }
+
+    #[test]
+    fn file_that_is_not_a_situation_card_file_is_not_valid() {
+        let folder = TemporaryFolder::new("not_cards");
+        fs::write(folder.path.join("cards.json"), "{ \"cards\": [] }").unwrap();
+
+        assert!(read_situation_cards(&folder.path).is_err());
+    }
 }
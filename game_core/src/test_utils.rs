@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use logging::logger::{LogData, Logger};
+
+use crate::{
+    game_controller::GameController,
+    game_data::{
+        custom_types::{ErrorData, GameID, NodeID, PlayerID},
+        enums::{district::District, game_error::GameError, in_game_id::InGameID, player_input_type::PlayerInputType, traffic::Traffic, type_entities_to_transport::TypeEntitiesToTransport},
+        structs::{
+            cost_tuple::CostTuple, gamestate::GameState, neighbour_relationship::NeighbourRelationship, node::Node, node_map::NodeMap,
+            player::Player, player_input::PlayerInput, player_objective_card::PlayerObjectiveCard, rule_info::RuleInfo, rule_violation::RuleViolation,
+            situation_card::SituationCard, vehicle::Vehicle,
+        },
+    },
+    game_storage::GameStorage,
+    rule_checker::RuleChecker,
+};
+
+/// The unique id of the orchestrator of the game made by [`small_game`].
+pub const ORCHESTRATOR_ID: PlayerID = 1;
+/// The unique id of the player of the game made by [`small_game`].
+pub const PLAYER_ID: PlayerID = 2;
+
+/// Returns an input of the given type from the player with the given unique id, for the first vehicle of the player.
+pub const fn input(player_id: PlayerID, game_id: GameID, input_type: PlayerInputType) -> PlayerInput {
+    PlayerInput {
+        player_id,
+        game_id,
+        input_type,
+        related_role: None,
+        related_node_id: None,
+        district_modifier: None,
+        situation_card_id: None,
+        edge_modifier: None,
+        related_bool: None,
+        related_path: None,
+        vehicle_id: 0,
+    }
+}
+
+/// A rule checker that allows every input, so that the tests only depend on the game logic in game_core.
+pub struct AllowAllRuleChecker;
+
+impl RuleChecker for AllowAllRuleChecker {
+    fn is_input_valid(&self, _game: &GameState, _input: &PlayerInput) -> Option<GameError> {
+        None
+    }
+
+    fn get_rule_violations(&self, _game: &GameState, _input: &PlayerInput) -> Vec<RuleViolation> {
+        Vec::new()
+    }
+
+    fn get_rules_info(&self) -> Vec<RuleInfo> {
+        Vec::new()
+    }
+}
+
+/// A logger that throws the log messages away.
+pub struct NoLogger;
+
+impl Logger for NoLogger {
+    fn log(&mut self, _data: LogData) {}
+}
+
+/// A storage that keeps the stored games in memory. It can be cloned, so that a test can look at the stored games after giving the storage to a game controller.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    pub games: Arc<Mutex<HashMap<GameID, GameState>>>,
+    /// The errors of the stored games that can not be loaded, returned after the other games by `load_games`.
+    pub unloadable_games: Vec<ErrorData>,
+}
+
+impl GameStorage for MemoryStorage {
+    fn save_game(&self, game: &GameState) -> Result<(), ErrorData> {
+        self.games.lock().map_err(|e| e.to_string())?.insert(game.id, game.clone());
+        Ok(())
+    }
+
+    fn remove_game(&self, game_id: GameID) -> Result<(), ErrorData> {
+        self.games.lock().map_err(|e| e.to_string())?.remove(&game_id);
+        Ok(())
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<GameState, ErrorData>>, ErrorData> {
+        let games = self.games.lock().map_err(|e| e.to_string())?;
+        Ok(games.values().cloned().map(Ok).chain(self.unloadable_games.iter().cloned().map(Err)).collect())
+    }
+}
+
+/// Returns a game controller that allows every input and stores its games in the given storage.
+pub fn test_controller(storage: MemoryStorage) -> GameController {
+    GameController::new(Arc::new(RwLock::new(NoLogger)), Box::new(AllowAllRuleChecker), Box::new(storage))
+}
+
+/// Returns a small map with the road 0 - 1 - 2 - 3 and the detour 1 - 4 - 3. The edge 0 - 1 is in the industry park, the edges 1 - 2 - 3 are in the city centre and the detour is in the suburbs. Every edge costs one move, and entering a district costs nothing until the cost is changed.
+pub fn small_map() -> NodeMap {
+    let mut map = NodeMap::new();
+    map.nodes = (0..5).map(|id| Node::new(id, format!("Node {}", id))).collect();
+    for (node_one, node_two, district) in [
+        (0, 1, District::IndustryPark),
+        (1, 2, District::CityCentre),
+        (2, 3, District::CityCentre),
+        (1, 4, District::Suburbs),
+        (4, 3, District::Suburbs),
+    ] {
+        connect(&mut map, node_one, node_two, district);
+    }
+    let mut district = Some(District::first());
+    while let Some(d) = district {
+        map.change_neighbourhood_cost(d, 0);
+        district = d.next();
+    }
+    map
+}
+
+fn connect(map: &mut NodeMap, node_one: NodeID, node_two: NodeID, district: District) {
+    map.edges.entry(node_one).or_default().push(NeighbourRelationship::new(node_two, district, 1, false));
+    map.edges.entry(node_two).or_default().push(NeighbourRelationship::new(node_one, district, 1, false));
+}
+
+/// Returns a started game on the [`small_map`] where it's the turn of the player. The player has the starting amount of money and one vehicle at node 0 with the starting amount of moves, and has to pick up a package at node 1 and drop it off at node 3. The situation card has no traffic in any district, and is the custom situation card of the game so that district modifiers change the traffic based on it instead of a situation card of the server.
+pub fn small_game() -> GameState {
+    let mut game = GameState::new("Test game".to_string(), 1);
+    game.map = small_map();
+    let costs = [District::IndustryPark, District::Port, District::Suburbs, District::RingRoad, District::CityCentre, District::Airport]
+        .into_iter()
+        .map(|district| CostTuple::new(district, Traffic::LevelOne))
+        .collect();
+    let objective_card = PlayerObjectiveCard::new("Delivery".to_string(), 0, 1, 3, Vec::new(), TypeEntitiesToTransport::Packages, 1);
+    game.situation_card = Some(SituationCard::new(1, "Test".to_string(), "A test situation".to_string(), String::new(), costs, vec![objective_card.clone()]));
+    game.custom_situation_card = game.situation_card.clone();
+
+    let mut orchestrator = Player::new(ORCHESTRATOR_ID, "Orchestrator".to_string());
+    orchestrator.in_game_id = InGameID::Orchestrator;
+    orchestrator.connected_game_id = Some(game.id);
+    let mut player = Player::new(PLAYER_ID, "Player".to_string());
+    player.in_game_id = InGameID::PlayerOne;
+    player.connected_game_id = Some(game.id);
+    player.money = game.get_starting_player_money_value();
+    player.vehicles = vec![Vehicle::new(0, objective_card.start_node_id, game.get_starting_player_movement_value(), Vec::new())];
+    player.objective_card = Some(objective_card);
+    game.players = vec![orchestrator, player];
+
+    game.is_lobby = false;
+    game.current_players_turn = InGameID::PlayerOne;
+    game
+}
+
+/// Returns the player of the game made by [`small_game`].
+pub fn player(game: &GameState) -> Player {
+    game.get_player_with_unique_id(PLAYER_ID).expect("The game has the player")
+}
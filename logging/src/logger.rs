@@ -1,5 +1,5 @@
 use chrono::Local;
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub enum LogLevel {
@@ -10,6 +10,22 @@ pub enum LogLevel {
     Ignore = 5,
 }
 
+impl FromStr for LogLevel {
+    type Err = String;
+
+    /// Parses the name of a log level, e.g. `warning`, without caring about upper or lower case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            "ignore" => Ok(Self::Ignore),
+            _ => Err(format!("{s} is not a log level. Use debug, info, warning, error or ignore.")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct LogData<'a> {
     pub severity_level: LogLevel,
@@ -4,15 +4,16 @@ use crate::logger::{LogData, LogLevel, Logger};
 use crate::{LOG_FOLDER_NAME, MAX_FILE_SIZE};
 use std::any::type_name;
 use std::env;
-use std::fs::{metadata, OpenOptions};
+use std::fs::{create_dir_all, metadata, OpenOptions};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// The threshold logger will print and store data if the logging data is over a the set thresholds.
 pub struct ThresholdLogger {
     print_threshold: LogLevel,
     store_threshold: LogLevel,
     file_index: u128,
+    log_folder: Option<PathBuf>,
 }
 
 impl ThresholdLogger {
@@ -23,6 +24,18 @@ impl ThresholdLogger {
             print_threshold,
             store_threshold,
             file_index: 0,
+            log_folder: None,
+        }
+    }
+
+    /// Creates a new threshold logger with the given thresholds that stores the logs in the given folder instead of the default folder next to the executable.
+    #[must_use]
+    pub const fn with_log_folder(print_threshold: LogLevel, store_threshold: LogLevel, log_folder: PathBuf) -> Self {
+        Self {
+            print_threshold,
+            store_threshold,
+            file_index: 0,
+            log_folder: Some(log_folder),
         }
     }
 
@@ -75,7 +88,7 @@ impl ThresholdLogger {
 
     fn create_file_path(&mut self) -> Result<String, String> {
         let mut file_name: String = self.create_file_name();
-        let mut file_path: String = match Self::create_file_path_for_file_name(&file_name) {
+        let mut file_path: String = match self.create_file_path_for_file_name(&file_name) {
             Ok(path) => path,
             Err(e) => return Err(format!("Failed to create file path because: {e}")),
         };
@@ -83,7 +96,7 @@ impl ThresholdLogger {
         while metadata(&file_path).map(|m| m.len()).unwrap_or(0) >= MAX_FILE_SIZE {
             self.file_index += 1;
             file_name = self.create_file_name();
-            file_path = match Self::create_file_path_for_file_name(&file_name) {
+            file_path = match self.create_file_path_for_file_name(&file_name) {
                 Ok(path) => path,
                 Err(e) => return Err(format!("Failed to create file path because: {e}")),
             };
@@ -100,13 +113,19 @@ impl ThresholdLogger {
         )
     }
 
-    fn create_file_path_for_file_name(file_name: &str) -> Result<String, String> {
+    fn create_file_path_for_file_name(&self, file_name: &str) -> Result<String, String> {
+        if let Some(log_folder) = &self.log_folder {
+            if let Err(e) = create_dir_all(log_folder) {
+                return Err(format!("Failed to create the log folder because: {e}"));
+            }
+            return Ok(log_folder.join(file_name).to_string_lossy().to_string());
+        }
         match env::current_exe() {
             Ok(path) => match path.parent() {
                 Some(exe_folder) => {
                     let file_path =
                         Path::new(exe_folder).join(LOG_FOLDER_NAME.to_string() + "/" + file_name);
-                    return Ok(file_path.to_string_lossy().to_string());
+                    Ok(file_path.to_string_lossy().to_string())
                 }
                 None => Err("Failed to get path of the folder the executable is in.".to_string()),
             },
@@ -3,15 +3,27 @@ use std::ops::ControlFlow;
 use game_core::{
     rule_checker::{RuleChecker},
     game_data::{structs::{
-        gamestate::GameState, player_input::PlayerInput, edge_restriction::EdgeRestriction, neighbour_relationship::NeighbourRelationship},
-        enums::{player_input_type::PlayerInputType, district_modifier_type::DistrictModifierType, restriction_type::RestrictionType, in_game_id::InGameID},
-        custom_types::{NodeID, ErrorData}}};
+        gamestate::GameState, player_input::PlayerInput, edge_restriction::EdgeRestriction, neighbour_relationship::NeighbourRelationship, rule_violation::RuleViolation, rule_info::RuleInfo},
+        enums::{player_input_type::PlayerInputType, district_modifier_type::DistrictModifierType, restriction_type::RestrictionType, in_game_id::InGameID, game_error::{GameError, InvalidInputReason}},
+        custom_types::NodeID}};
+
+/// The name given to the violations of a movement path that are not found by one of the rules, e.g. when the path is empty.
+const MOVEMENT_PATH_RULE_NAME: &str = "movement_path";
 
 type RuleFn = Box<dyn Fn(&GameState, &PlayerInput) -> ValidationResponse<String> + Send + Sync>;
 
 struct Rule {
+    /// The name of the rule, which is given to the client together with the error when the rule is broken.
+    pub name: &'static str,
+    pub description: &'static str,
+    /// If the rule is used in a game where the rule set of the game does not say anything about it.
+    pub enabled_by_default: bool,
+    /// If the rule can be turned off by the rule set of a game.
+    pub can_be_disabled: bool,
     pub related_inputs: Vec<PlayerInputType>,
     pub rule_fn: RuleFn,
+    /// Turns the error message of the rule into the kind of error the rule gives when it's broken.
+    pub to_error: fn(String) -> GameError,
 }
 
 /// This struct contains the implementation of the RuleChecker trait.
@@ -27,27 +39,35 @@ enum ValidationResponse<T> {
 
 impl RuleChecker for GameRuleChecker {
     /// Checks if the input is valid based on the rules defined by this `GameRuleChecker`.
-    fn is_input_valid(&self, game: &GameState, player_input: &PlayerInput) -> Option<ErrorData> {
-        let mut error_str = "Invalid input!".to_string();
-        let foreach_status = &self.rules.iter().try_for_each(|rule| {
-            if rule.related_inputs.iter().all(|input_type| {
-                input_type != &player_input.input_type && input_type != &PlayerInputType::All
-            }) {
-                return ControlFlow::Continue(());
-            }
+    /// 
+    /// A movement with a path is valid if every step in the path is valid when the steps before it have been made, which also makes sure the player has enough moves for the whole path.
+    fn is_input_valid(&self, game: &GameState, player_input: &PlayerInput) -> Option<GameError> {
+        self.find_violations(game, player_input, true)
+            .into_iter()
+            .next()
+            .map(|(_, error)| error)
+    }
 
-            match (rule.rule_fn)(game, player_input) {
-                ValidationResponse::Valid => ControlFlow::Continue(()),
-                ValidationResponse::Invalid(e) => {
-                    error_str = e;
-                    ControlFlow::Break(false)
-                }
-            }
-        });
-        if foreach_status.eq(&ControlFlow::Break(false)) {
-            return Some(error_str);
-        }
-        None
+    /// Checks the input against every rule defined by this `GameRuleChecker` that is related to the input, and returns every rule it breaks.
+    /// 
+    /// For a movement with a path, the violations of the first step that is not valid are returned, since the steps after it can not be checked without making it.
+    fn get_rule_violations(&self, game: &GameState, player_input: &PlayerInput) -> Vec<RuleViolation> {
+        self.find_violations(game, player_input, false)
+            .into_iter()
+            .map(|(rule_name, error)| RuleViolation::new(rule_name, &error))
+            .collect()
+    }
+
+    fn get_rules_info(&self) -> Vec<RuleInfo> {
+        self.rules
+            .iter()
+            .map(|rule| RuleInfo {
+                name: rule.name.to_string(),
+                description: rule.description.to_string(),
+                enabled_by_default: rule.enabled_by_default,
+                can_be_disabled: rule.can_be_disabled,
+            })
+            .collect()
     }
 }
 
@@ -66,51 +86,179 @@ impl GameRuleChecker {
         }
     }
 
+    /// Finds the rules the input breaks together with the error of each of them. If `stop_at_first` is true, the rules are not checked further after the first broken rule.
+    fn find_violations(&self, game: &GameState, player_input: &PlayerInput, stop_at_first: bool) -> Vec<(&'static str, GameError)> {
+        if player_input.input_type == PlayerInputType::Movement {
+            if let Some(path) = &player_input.related_path {
+                return self.find_movement_path_violations(game, player_input, path, stop_at_first);
+            }
+        }
+
+        let mut violations = Vec::new();
+        let _ = self.rules.iter().try_for_each(|rule| {
+            if rule.related_inputs.iter().all(|input_type| {
+                input_type != &player_input.input_type && input_type != &PlayerInputType::All
+            }) {
+                return ControlFlow::Continue(());
+            }
+            if rule.can_be_disabled && !game.rule_set.is_rule_enabled(rule.name, rule.enabled_by_default) {
+                return ControlFlow::Continue(());
+            }
+
+            match (rule.rule_fn)(game, player_input) {
+                ValidationResponse::Valid => ControlFlow::Continue(()),
+                ValidationResponse::Invalid(e) => {
+                    violations.push((rule.name, (rule.to_error)(e)));
+                    match stop_at_first {
+                        true => ControlFlow::Break(()),
+                        false => ControlFlow::Continue(()),
+                    }
+                }
+            }
+        });
+        violations
+    }
+
+    fn find_movement_path_violations(&self, game: &GameState, player_input: &PlayerInput, path: &[NodeID], stop_at_first: bool) -> Vec<(&'static str, GameError)> {
+        if path.is_empty() {
+            return vec![(MOVEMENT_PATH_RULE_NAME, GameError::InvalidInput(InvalidInputReason::MissingData, "The movement path has no nodes!".to_string()))];
+        }
+        if player_input.related_node_id.is_some() {
+            return vec![(MOVEMENT_PATH_RULE_NAME, GameError::InvalidInput(InvalidInputReason::MissingData, "A movement can not have both a related node and a path!".to_string()))];
+        }
+
+        let mut game_clone = game.clone();
+        for node_id in path {
+            let mut step_input = player_input.clone();
+            step_input.related_node_id = Some(*node_id);
+            step_input.related_path = None;
+            let violations = self.find_violations(&game_clone, &step_input, stop_at_first);
+            if !violations.is_empty() {
+                return violations
+                    .into_iter()
+                    .map(|(rule_name, error)| (rule_name, error.map_message(|message| format!("The move to node {} in the path is not valid because: {}", node_id, message))))
+                    .collect();
+            }
+            if let Err(e) = game_clone.move_player_with_id(player_input.player_id, player_input.vehicle_id, *node_id) {
+                return vec![(MOVEMENT_PATH_RULE_NAME, e)];
+            }
+            if let Err(e) = game_clone.update_objective_status() {
+                return vec![(MOVEMENT_PATH_RULE_NAME, e)];
+            }
+        }
+        Vec::new()
+    }
+
     fn get_rules() -> Vec<Rule> {
         let game_started = Rule {
+            name: "game_started",
+            description: "The game has to be started before the players can move, modify districts, end their turn, undo an action or end the game.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![
                 PlayerInputType::Movement,
                 PlayerInputType::ModifyDistrict,
                 PlayerInputType::NextTurn,
                 PlayerInputType::UndoAction,
+                PlayerInputType::EndGame,
             ],
             rule_fn: Box::new(has_game_started),
+            to_error: GameError::Conflict,
         };
         let players_turn = Rule {
+            name: "players_turn",
+            description: "A player can only make inputs when it's their turn.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![PlayerInputType::All],
             rule_fn: Box::new(is_players_turn),
+            to_error: GameError::NotYourTurn,
         };
         let orchestrator_check = Rule {
+            name: "orchestrator",
+            description: "Only the orchestrator can start the game, modify edges and districts and end the game.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![
                 PlayerInputType::StartGame,
                 PlayerInputType::ModifyEdgeRestrictions,
                 PlayerInputType::ModifyDistrict,
+                PlayerInputType::EndGame,
             ],
             rule_fn: Box::new(is_orchestrator),
+            to_error: GameError::Forbidden,
         };
         let player_has_position = Rule {
+            name: "player_has_position",
+            description: "A vehicle has to be on a node to move.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![PlayerInputType::Movement],
             rule_fn: Box::new(has_position),
+            to_error: no_position,
         };
         let toggle_bus = Rule {
+            name: "toggle_bus",
+            description: "A vehicle can only start or stop being a bus on a parking spot.",
+            enabled_by_default: true,
+            can_be_disabled: true,
             related_inputs: vec![PlayerInputType::SetPlayerBusBool],
             rule_fn: Box::new(can_toggle_bus),
+            to_error: bus_not_allowed,
         };
         let next_to_node = Rule {
+            name: "next_to_node",
+            description: "A vehicle can only move to a node next to the node it is on.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![PlayerInputType::Movement],
             rule_fn: Box::new(next_node_is_neighbour),
+            to_error: not_neighbour,
         };
         let enough_moves = Rule {
+            name: "enough_moves",
+            description: "A vehicle can only move if it has enough moves left.",
+            enabled_by_default: true,
+            can_be_disabled: true,
             related_inputs: vec![PlayerInputType::Movement],
             rule_fn: Box::new(has_enough_moves),
+            to_error: not_enough_moves,
+        };
+        let enough_money = Rule {
+            name: "enough_money",
+            description: "A player can only move into a district with a toll if they can afford it.",
+            enabled_by_default: true,
+            can_be_disabled: true,
+            related_inputs: vec![PlayerInputType::Movement],
+            rule_fn: Box::new(has_enough_money),
+            to_error: not_enough_money,
         };
         let move_to_node = Rule {
+            name: "move_to_node",
+            description: "A vehicle can only move along edges the player has access to, e.g. not along park & ride edges unless it is a bus, not against one way streets and only into districts with an access modifier with the right vehicle type.",
+            enabled_by_default: true,
+            can_be_disabled: true,
             related_inputs: vec![PlayerInputType::Movement],
             rule_fn: Box::new(can_move_to_node),
+            to_error: movement_not_allowed,
         };
         let can_modify_edge_restriction = Rule {
+            name: "modify_edge_restriction",
+            description: "An edge can only get a restriction if it does not have one already and is modifiable.",
+            enabled_by_default: true,
+            can_be_disabled: false,
             related_inputs: vec![PlayerInputType::ModifyEdgeRestrictions],
             rule_fn: Box::new(is_edge_modification_action_valid),
+            to_error: edge_modification_not_allowed,
+        };
+        let strict_park_and_ride = Rule {
+            name: "strict_park_and_ride",
+            description: "Park & ride can only be placed next to a parking spot or another park & ride edge, and a park & ride edge can not be removed if it connects two other park & ride edges.",
+            enabled_by_default: false,
+            can_be_disabled: true,
+            related_inputs: vec![PlayerInputType::ModifyEdgeRestrictions],
+            rule_fn: Box::new(is_strict_park_and_ride_modification_valid),
+            to_error: edge_modification_not_allowed,
         };
 
         let rules = vec![
@@ -121,8 +269,10 @@ impl GameRuleChecker {
             toggle_bus,
             next_to_node,
             enough_moves,
+            enough_money,
             move_to_node,
             can_modify_edge_restriction,
+            strict_park_and_ride,
         ];
         rules
     }
@@ -140,11 +290,20 @@ macro_rules! get_player_or_return_invalid_response {
     }};
 }
 
-macro_rules! get_player_position_id_or_return_invalid_response {
-    ($player:expr) => {{
-        match $player.position_node_id {
+macro_rules! get_vehicle_or_return_invalid_response {
+    ($player:expr, $player_input:expr) => {{
+        match $player.get_vehicle($player_input.vehicle_id) {
+            Ok(vehicle) => vehicle.clone(),
+            Err(e) => return ValidationResponse::Invalid(e.to_string()),
+        }
+    }};
+}
+
+macro_rules! get_vehicle_position_id_or_return_invalid_response {
+    ($vehicle:expr) => {{
+        match $vehicle.position_node_id {
             Some(id) => id,
-            None => return ValidationResponse::Invalid("The player does not have a position and can therefore not check if it's a valid action!".to_string()),
+            None => return ValidationResponse::Invalid("The vehicle does not have a position and can therefore not check if it's a valid action!".to_string()),
         }
     }};
 }
@@ -161,9 +320,10 @@ fn has_game_started(game: &GameState, _player_input: &PlayerInput) -> Validation
 
 fn has_enough_moves(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
     let player = get_player_or_return_invalid_response!(game, player_input);
+    let vehicle = get_vehicle_or_return_invalid_response!(player, player_input);
 
-    if player.remaining_moves == 0 {
-        return ValidationResponse::Invalid("The player has no remaining moves!".to_string());
+    if vehicle.remaining_moves == 0 {
+        return ValidationResponse::Invalid("The vehicle has no remaining moves!".to_string());
     }
 
     let Some(related_node_id) = player_input.related_node_id else {
@@ -172,46 +332,70 @@ fn has_enough_moves(game: &GameState, player_input: &PlayerInput) -> ValidationR
 
     let mut game_clone = game.clone();
 
-    match game_clone.move_player_with_id(player_input.player_id, related_node_id) {
+    match game_clone.move_player_with_id(player_input.player_id, player_input.vehicle_id, related_node_id) {
         Ok(_) => (),
-        Err(e) => return ValidationResponse::Invalid(e),
+        Err(e) => return ValidationResponse::Invalid(e.to_string()),
     }
 
     has_non_negative_amount_of_moves_left(&game_clone, player_input)
 }
 
-// Checks if the player has non-negative amount of remaining moves in the provided GameState.
+// Checks if the vehicle of the player has non-negative amount of remaining moves in the provided GameState.
 fn has_non_negative_amount_of_moves_left(
     game: &GameState,
     player_input: &PlayerInput,
 ) -> ValidationResponse<String> {
     let player = get_player_or_return_invalid_response!(game, player_input);
+    let vehicle = get_vehicle_or_return_invalid_response!(player, player_input);
+
+    if vehicle.remaining_moves < 0 {
+        return ValidationResponse::Invalid(
+            format!("The vehicle does not have enough remaining moves! The vehicle would have {} remaining moves!", vehicle.remaining_moves),
+        );
+    }
+
+    ValidationResponse::Valid
+}
+
+// Checks if the player can afford the tolls they would have to pay by moving to the related node.
+fn has_enough_money(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
+    let Some(related_node_id) = player_input.related_node_id else {
+        return ValidationResponse::Invalid("There was no node to get the toll cost to!".to_string());
+    };
+
+    let mut game_clone = game.clone();
+
+    match game_clone.move_player_with_id(player_input.player_id, player_input.vehicle_id, related_node_id) {
+        Ok(_) => (),
+        Err(e) => return ValidationResponse::Invalid(e.to_string()),
+    }
 
-    if player.remaining_moves < 0 {
+    let player = get_player_or_return_invalid_response!(game_clone, player_input);
+
+    if player.money < 0 {
         return ValidationResponse::Invalid(
-            format!("The player does not have enough remaining moves! The player would have {} remaining moves!", player.remaining_moves),
+            format!("The player cannot afford the toll to move to node {}! The player would have {} money left!", related_node_id, player.money),
         );
     }
 
     ValidationResponse::Valid
 }
 
-// Checks if the player can enter the district the player wants to move to based on their objective card/vehicle type.
+// Checks if the vehicle of the player can enter the district it wants to move to based on its vehicle types.
 fn can_enter_district(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
     let player = get_player_or_return_invalid_response!(game, player_input);
 
     let district_modifiers = &game.district_modifiers;
 
-    let player_objective_card = match &player.objective_card {
-        Some(objective_card) => objective_card,
-        None => {
-            return ValidationResponse::Invalid(
-                "Error: Player does not have an objective card".to_string(),
-            )
-        }
-    };
+    if player.objective_card.is_none() {
+        return ValidationResponse::Invalid(
+            "Error: Player does not have an objective card".to_string(),
+        );
+    }
+
+    let vehicle = get_vehicle_or_return_invalid_response!(player, player_input);
 
-    let neighbours = match player.position_node_id {
+    let neighbours = match vehicle.position_node_id {
         Some(pos) => match game.map.get_neighbour_relationships_of_node_with_id(pos) {
             Some(vec) => vec,
             None => {
@@ -223,7 +407,7 @@ fn can_enter_district(game: &GameState, player_input: &PlayerInput) -> Validatio
         },
         None => {
             return ValidationResponse::Invalid(
-                "Error: Player does not have a valid position and can therefore not move"
+                "Error: Vehicle does not have a valid position and can therefore not move"
                     .to_string(),
             )
         }
@@ -247,9 +431,7 @@ fn can_enter_district(game: &GameState, player_input: &PlayerInput) -> Validatio
             return ValidationResponse::Invalid("Error: There was no vehicle for access modifier".to_string());
         };
         district_has_modifier = true;
-        if player_objective_card
-            .special_vehicle_types
-            .contains(&vehicle_type)
+        if vehicle.has_vehicle_type(vehicle_type)
             || (vehicle_type == RestrictionType::Destination
             && GameState::player_has_objective_in_district(&game.map, &player, dm.district))
         {
@@ -266,11 +448,11 @@ fn can_enter_district(game: &GameState, player_input: &PlayerInput) -> Validatio
 }
 
 fn has_position(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
-    match game.get_player_with_unique_id(player_input.player_id) {
-        Ok(p) => {
-            if p.position_node_id.is_none() {
+    match game.get_player_with_unique_id(player_input.player_id).and_then(|p| p.get_vehicle(player_input.vehicle_id).cloned()) {
+        Ok(vehicle) => {
+            if vehicle.position_node_id.is_none() {
                 return ValidationResponse::Invalid(
-                    "The player does not have a position!".to_string(),
+                    "The vehicle does not have a position!".to_string(),
                 );
             }
             ValidationResponse::Valid
@@ -283,9 +465,9 @@ fn next_node_is_neighbour(
     game: &GameState,
     player_input: &PlayerInput,
 ) -> ValidationResponse<String> {
-    match game.get_player_with_unique_id(player_input.player_id) {
-        Ok(p) => {
-            match p.position_node_id {
+    match game.get_player_with_unique_id(player_input.player_id).and_then(|p| p.get_vehicle(player_input.vehicle_id).cloned()) {
+        Ok(vehicle) => {
+            match vehicle.position_node_id {
                 Some(node_id) => {
                     let Some(related_node_id) = player_input.related_node_id else {
                         return ValidationResponse::Invalid("There was node to check if it's a neighbour!".to_string());
@@ -293,17 +475,17 @@ fn next_node_is_neighbour(
                     let are_neighbours =
                         match game.map.are_nodes_neighbours(node_id, related_node_id) {
                             Ok(b) => b,
-                            Err(e) => return ValidationResponse::Invalid(e),
+                            Err(e) => return ValidationResponse::Invalid(e.to_string()),
                         };
                     if !are_neighbours {
                         return ValidationResponse::Invalid(format!(
-                            "The node {related_node_id} is not a neighbour of the player's position!",
+                            "The node {related_node_id} is not a neighbour of the vehicle's position!",
                         ));
                     }
                 }
                 None => {
                     return ValidationResponse::Invalid(
-                        "The player does not have a position!".to_string(),
+                        "The vehicle does not have a position!".to_string(),
                     )
                 }
             }
@@ -314,7 +496,10 @@ fn next_node_is_neighbour(
 }
 
 fn is_players_turn(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
-    if game.is_lobby || player_input.input_type == PlayerInputType::LeaveGame {
+    if game.is_lobby
+        || player_input.input_type == PlayerInputType::LeaveGame
+        || player_input.input_type == PlayerInputType::EndGame
+    {
         return ValidationResponse::Valid;
     }
 
@@ -357,12 +542,29 @@ fn is_edge_modification_action_valid(
     };
 
     default_can_modify_edge_restriction(&edge_mod, &neighbours_one, edge_mod.node_two)
+}
+
+// Only lets the orchestrator add park & ride next to a parking spot or another park & ride edge, and not delete park & ride edges that connect other park & ride edges.
+fn is_strict_park_and_ride_modification_valid(
+    game: &GameState,
+    player_input: &PlayerInput,
+) -> ValidationResponse<String> {
+    let Some(edge_mod) = player_input.edge_modifier.clone() else {
+        return ValidationResponse::Invalid("There was no modifier on the edge modifier player input, and can therefore not check the input further!".to_string());
+    };
+    if edge_mod.edge_restriction != RestrictionType::ParkAndRide {
+        return ValidationResponse::Valid;
+    }
+
+    let Some(neighbours_one) = game.map.get_neighbour_relationships_of_node_with_id(edge_mod.node_one) else {
+        return ValidationResponse::Invalid(format!("The node {} does not have neighbours and can therefore not have restrictions!", edge_mod.node_one));
+    };
 
-    // match edge_mod.edge_restriction { // This can be turned on if you only want to add or delete edges next to park and ride start node or other park and ride edges, but you cannot delete edges if there are cycles.
-    //     RestrictionType::ParkAndRide => can_modify_park_and_ride(game, &edge_mod, &neighbours_one, &neighbours_two), 
-    //     _ => default_can_modify_edge_restriction(&edge_mod, &neighbours_one, edge_mod.node_two),
-    // }
+    let Some(neighbours_two) = game.map.get_neighbour_relationships_of_node_with_id(edge_mod.node_two) else {
+        return ValidationResponse::Invalid(format!("The node {} does not have neighbours and can therefore not have restrictions!", edge_mod.node_two));
+    };
 
+    can_modify_park_and_ride(game, &edge_mod, &neighbours_one, &neighbours_two)
 }
 
 fn default_can_modify_edge_restriction(edge_mod: &EdgeRestriction, neighbours_one: &[NeighbourRelationship], node_two_id: NodeID) -> ValidationResponse<String> {
@@ -381,7 +583,6 @@ fn default_can_modify_edge_restriction(edge_mod: &EdgeRestriction, neighbours_on
     ValidationResponse::Valid
 }
 
-#[allow(dead_code)]
 fn can_modify_park_and_ride(game: &GameState, park_and_ride_mod: &EdgeRestriction, neighbours_one: &[NeighbourRelationship], neighbours_two: &[NeighbourRelationship]) -> ValidationResponse<String> {
     if park_and_ride_mod.delete {
         if neighbours_one
@@ -404,7 +605,7 @@ fn can_modify_park_and_ride(game: &GameState, park_and_ride_mod: &EdgeRestrictio
         Ok(n) => n,
         Err(e) => {
             return ValidationResponse::Invalid(
-                e + " and can therefore not check wether the park & ride can be placed here!",
+                e.to_string() + " and can therefore not check wether the park & ride can be placed here!",
             )
         }
     };
@@ -413,7 +614,7 @@ fn can_modify_park_and_ride(game: &GameState, park_and_ride_mod: &EdgeRestrictio
         Ok(n) => n,
         Err(e) => {
             return ValidationResponse::Invalid(
-                e + " and can therefore not check wether the park & ride can be placed here!",
+                e.to_string() + " and can therefore not check wether the park & ride can be placed here!",
             )
         }
     };
@@ -441,8 +642,9 @@ fn can_modify_park_and_ride(game: &GameState, park_and_ride_mod: &EdgeRestrictio
 
 fn can_move_to_node(game: &GameState, player_input: &PlayerInput) -> ValidationResponse<String> {
     let player = get_player_or_return_invalid_response!(game, player_input);
+    let vehicle = get_vehicle_or_return_invalid_response!(player, player_input);
     
-    let player_pos = get_player_position_id_or_return_invalid_response!(player);
+    let player_pos = get_vehicle_position_id_or_return_invalid_response!(vehicle);
 
     let Some(to_node_id) = player_input.related_node_id else {
         return ValidationResponse::Invalid("There is no related node to the movement input. There needs to be a node if a players should move!".to_string());
@@ -452,7 +654,7 @@ fn can_move_to_node(game: &GameState, player_input: &PlayerInput) -> ValidationR
         return ValidationResponse::Invalid(format!("The node {} does not have neighbours and can therefore not have park and ride!", player_pos));
     };
 
-    if player.is_bus {
+    if vehicle.is_bus {
         if neighbours
             .iter()
             .any(|neighbour| neighbour.restriction == Some(RestrictionType::ParkAndRide) && neighbour.to == to_node_id)
@@ -468,7 +670,7 @@ fn can_move_to_node(game: &GameState, player_input: &PlayerInput) -> ValidationR
         Ok(n) => n,
         Err(e) => {
             return ValidationResponse::Invalid(
-                e + " And can therefore not check whether the player can move here!",
+                e.to_string() + " And can therefore not check whether the player can move here!",
             )
         }
     };
@@ -477,12 +679,12 @@ fn can_move_to_node(game: &GameState, player_input: &PlayerInput) -> ValidationR
         Ok(n) => n,
         Err(e) => {
             return ValidationResponse::Invalid(
-                e + " And can therefore not check whether the player can move here!",
+                e.to_string() + " And can therefore not check whether the player can move here!",
             )
         }
     };
 
-    if current_node.is_connected_to_rail && to_node.is_connected_to_rail && !player.is_bus {
+    if current_node.is_connected_to_rail && to_node.is_connected_to_rail && !vehicle.is_bus {
         if neighbours
             .iter()
             .any(|neighbour| neighbour.is_connected_through_rail && neighbour.to == to_node_id)
@@ -515,11 +717,11 @@ fn can_move_to_node(game: &GameState, player_input: &PlayerInput) -> ValidationR
     };
 
     if let Some(restriction) = neighbour_relationship.restriction {
-        let Some(objective_card) = &player.objective_card else {
+        if player.objective_card.is_none() {
             return ValidationResponse::Invalid(format!("The player {} does not have an objective card and we can therefore not check if the player has access to the given zone!", player.name));
-        };
+        }
 
-        if (!(objective_card.special_vehicle_types.contains(&restriction)
+        if (!(vehicle.has_vehicle_type(restriction)
         || (restriction == RestrictionType::Destination
         && GameState::player_has_objective_in_district(&game.map, &player, neighbour_relationship.neighbourhood)))) && restriction != RestrictionType::OneWay
          {
@@ -553,12 +755,13 @@ fn can_toggle_bus(game: &GameState, player_input: &PlayerInput) -> ValidationRes
         return ValidationResponse::Invalid("Could not check if you can toggle bus because the related bool was not set. It's needed for so that we can know if you want to stop being a bus or change to a bus!".to_string());
     };
 
-    let player_pos = get_player_position_id_or_return_invalid_response!(player);
+    let vehicle = get_vehicle_or_return_invalid_response!(player, player_input);
+    let player_pos = get_vehicle_position_id_or_return_invalid_response!(vehicle);
     let node = match game.map.get_node_by_id(player_pos) {
         Ok(n) => n,
         Err(e) => {
             return ValidationResponse::Invalid(
-                e + " and can therefore not check wether the player can toggle bus!",
+                e.to_string() + " and can therefore not check wether the player can toggle bus!",
             )
         }
     };
@@ -571,3 +774,195 @@ fn can_toggle_bus(game: &GameState, player_input: &PlayerInput) -> ValidationRes
 
     ValidationResponse::Valid
 }
+
+// ==================== ERRORS ====================
+// The kinds of errors the rules give when they are broken.
+
+fn no_position(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::NoPosition, message)
+}
+
+fn bus_not_allowed(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::BusNotAllowed, message)
+}
+
+fn not_neighbour(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::NotNeighbour, message)
+}
+
+fn not_enough_moves(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::NotEnoughMoves, message)
+}
+
+fn not_enough_money(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::NotEnoughMoney, message)
+}
+
+fn movement_not_allowed(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, message)
+}
+
+fn edge_modification_not_allowed(message: String) -> GameError {
+    GameError::InvalidInput(InvalidInputReason::EdgeModificationNotAllowed, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use game_core::game_data::{
+        custom_types::{PlayerID, VehicleID},
+        enums::{district::District, type_entities_to_transport::TypeEntitiesToTransport},
+        structs::{district_modifier::DistrictModifier, node::Node, node_map::NodeMap, player::Player, player_objective_card::PlayerObjectiveCard, vehicle::Vehicle},
+    };
+
+    use super::*;
+
+    const PLAYER_ID: PlayerID = 2;
+
+    /// Returns a started game on the road 0 - 1 - 2, where the edge 0 - 1 is in the suburbs and the edge 1 - 2 is in the city centre. It's the turn of the player, who has a car at node 0 and an emergency vehicle at node 1, and has to deliver a package from node 0 to node 1.
+    fn game() -> GameState {
+        let mut game = GameState::new("Test game".to_string(), 1);
+        let mut map = NodeMap::new();
+        map.nodes = (0..3).map(|id| Node::new(id, format!("Node {}", id))).collect();
+        for (node_one, node_two, district) in [(0, 1, District::Suburbs), (1, 2, District::CityCentre)] {
+            map.edges.entry(node_one).or_default().push(NeighbourRelationship::new(node_two, district, 1, false));
+            map.edges.entry(node_two).or_default().push(NeighbourRelationship::new(node_one, district, 1, false));
+        }
+        let mut district = Some(District::first());
+        while let Some(d) = district {
+            map.change_neighbourhood_cost(d, 0);
+            district = d.next();
+        }
+        game.map = map;
+
+        let mut orchestrator = Player::new(1, "Orchestrator".to_string());
+        orchestrator.in_game_id = InGameID::Orchestrator;
+        let mut player = Player::new(PLAYER_ID, "Player".to_string());
+        player.in_game_id = InGameID::PlayerOne;
+        player.objective_card = Some(PlayerObjectiveCard::new("Delivery".to_string(), 0, 0, 1, Vec::new(), TypeEntitiesToTransport::Packages, 1));
+        player.vehicles = vec![Vehicle::new(0, 0, 5, Vec::new()), Vehicle::new(1, 1, 5, vec![RestrictionType::Emergency])];
+        game.players = vec![orchestrator, player];
+
+        game.is_lobby = false;
+        game.current_players_turn = InGameID::PlayerOne;
+        game
+    }
+
+    fn movement(vehicle_id: VehicleID, to_node_id: NodeID) -> PlayerInput {
+        PlayerInput {
+            player_id: PLAYER_ID,
+            game_id: 1,
+            input_type: PlayerInputType::Movement,
+            related_role: None,
+            related_node_id: Some(to_node_id),
+            district_modifier: None,
+            situation_card_id: None,
+            edge_modifier: None,
+            related_bool: None,
+            related_path: None,
+            vehicle_id,
+        }
+    }
+
+    fn access_modifier(district: District, vehicle_type: RestrictionType) -> DistrictModifier {
+        DistrictModifier {
+            district,
+            modifier: DistrictModifierType::Access,
+            vehicle_type: Some(vehicle_type),
+            associated_movement_value: None,
+            associated_money_value: None,
+            delete: false,
+        }
+    }
+
+    #[test]
+    fn movement_to_a_neighbour_is_valid() {
+        let game = game();
+
+        assert!(GameRuleChecker::new().is_input_valid(&game, &movement(0, 1)).is_none());
+    }
+
+    #[test]
+    fn movement_without_moves_left_is_not_valid() {
+        let mut game = game();
+        game.players[1].vehicles[0].remaining_moves = 0;
+
+        let error = GameRuleChecker::new().is_input_valid(&game, &movement(0, 1));
+
+        assert!(matches!(error, Some(GameError::InvalidInput(InvalidInputReason::NotEnoughMoves, _))));
+    }
+
+    #[test]
+    fn movement_into_a_toll_the_player_can_not_afford_is_not_valid() {
+        let mut game = game();
+        game.players[1].money = 2;
+        game.district_modifiers.push(DistrictModifier {
+            district: District::Suburbs,
+            modifier: DistrictModifierType::Toll,
+            vehicle_type: None,
+            associated_movement_value: None,
+            associated_money_value: Some(3),
+            delete: false,
+        });
+
+        let error = GameRuleChecker::new().is_input_valid(&game, &movement(0, 1));
+
+        assert!(matches!(error, Some(GameError::InvalidInput(InvalidInputReason::NotEnoughMoney, _))));
+    }
+
+    #[test]
+    fn access_modifier_is_checked_against_the_moving_vehicle() {
+        let mut game = game();
+        game.players[1].vehicles[0].position_node_id = Some(1);
+        game.district_modifiers.push(access_modifier(District::CityCentre, RestrictionType::Emergency));
+        let rule_checker = GameRuleChecker::new();
+
+        let car_error = rule_checker.is_input_valid(&game, &movement(0, 2));
+        let emergency_vehicle_error = rule_checker.is_input_valid(&game, &movement(1, 2));
+
+        assert!(matches!(car_error, Some(GameError::InvalidInput(InvalidInputReason::MovementNotAllowed, _))));
+        assert!(emergency_vehicle_error.is_none());
+    }
+
+    #[test]
+    fn destination_access_lets_in_the_players_with_an_objective_in_the_district() {
+        let mut game = game();
+        game.district_modifiers.push(access_modifier(District::Suburbs, RestrictionType::Destination));
+
+        assert!(GameRuleChecker::new().is_input_valid(&game, &movement(0, 1)).is_none());
+    }
+
+    #[test]
+    fn every_broken_rule_is_returned() {
+        let mut game = game();
+        game.current_players_turn = InGameID::Orchestrator;
+
+        let violations = GameRuleChecker::new().get_rule_violations(&game, &movement(0, 2));
+
+        let rules: Vec<&str> = violations.iter().map(|violation| violation.rule.as_str()).collect();
+        assert!(rules.contains(&"players_turn"));
+        assert!(rules.contains(&"next_to_node"));
+    }
+
+    #[test]
+    fn disabled_rule_is_not_checked() {
+        let mut game = game();
+        game.players[1].vehicles[0].remaining_moves = 0;
+        game.rule_set.disabled_rules = vec!["enough_moves".to_string()];
+
+        assert!(GameRuleChecker::new().is_input_valid(&game, &movement(0, 1)).is_none());
+    }
+
+    #[test]
+    fn movement_path_is_checked_step_by_step() {
+        let mut game = game();
+        game.players[1].vehicles[0].remaining_moves = 1;
+        let mut path_movement = movement(0, 0);
+        path_movement.related_node_id = None;
+        path_movement.related_path = Some(vec![1, 2]);
+
+        let violations = GameRuleChecker::new().get_rule_violations(&game, &path_movement);
+
+        assert_eq!(violations.iter().map(|violation| violation.rule.as_str()).collect::<Vec<_>>(), vec!["enough_moves"]);
+        assert!(violations[0].message.contains("node 2"));
+    }
+}
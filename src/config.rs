@@ -0,0 +1,373 @@
+//! Contains the configuration of the server.
+//!
+//! The configuration is put together in layers, where every layer overrides the values set by the layers before it:
+//! 1. The default values.
+//! 2. A JSON config file. The file is given with `--config <path>` or the `BOARDGAME_CONFIG` environment variable, and otherwise `config.json` next to the server binary is used if it exists.
+//! 3. Environment variables, named `BOARDGAME_` followed by the name of the setting in upper case, e.g. `BOARDGAME_PORT`.
+//! 4. Command line flags, named `--` followed by the name of the setting with `-` instead of `_`, e.g. `--port 5000`.
+
+use std::{env, fs, path::{Path, PathBuf}, time::Duration};
+
+use game_core::game_data::{constants::{PLAYER_TIMEOUT, DISCONNECT_GRACE_PERIOD}, structs::game_settings::{GameSettings, GameSettingsChanges}};
+use logging::logger::LogLevel;
+use serde::Deserialize;
+
+const DEFAULT_SERVER_IP: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 5000;
+const CONFIG_FILE_NAME: &str = "config.json";
+const CONFIG_FLAG: &str = "--config";
+const CONFIG_ENV_VAR: &str = "BOARDGAME_CONFIG";
+const ENV_VAR_PREFIX: &str = "BOARDGAME_";
+
+/// The names of every setting that can be set with an environment variable or a command line flag.
+const SETTING_NAMES: [&str; 17] = [
+    "ip",
+    "port",
+    "print_log_threshold",
+    "store_log_threshold",
+    "log_folder",
+    "player_timeout_seconds",
+    "disconnect_grace_period_seconds",
+    "max_player_count",
+    "max_toll_modifier_count",
+    "max_access_modifier_count",
+    "max_priority_modifier_count",
+    "start_movement_amount",
+    "start_money_amount",
+    "max_round_count",
+    "turn_time_limit_seconds",
+    "reveal_objective_cards",
+    "vehicles_per_player",
+];
+
+/// The ServerConfig struct contains everything about the server that can be configured without recompiling it.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// The IP address the server binds to.
+    pub ip: String,
+    pub port: u16,
+    /// The lowest log level that is printed.
+    pub print_log_threshold: LogLevel,
+    /// The lowest log level that is stored in the log files.
+    pub store_log_threshold: LogLevel,
+    /// The folder the log files are stored in. The `BoardGameServerLogs` folder next to the server binary is used if no folder is given.
+    pub log_folder: Option<PathBuf>,
+    /// How long a player can go without checking in before they are disconnected.
+    pub player_timeout: Duration,
+    /// How long a disconnected player keeps their seat before they are removed.
+    pub disconnect_grace_period: Duration,
+    /// The game balance values used in games that are not given their own settings when they are created.
+    pub game_settings: GameSettings,
+}
+
+/// The ConfigFile struct describes the config file. Every value is optional, and the values that are left out are not changed.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    ip: Option<String>,
+    port: Option<u16>,
+    print_log_threshold: Option<String>,
+    store_log_threshold: Option<String>,
+    log_folder: Option<PathBuf>,
+    player_timeout_seconds: Option<u64>,
+    disconnect_grace_period_seconds: Option<u64>,
+    game_settings: GameSettingsChanges,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            ip: DEFAULT_SERVER_IP.to_string(),
+            port: DEFAULT_PORT,
+            print_log_threshold: LogLevel::Ignore,
+            store_log_threshold: LogLevel::Ignore,
+            log_folder: None,
+            player_timeout: PLAYER_TIMEOUT,
+            disconnect_grace_period: DISCONNECT_GRACE_PERIOD,
+            game_settings: GameSettings::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Loads the config from the config file, the environment variables and the command line flags given to the server. Will return an error if any of them has a value that is not valid.
+    pub fn load(executable_folder: &Path) -> Result<Self, String> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Self::load_from(&args, |name| env::var(name).ok(), executable_folder)
+    }
+
+    /// Loads the config from the given command line flags and the environment variables found with `env_var`.
+    fn load_from(args: &[String], env_var: impl Fn(&str) -> Option<String>, executable_folder: &Path) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        let config_file_path = match flag_value(args, CONFIG_FLAG)? {
+            Some(path) => Some(PathBuf::from(path)),
+            None => env_var(CONFIG_ENV_VAR).map(PathBuf::from),
+        };
+        match config_file_path {
+            Some(path) => config.apply_file(&path)?,
+            None => {
+                let default_path = executable_folder.join(CONFIG_FILE_NAME);
+                if default_path.exists() {
+                    config.apply_file(&default_path)?;
+                }
+            }
+        }
+
+        for name in SETTING_NAMES {
+            if let Some(value) = env_var(&format!("{}{}", ENV_VAR_PREFIX, name.to_uppercase())) {
+                config.set(name, &value)?;
+            }
+        }
+
+        config.apply_args(args)?;
+        config.game_settings.validate().map_err(|e| format!("The game settings are not valid because: {}", e))?;
+        Ok(config)
+    }
+
+    fn apply_file(&mut self, path: &Path) -> Result<(), String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read the config file {} because: {}", path.display(), e))?;
+        let file: ConfigFile = serde_json::from_str(&contents).map_err(|e| format!("Failed to parse the config file {} because: {}", path.display(), e))?;
+        if let Some(ip) = file.ip {
+            self.ip = ip;
+        }
+        if let Some(port) = file.port {
+            self.port = port;
+        }
+        if let Some(level) = file.print_log_threshold {
+            self.set("print_log_threshold", &level)?;
+        }
+        if let Some(level) = file.store_log_threshold {
+            self.set("store_log_threshold", &level)?;
+        }
+        if let Some(log_folder) = file.log_folder {
+            self.log_folder = Some(log_folder);
+        }
+        if let Some(seconds) = file.player_timeout_seconds {
+            self.player_timeout = Duration::from_secs(seconds);
+        }
+        if let Some(seconds) = file.disconnect_grace_period_seconds {
+            self.disconnect_grace_period = Duration::from_secs(seconds);
+        }
+        self.game_settings = file.game_settings.apply_to(self.game_settings);
+        Ok(())
+    }
+
+    fn apply_args(&mut self, args: &[String]) -> Result<(), String> {
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let Some(value) = args.next() else {
+                return Err(format!("The flag {} is missing a value!", flag));
+            };
+            if flag == CONFIG_FLAG {
+                continue;
+            }
+            let name = flag.trim_start_matches("--").replace('-', "_");
+            if !flag.starts_with("--") || !SETTING_NAMES.contains(&name.as_str()) {
+                return Err(format!("{} is not a known flag!", flag));
+            }
+            self.set(&name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the setting with the given name to the given value.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let settings = &mut self.game_settings;
+        match name {
+            "ip" => self.ip = value.to_string(),
+            "port" => self.port = parse(name, value)?,
+            "print_log_threshold" => self.print_log_threshold = parse(name, value)?,
+            "store_log_threshold" => self.store_log_threshold = parse(name, value)?,
+            "log_folder" => self.log_folder = Some(PathBuf::from(value)),
+            "player_timeout_seconds" => self.player_timeout = Duration::from_secs(parse(name, value)?),
+            "disconnect_grace_period_seconds" => self.disconnect_grace_period = Duration::from_secs(parse(name, value)?),
+            "max_player_count" => settings.max_player_count = parse(name, value)?,
+            "max_toll_modifier_count" => settings.max_toll_modifier_count = parse(name, value)?,
+            "max_access_modifier_count" => settings.max_access_modifier_count = parse(name, value)?,
+            "max_priority_modifier_count" => settings.max_priority_modifier_count = parse(name, value)?,
+            "start_movement_amount" => settings.start_movement_amount = parse(name, value)?,
+            "start_money_amount" => settings.start_money_amount = parse(name, value)?,
+            "max_round_count" => settings.max_round_count = match parse(name, value)? {
+                0 => None,
+                rounds => Some(rounds),
+            },
+            "turn_time_limit_seconds" => settings.turn_time_limit_seconds = Some(parse(name, value)?).filter(|seconds| *seconds > 0),
+            "reveal_objective_cards" => settings.reveal_objective_cards = parse(name, value)?,
+            "vehicles_per_player" => settings.vehicles_per_player = parse(name, value)?,
+            _ => return Err(format!("There is no setting with the name {}!", name)),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the value after the given flag, if the flag is given.
+fn flag_value(args: &[String], flag: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => match args.get(index + 1) {
+            Some(value) => Ok(Some(value.clone())),
+            None => Err(format!("The flag {} is missing a value!", flag)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} is not a valid value for {}!", value, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, process};
+
+    use super::*;
+
+    /// A folder for the config files of a test, which is removed again when it is dropped.
+    struct TemporaryFolder {
+        path: PathBuf,
+    }
+
+    impl TemporaryFolder {
+        fn new(test_name: &str) -> Self {
+            let path = env::temp_dir().join(format!("server_config_{}_{}", test_name, process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self { path }
+        }
+
+        fn write(&self, file_name: &str, contents: &str) -> PathBuf {
+            let path = self.path.join(file_name);
+            fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TemporaryFolder {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn load(args: &[String], env_vars: &[(&str, &str)], executable_folder: &Path) -> Result<ServerConfig, String> {
+        let env_vars: HashMap<String, String> = env_vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        ServerConfig::load_from(args, |name| env_vars.get(name).cloned(), executable_folder)
+    }
+
+    #[test]
+    fn defaults_are_used_when_nothing_is_configured() {
+        let folder = TemporaryFolder::new("defaults");
+
+        let config = load(&[], &[], &folder.path).unwrap();
+
+        assert_eq!(config.ip, DEFAULT_SERVER_IP);
+        assert_eq!(config.port, DEFAULT_PORT);
+        assert_eq!(config.player_timeout, PLAYER_TIMEOUT);
+        assert_eq!(config.disconnect_grace_period, DISCONNECT_GRACE_PERIOD);
+        assert_eq!(config.game_settings, GameSettings::default());
+    }
+
+    #[test]
+    fn every_layer_overrides_the_layers_before_it() {
+        let folder = TemporaryFolder::new("layers");
+        folder.write(CONFIG_FILE_NAME, r#"{ "ip": "0.0.0.0", "port": 6000, "player_timeout_seconds": 30, "game_settings": { "max_round_count": 10 } }"#);
+        let env_vars = [("BOARDGAME_PORT", "7000"), ("BOARDGAME_PLAYER_TIMEOUT_SECONDS", "40")];
+
+        let config = load(&args(&["--player-timeout-seconds", "50"]), &env_vars, &folder.path).unwrap();
+
+        assert_eq!(config.ip, "0.0.0.0");
+        assert_eq!(config.port, 7000);
+        assert_eq!(config.player_timeout, Duration::from_secs(50));
+        assert_eq!(config.game_settings.max_round_count, Some(10));
+        assert_eq!(config.game_settings.start_money_amount, GameSettings::default().start_money_amount);
+    }
+
+    #[test]
+    fn the_config_flag_is_used_before_the_config_environment_variable() {
+        let folder = TemporaryFolder::new("config_flag");
+        let flag_file = folder.write("flag.json", r#"{ "port": 6000 }"#);
+        let env_file = folder.write("env.json", r#"{ "port": 7000 }"#);
+        let env_vars = [(CONFIG_ENV_VAR, env_file.to_str().unwrap())];
+
+        let with_flag = load(&args(&["--config", flag_file.to_str().unwrap()]), &env_vars, &folder.path).unwrap();
+        let without_flag = load(&[], &env_vars, &folder.path).unwrap();
+
+        assert_eq!(with_flag.port, 6000);
+        assert_eq!(without_flag.port, 7000);
+    }
+
+    #[test]
+    fn a_given_config_file_that_is_missing_is_an_error() {
+        let folder = TemporaryFolder::new("missing_file");
+        let missing_file = folder.path.join("missing.json");
+
+        let result = load(&args(&["--config", missing_file.to_str().unwrap()]), &[], &folder.path);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unknown_fields_in_the_config_file_are_an_error() {
+        let folder = TemporaryFolder::new("unknown_field");
+        folder.write(CONFIG_FILE_NAME, r#"{ "prot": 6000 }"#);
+
+        let result = load(&[], &[], &folder.path);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn invalid_flags_and_values_are_errors() {
+        let folder = TemporaryFolder::new("invalid_flags");
+
+        let unknown_flag = load(&args(&["--colour", "blue"]), &[], &folder.path);
+        let missing_value = load(&args(&["--port"]), &[], &folder.path);
+        let invalid_value = load(&[], &[("BOARDGAME_PORT", "not a port")], &folder.path);
+
+        assert!(unknown_flag.unwrap_err().contains("--colour"));
+        assert!(missing_value.unwrap_err().contains("--port"));
+        assert!(invalid_value.unwrap_err().contains("port"));
+    }
+
+    #[test]
+    fn invalid_game_settings_are_an_error() {
+        let folder = TemporaryFolder::new("invalid_settings");
+
+        let result = load(&args(&["--max-player-count", "1"]), &[], &folder.path);
+
+        assert!(result.unwrap_err().contains("game settings"));
+    }
+
+    #[test]
+    fn a_max_round_count_of_zero_removes_the_round_limit() {
+        let folder = TemporaryFolder::new("max_round_count");
+
+        let config = load(&args(&["--max-round-count", "0"]), &[("BOARDGAME_MAX_ROUND_COUNT", "10")], &folder.path).unwrap();
+
+        assert_eq!(config.game_settings.max_round_count, None);
+    }
+
+    #[test]
+    fn a_turn_time_limit_of_zero_seconds_turns_the_timer_off() {
+        let folder = TemporaryFolder::new("turn_time_limit");
+
+        let config = load(&args(&["--turn-time-limit-seconds", "0"]), &[("BOARDGAME_TURN_TIME_LIMIT_SECONDS", "60")], &folder.path).unwrap();
+
+        assert_eq!(config.game_settings.turn_time_limit_seconds, None);
+    }
+
+    #[test]
+    fn disconnect_grace_period_is_read_from_the_config_file_and_the_environment() {
+        let folder = TemporaryFolder::new("disconnect_grace_period");
+        folder.write(CONFIG_FILE_NAME, r#"{ "disconnect_grace_period_seconds": 30 }"#);
+
+        let from_file = load(&[], &[], &folder.path).unwrap();
+        let from_env = load(&[], &[("BOARDGAME_DISCONNECT_GRACE_PERIOD_SECONDS", "60")], &folder.path).unwrap();
+
+        assert_eq!(from_file.disconnect_grace_period, Duration::from_secs(30));
+        assert_eq!(from_env.disconnect_grace_period, Duration::from_secs(60));
+    }
+}
@@ -1,10 +1,9 @@
 //! This is the main file of the server. It contains the main function and the server endpoints.
 //! 
 //! This server uses the actix-web framework to handle requests. The server is configured to allow any origin, method and header. This is needed to allow the client (Unity) to connect to the server.
-//! The [`AppData`] struct contains the game controller that actually handles the game logic. The game controller is wrapped in a [`Arc`] and a [`Mutex`] to allow multiple threads to access it.
+//! The [`AppData`] struct contains the game controller that actually handles the game logic. The game controller locks every game on its own, so it is shared between the threads without a [`Mutex`] around it and requests for different games can be handled at the same time.
 //! 
 //! [`AppData`]: struct.AppData.html
-//! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
 //! [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
 //! 
 //! # Main libraries used
@@ -14,16 +13,28 @@
 
 #![allow(unknown_lints, clippy::significant_drop_tightening)]
 
+/// The config module contains the ServerConfig struct which is loaded from the config file, the environment variables and the command line flags.
+mod config;
+
 use actix_cors::Cors;
-use game_core::{game_controller::GameController, game_data::structs::{new_game_info::NewGameInfo, player::Player, player_input::PlayerInput, gamestate::GameState}, situation_card_list::situation_card_list_wrapper};
+use game_core::{bots::BotStrategy, game_controller::GameController, game_data::{structs::{new_game_info::NewGameInfo, player::Player, player_input::PlayerInput, gamestate::GameState, situation_card_definition::SituationCardDefinition, spectator::Spectator}, custom_types::{GameID, PlayerID, VehicleID}, enums::game_error::GameError}, situation_card_list::{situation_card_list_wrapper, load_situation_cards}, map_list::{load_maps, map_names}};
 use serde::{Serialize, Deserialize};
 use rules::game_rule_checker::GameRuleChecker;
-use std::sync::{Arc, Mutex, RwLock};
-use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder};
-use logging::{logger::LogLevel, threshold_logger::ThresholdLogger};
+use storage::file_game_storage::FileGameStorage;
+use std::{env, path::PathBuf, sync::{Arc, Mutex, RwLock}, time::{Duration, Instant}};
+use actix_web::{get, post, http::header, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use actix_ws::{Message, Session};
+use logging::threshold_logger::ThresholdLogger;
 use serde_json::json;
+use config::ServerConfig;
 
-const SERVER_IP: &str = "127.0.0.1";
+const GAME_STORAGE_FOLDER_NAME: &str = "BoardGameServerGames";
+const SITUATION_CARDS_FOLDER_NAME: &str = "SituationCards";
+const MAPS_FOLDER_NAME: &str = "Maps";
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+const BOT_TURN_INTERVAL: Duration = Duration::from_secs(1);
+const TURN_TIMER_INTERVAL: Duration = Duration::from_secs(1);
+const WEBSOCKET_TOKEN_PROTOCOL: &str = "bearer";
 
 // ==================== Macros ====================
 
@@ -43,11 +54,25 @@ macro_rules! server_app_with_data {
                 .service(create_new_game)
                 .service(get_amount_of_created_player_ids)
                 .service(get_gamestate)
+                .service(get_game_results)
+                .service(get_action_log)
+                .service(get_replayed_gamestate)
+                .service(get_reachable_nodes)
+                .service(get_cheapest_route)
+                .service(get_orchestrator_advice)
                 .service(handle_player_input)
+                .service(validate_player_input)
+                .service(upload_situation_card)
+                .service(add_bot)
                 .service(get_lobbies)
                 .service(join_game)
+                .service(spectate_game)
                 .service(get_situation_cards)
+                .service(get_maps)
+                .service(get_rules)
                 .service(player_check_in)
+                .service(reconnect_to_game)
+                .service(subscribe_to_game)
         }
     }
 }
@@ -56,22 +81,72 @@ macro_rules! server_app_with_data {
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let logger = Arc::new(RwLock::new(ThresholdLogger::new(
-        LogLevel::Ignore,
-        LogLevel::Ignore,
-    )));
+    let config = match ServerConfig::load(&executable_folder()?) {
+        Ok(config) => config,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)),
+    };
+    let logger = Arc::new(RwLock::new(match &config.log_folder {
+        Some(log_folder) => ThresholdLogger::with_log_folder(config.print_log_threshold, config.store_log_threshold, log_folder.clone()),
+        None => ThresholdLogger::new(config.print_log_threshold, config.store_log_threshold),
+    }));
+    // The server should not start with maps or situation cards that can not be used, so the error is returned and the server stops.
+    // The maps are loaded first, since the situation cards are validated against the default map.
+    if let Err(e) = load_maps(&executable_folder()?.join(MAPS_FOLDER_NAME)) {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, e));
+    }
+    let situation_cards_folder = executable_folder()?.join(SITUATION_CARDS_FOLDER_NAME);
+    if let Err(e) = load_situation_cards(&situation_cards_folder) {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, e));
+    }
+    let storage = match FileGameStorage::new_in_executable_folder(GAME_STORAGE_FOLDER_NAME) {
+        Ok(s) => s,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+    };
+    let game_controller = GameController::with_settings(logger.clone(), Box::new(GameRuleChecker::new()), Box::new(storage), config.game_settings, config.player_timeout, config.disconnect_grace_period);
+    // Any errors while restoring are logged by the game controller, and the server can still run without the stored games.
+    let _ = game_controller.restore_stored_games();
     let app_data = web::Data::new(AppData {
-        game_controller: Mutex::new(GameController::new(logger.clone(), Box::new(GameRuleChecker::new()))),
+        game_controller,
+        game_subscribers: Mutex::new(Vec::new()),
+    });
+
+    let bot_data = app_data.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(BOT_TURN_INTERVAL);
+        loop {
+            interval.tick().await;
+            // If the bots could not be played, they play on the next tick instead.
+            let _ = run_on_game_controller(&bot_data, |game_controller| game_controller.play_bot_turns()).await;
+            push_updated_games(&bot_data).await;
+        }
+    });
+
+    let turn_timer_data = app_data.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(TURN_TIMER_INTERVAL);
+        loop {
+            interval.tick().await;
+            // If the turns could not be checked, they are checked on the next tick instead.
+            let _ = run_on_game_controller(&turn_timer_data, |game_controller| game_controller.end_expired_turns()).await;
+            push_updated_games(&turn_timer_data).await;
+        }
     });
 
     HttpServer::new(move || {
         server_app_with_data!(app_data)
     })
-    .bind((SERVER_IP, 5000))?
+    .bind((config.ip.as_str(), config.port))?
     .run()
     .await
 }
 
+fn executable_folder() -> std::io::Result<PathBuf> {
+    match env::current_exe()?.parent() {
+        Some(folder) => Ok(folder.to_path_buf()),
+        None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Failed to get path of the folder the executable is in.")),
+    }
+}
+
 // ==================== Some server used structs ====================
 
 #[derive(Serialize, Deserialize)]
@@ -79,126 +154,465 @@ struct LobbyList {
     lobbies: Vec<GameState>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct MapList {
+    maps: Vec<String>,
+}
+
+/// The body of every error response. The code is machine readable and can be used by the client to show the error in the language of the player, while the message describes the error in English.
+#[derive(Serialize, Deserialize)]
+struct ErrorResponse {
+    code: String,
+    message: String,
+}
+
+/// The query of the subscribe endpoint. Browsers can not set the `Authorization` header when opening a WebSocket, so the token of the player can be given here instead.
+#[derive(Serialize, Deserialize)]
+struct SubscriptionQuery {
+    token: Option<String>,
+}
+
+/// The body of the reconnect endpoint, with the reconnect code the player got together with their first token.
+#[derive(Serialize, Deserialize)]
+struct ReconnectInfo {
+    reconnect_code: String,
+}
+
+/// The query of the endpoints that return a game state, telling which player or spectator the game state is shown to. See [`GameState::view_for`].
+#[derive(Serialize, Deserialize)]
+struct ViewerQuery {
+    player_id: Option<PlayerID>,
+}
+
+/// The query of the route endpoints, telling which vehicle of the player the routes are found for. The first vehicle is used if it's not given.
+#[derive(Serialize, Deserialize)]
+struct VehicleQuery {
+    #[serde(default)]
+    vehicle_id: VehicleID,
+}
+
 struct AppData {
-    game_controller: Mutex<GameController>,
+    game_controller: GameController,
+    game_subscribers: Mutex<Vec<GameSubscriber>>,
+}
+
+/// A player that follows a game through a WebSocket connection and is sent the game state every time the game is updated.
+struct GameSubscriber {
+    game_id: GameID,
+    player_id: PlayerID,
+    session: Session,
+}
+
+// ==================== Game controller access ====================
+
+/// Runs the given function on the game controller on the thread pool for blocking work, since the game controller writes the games it changes to the game storage.
+async fn run_on_game_controller<T, F>(shared_data: &web::Data<AppData>, function: F) -> Result<T, GameError>
+where
+    T: Send + 'static,
+    F: FnOnce(&GameController) -> T + Send + 'static,
+{
+    let shared_data = shared_data.clone();
+    match web::block(move || function(&shared_data.game_controller)).await {
+        Ok(result) => Ok(result),
+        Err(e) => Err(GameError::Internal(format!("Could not run the game controller because: {e}"))),
+    }
+}
+
+// ==================== Game update pushing ====================
+
+/// Sends the new state of every updated game to the players subscribed to it. Subscribers that are closed or no longer in the game are removed.
+async fn push_updated_games(shared_data: &web::Data<AppData>) {
+    let updated_games = run_on_game_controller(shared_data, |game_controller| {
+        game_controller
+            .take_updated_game_ids()
+            .into_iter()
+            .filter_map(|game_id| game_controller.get_game_by_id(game_id).ok())
+            .collect::<Vec<GameState>>()
+    })
+    .await;
+    let Ok(updated_games) = updated_games else {
+        return;
+    };
+
+    for game in updated_games {
+        let subscribers: Vec<(PlayerID, Session)> = {
+            let Ok(mut game_subscribers) = shared_data.game_subscribers.lock() else {
+                return;
+            };
+            game_subscribers.retain(|s| s.game_id != game.id || game.can_be_followed_by(s.player_id));
+            game_subscribers
+                .iter()
+                .filter(|s| s.game_id == game.id)
+                .map(|s| (s.player_id, s.session.clone()))
+                .collect()
+        };
+        for (player_id, mut session) in subscribers {
+            let game_json = json!(game.clone().view_for(Some(player_id))).to_string();
+            if session.text(game_json).await.is_err() {
+                remove_game_subscriber(shared_data, game.id, player_id);
+            }
+        }
+    }
+}
+
+fn remove_game_subscriber(shared_data: &web::Data<AppData>, game_id: GameID, player_id: PlayerID) {
+    if let Ok(mut game_subscribers) = shared_data.game_subscribers.lock() {
+        game_subscribers.retain(|s| s.game_id != game_id || s.player_id != player_id);
+    }
+}
+
+/// Sends the current game state to the new subscriber, checks in the player when they send something over the WebSocket, and closes the connection if the player has not answered the heartbeat pings within the player timeout of the game controller.
+/// 
+/// The token the subscription was opened with is checked before every check in, so the connection is closed if it's not the token of the player anymore, e.g. because the player reconnected and got a new token.
+async fn handle_game_subscription(shared_data: web::Data<AppData>, game_id: GameID, player_id: PlayerID, token: Option<String>, mut session: Session, mut message_stream: actix_ws::MessageStream) {
+    let current_game = run_on_game_controller(&shared_data, move |game_controller| game_controller.get_game_by_id(game_id)).await;
+    if let Ok(Ok(game)) = current_game {
+        if session.text(json!(game.view_for(Some(player_id))).to_string()).await.is_err() {
+            remove_game_subscriber(&shared_data, game_id, player_id);
+            return;
+        }
+    }
+
+    let last_heartbeat = Arc::new(Mutex::new(Instant::now()));
+
+    let heartbeat_data = shared_data.clone();
+    let heartbeat_time = last_heartbeat.clone();
+    let mut heartbeat_session = session.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+            let is_timed_out = heartbeat_time.lock().map_or(true, |time| time.elapsed() > heartbeat_data.game_controller.player_timeout);
+            if is_timed_out || heartbeat_session.ping(b"").await.is_err() {
+                remove_game_subscriber(&heartbeat_data, game_id, player_id);
+                let _ = heartbeat_session.close(None).await;
+                return;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = message_stream.recv().await {
+        if let Message::Close(_) = message {
+            break;
+        }
+        if let Message::Ping(bytes) = message {
+            if session.pong(&bytes).await.is_err() {
+                break;
+            }
+        }
+        if shared_data.game_controller.authenticate(player_id, token.as_deref()).is_err() {
+            break;
+        }
+        if let Ok(mut time) = last_heartbeat.lock() {
+            *time = Instant::now();
+        }
+        let check_in_result = run_on_game_controller(&shared_data, move |game_controller| game_controller.update_check_in_and_remove_inactive(player_id)).await;
+        if !matches!(check_in_result, Ok(Ok(_))) {
+            break;
+        }
+        push_updated_games(&shared_data).await;
+    }
+
+    remove_game_subscriber(&shared_data, game_id, player_id);
+    let _ = session.close(None).await;
+}
+
+// ==================== Error responses ====================
+
+/// Creates the response for an error, with a status code that fits the kind of error and an [`ErrorResponse`] as the body. The context tells what failed and is put in front of the message of the error.
+fn error_response(context: &str, error: GameError) -> HttpResponse {
+    let mut response = match error {
+        GameError::NotFound(_) => HttpResponse::NotFound(),
+        GameError::Unauthorized(_) => HttpResponse::Unauthorized(),
+        GameError::Forbidden(_) | GameError::NotYourTurn(_) => HttpResponse::Forbidden(),
+        GameError::InvalidInput(_, _) => HttpResponse::BadRequest(),
+        GameError::Conflict(_) => HttpResponse::Conflict(),
+        GameError::Internal(_) => HttpResponse::InternalServerError(),
+    };
+    response.json(ErrorResponse {
+        code: error.code(),
+        message: format!("{} because: {}", context, error),
+    })
+}
+
+/// Returns the token in the `Authorization: Bearer <token>` header of the request, if it has one.
+fn request_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+}
+
+/// Returns the token a WebSocket subscription is opened with, and if it was given as a subprotocol. The token is taken from the `Authorization` header, the `token` query or the `Sec-WebSocket-Protocol: bearer, <token>` header, in that order.
+fn subscription_token(req: &HttpRequest, query: &SubscriptionQuery) -> (Option<String>, bool) {
+    if let Some(token) = request_token(req).or_else(|| query.token.clone()) {
+        return (Some(token), false);
+    }
+    let protocols: Vec<String> = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(',').map(|protocol| protocol.trim().to_string()).collect())
+        .unwrap_or_default();
+    match protocols.as_slice() {
+        [bearer, token, ..] if bearer == WEBSOCKET_TOKEN_PROTOCOL => (Some(token.clone()), true),
+        _ => (None, false),
+    }
+}
+
+/// Checks that the request has the token of the player it acts for in its `Authorization: Bearer <token>` header.
+fn authenticate(req: &HttpRequest, shared_data: &web::Data<AppData>, player_id: PlayerID) -> Result<(), GameError> {
+    shared_data.game_controller.authenticate(player_id, request_token(req).as_deref())
+}
+
+/// Returns the id of the player the game state is shown to, if a player id is given in the query. Will return an error if the request does not have the token of that player.
+fn authenticate_viewer(req: &HttpRequest, shared_data: &web::Data<AppData>, viewer: &ViewerQuery) -> Result<Option<PlayerID>, GameError> {
+    match viewer.player_id {
+        Some(player_id) => authenticate(req, shared_data, player_id).map(|_| Some(player_id)),
+        None => Ok(None),
+    }
 }
 
 // ==================== Server endpoints ====================
 
 #[get("/create/playerID")]
 async fn get_unique_id(shared_data: web::Data<AppData>) -> impl Responder {
-    let data = shared_data.game_controller.lock();
-    match data {
-        Ok(mut game_controller) => {
-            let player_result = game_controller.generate_player_id();
-            match player_result {
-                Ok(id) => HttpResponse::Ok().body(id.to_string()),
-                Err(e) => HttpResponse::InternalServerError()
-                    .body(format!("Failed to make player ID because: {e}")),
-            }
-        }
-        Err(e) => HttpResponse::InternalServerError()
-            .body(format!("Failed to make player ID because: {e}")),
+    let credentials_result = shared_data.game_controller.generate_player_credentials();
+    match credentials_result {
+        Ok(credentials) => HttpResponse::Ok().json(json!(credentials)),
+        Err(e) => error_response("Failed to make player ID", e),
     }
 }
 
 #[post("/create/game")]
 async fn create_new_game(
+    req: HttpRequest,
     json_data: web::Json<NewGameInfo>,
     shared_data: web::Data<AppData>,
 ) -> impl Responder {
     let lobby_info = json_data.into_inner();
-    let data = shared_data.game_controller.lock();
-    match data {
-        Ok(mut game_controller) => {
-            let game_result = game_controller.create_new_game(lobby_info);
-            match game_result {
-                Ok(g) => HttpResponse::Ok().json(json!(g)),
-                Err(e) => HttpResponse::InternalServerError()
-                    .body(format!("Failed to create game because: {e}")),
-            }
-        }
-        Err(e) => {
-            HttpResponse::InternalServerError().body(format!("Failed to create game because {e}"))
-        }
+    let host_id = lobby_info.host.unique_id;
+    if let Err(e) = authenticate(&req, &shared_data, host_id) {
+        return error_response("Failed to create game", e);
+    }
+    let game_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.create_new_game(lobby_info)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to create game", e),
+    };
+    push_updated_games(&shared_data).await;
+    match game_result {
+        Ok(g) => HttpResponse::Ok().json(json!(g.view_for(Some(host_id)))),
+        Err(e) => error_response("Failed to create game", e),
     }
 }
 
 #[get("/debug/playerIDs/amount")]
 async fn get_amount_of_created_player_ids(shared_data: web::Data<AppData>) -> impl Responder {
-    let game_controller = match shared_data.game_controller.lock() {
-        Ok(controller) => controller, 
-        Err(_) => return HttpResponse::InternalServerError().body("Failed to get amount of player IDs because could not lock game controller".to_string()),
-        };
     HttpResponse::Ok().body(
-        game_controller
+        shared_data.game_controller
             .get_amount_of_created_player_ids()
             .to_string(),
     )
 }
 
 #[get("/games/game/{id}")]
-async fn get_gamestate(id: web::Path<i32>, shared_data: web::Data<AppData>) -> impl Responder {
-    
-    let mut game_controller = match shared_data.game_controller.lock() { 
-        Ok(controller) => controller,
-        Err(_) => return HttpResponse::InternalServerError().body("Failed to get amount of player IDs because could not lock game controller".to_string()),
+async fn get_gamestate(req: HttpRequest, id: web::Path<i32>, viewer: web::Query<ViewerQuery>, shared_data: web::Data<AppData>) -> impl Responder {
+    let viewer_id = match authenticate_viewer(&req, &shared_data, &viewer) {
+        Ok(viewer_id) => viewer_id,
+        Err(e) => return error_response("Could not return the game", e),
     };
-
-    let game_result = game_controller.get_game_by_id(*id);
+    let game_result = shared_data.game_controller.get_game_by_id(*id);
     match game_result {
-        Ok(game) => HttpResponse::Ok().json(json!(game)),
-        Err(e) => HttpResponse::InternalServerError().body(format!("Could not return the game because: {}", e)),
+        Ok(game) => HttpResponse::Ok().json(json!(game.view_for(viewer_id))),
+        Err(e) => error_response("Could not return the game", e),
     }
 }
 
-#[post("/games/join/{game_id}")]
-async fn join_game(game_id: web::Path<i32>, player: web::Json<Player>, shared_data: web::Data<AppData>) -> impl Responder {
-    let mut game_controller = match shared_data.game_controller.lock() { 
-        Ok(controller) => controller,
-        Err(_) => return HttpResponse::InternalServerError().body("Failed to get amount of player IDs because could not lock game controller".to_string()),
+#[get("/games/game/{id}/results")]
+async fn get_game_results(id: web::Path<i32>, shared_data: web::Data<AppData>) -> impl Responder {
+    match shared_data.game_controller.get_game_results(*id) {
+        Ok(results) => HttpResponse::Ok().json(json!(results)),
+        Err(e) => error_response("Could not return the game results", e),
+    }
+}
+
+#[get("/games/game/{id}/log")]
+async fn get_action_log(id: web::Path<i32>, shared_data: web::Data<AppData>) -> impl Responder {
+    match shared_data.game_controller.get_action_log(*id) {
+        Ok(entries) => HttpResponse::Ok().json(json!(entries)),
+        Err(e) => error_response("Could not return the action log", e),
+    }
+}
+
+#[get("/games/game/{id}/replay/{turn}")]
+async fn get_replayed_gamestate(req: HttpRequest, path: web::Path<(i32, u32)>, viewer: web::Query<ViewerQuery>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (id, turn) = path.into_inner();
+    let viewer_id = match authenticate_viewer(&req, &shared_data, &viewer) {
+        Ok(viewer_id) => viewer_id,
+        Err(e) => return error_response("Could not replay the game", e),
     };
+    match shared_data.game_controller.get_game_state_at_turn(id, turn) {
+        Ok(game) => HttpResponse::Ok().json(json!(game.view_for(viewer_id))),
+        Err(e) => error_response("Could not replay the game", e),
+    }
+}
 
-    let join_game_result = game_controller.join_game(*game_id, player.into_inner());
+#[get("/games/game/{game_id}/reachable/{player_id}")]
+async fn get_reachable_nodes(req: HttpRequest, path: web::Path<(i32, i32)>, query: web::Query<VehicleQuery>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Could not find the reachable nodes", e);
+    }
+    match shared_data.game_controller.get_reachable_nodes(game_id, player_id, query.vehicle_id) {
+        Ok(routes) => HttpResponse::Ok().json(json!(routes)),
+        Err(e) => error_response("Could not find the reachable nodes", e),
+    }
+}
+
+#[get("/games/game/{game_id}/route/{player_id}/{node_id}")]
+async fn get_cheapest_route(req: HttpRequest, path: web::Path<(i32, i32, u8)>, query: web::Query<VehicleQuery>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id, node_id) = path.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Could not find a route", e);
+    }
+    match shared_data.game_controller.get_cheapest_route(game_id, player_id, query.vehicle_id, node_id) {
+        Ok(route) => HttpResponse::Ok().json(json!(route)),
+        Err(e) => error_response("Could not find a route", e),
+    }
+}
+
+#[get("/games/game/{game_id}/advice/{player_id}")]
+async fn get_orchestrator_advice(req: HttpRequest, path: web::Path<(i32, i32)>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Could not give advice", e);
+    }
+    match shared_data.game_controller.get_orchestrator_advice(game_id, player_id) {
+        Ok(advice) => HttpResponse::Ok().json(json!(advice)),
+        Err(e) => error_response("Could not give advice", e),
+    }
+}
+
+#[post("/games/join/{game_id}")]
+async fn join_game(req: HttpRequest, game_id: web::Path<i32>, player: web::Json<Player>, shared_data: web::Data<AppData>) -> impl Responder {
+    let game_id = game_id.into_inner();
+    let player = player.into_inner();
+    let player_id = player.unique_id;
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Failed to join game", e);
+    }
+    let join_game_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.join_game(game_id, player)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to join game", e),
+    };
+    push_updated_games(&shared_data).await;
 
     match join_game_result {
-        Ok(g) => HttpResponse::Ok().json(json!(g)),
-        Err(e) => {
-            HttpResponse::InternalServerError().body(format!("Failed to join game because {e}"))
-        }
+        Ok(g) => HttpResponse::Ok().json(json!(g.view_for(Some(player_id)))),
+        Err(e) => error_response("Failed to join game", e),
+    }
+}
+
+#[post("/games/join/{game_id}/spectator")]
+async fn spectate_game(req: HttpRequest, game_id: web::Path<i32>, spectator: web::Json<Spectator>, shared_data: web::Data<AppData>) -> impl Responder {
+    let game_id = game_id.into_inner();
+    let spectator = spectator.into_inner();
+    let spectator_id = spectator.unique_id;
+    if let Err(e) = authenticate(&req, &shared_data, spectator_id) {
+        return error_response("Failed to spectate game", e);
+    }
+    let spectate_game_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.join_game_as_spectator(game_id, spectator)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to spectate game", e),
+    };
+    push_updated_games(&shared_data).await;
+
+    match spectate_game_result {
+        Ok(g) => HttpResponse::Ok().json(json!(g.view_for(Some(spectator_id)))),
+        Err(e) => error_response("Failed to spectate game", e),
     }
 }
 
 #[post("/games/input")]
 async fn handle_player_input(
+    req: HttpRequest,
     json_data: web::Json<PlayerInput>,
     shared_data: web::Data<AppData>,
 ) -> impl Responder {
     let input = json_data.into_inner();
-    
-    let mut game_controller = match shared_data.game_controller.lock() { 
-        Ok(controller) => controller,
-        Err(_) => return HttpResponse::InternalServerError().body("Failed to get amount of player IDs because could not lock game controller".to_string()),
-    };
+    if let Err(e) = authenticate(&req, &shared_data, input.player_id) {
+        return error_response("Failed to do action", e);
+    }
 
-    let gamestate_result = game_controller.handle_player_input(input); 
+    let player_id = input.player_id;
+    let gamestate_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.handle_player_input(input)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to do action", e),
+    };
+    push_updated_games(&shared_data).await;
     match gamestate_result {
         Ok(g) => {
-            HttpResponse::Ok().json(json!(g))
+            HttpResponse::Ok().json(json!(g.view_for(Some(player_id))))
         },
-        Err(e) => {
-            HttpResponse::InternalServerError().body(format!("Failed to do action because: {e}"))
-        }
+        Err(e) => error_response("Failed to do action", e),
     }
 }
 
-#[get("/games/lobbies")]
-async fn get_lobbies(shared_data: web::Data<AppData>) -> impl Responder {
-    let Ok(game_controller) = shared_data.game_controller.lock() else {
-        return HttpResponse::InternalServerError().body("Failed to get lobbies because the server could not lock the game controller for safe use".to_string());
+#[post("/games/input/validate")]
+async fn validate_player_input(
+    req: HttpRequest,
+    json_data: web::Json<PlayerInput>,
+    shared_data: web::Data<AppData>,
+) -> impl Responder {
+    if let Err(e) = authenticate(&req, &shared_data, json_data.player_id) {
+        return error_response("Failed to validate the action", e);
+    }
+    match shared_data.game_controller.validate_player_input(json_data.into_inner()) {
+        Ok(violations) => HttpResponse::Ok().json(json!(violations)),
+        Err(e) => error_response("Failed to validate the action", e),
+    }
+}
+
+#[post("/games/game/{game_id}/situationcard/{player_id}")]
+async fn upload_situation_card(req: HttpRequest, path: web::Path<(i32, i32)>, json_data: web::Json<SituationCardDefinition>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Failed to upload the situation card", e);
+    }
+    let definition = json_data.into_inner();
+    let gamestate_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.assign_custom_situation_card(game_id, player_id, definition)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to upload the situation card", e),
+    };
+    push_updated_games(&shared_data).await;
+    match gamestate_result {
+        Ok(g) => HttpResponse::Ok().json(json!(g.view_for(Some(player_id)))),
+        Err(e) => error_response("Failed to upload the situation card", e),
+    }
+}
+
+#[post("/games/game/{game_id}/bot/{player_id}")]
+async fn add_bot(req: HttpRequest, path: web::Path<(i32, i32)>, strategy: web::Json<BotStrategy>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Failed to add the bot", e);
+    }
+    let strategy = strategy.into_inner();
+    let gamestate_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.add_bot(game_id, player_id, strategy)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to add the bot", e),
     };
+    push_updated_games(&shared_data).await;
+    match gamestate_result {
+        Ok(g) => HttpResponse::Ok().json(json!(g.view_for(Some(player_id)))),
+        Err(e) => error_response("Failed to add the bot", e),
+    }
+}
 
-    let lobbies = LobbyList{ lobbies: game_controller.get_all_lobbies() };
+#[get("/games/lobbies")]
+async fn get_lobbies(shared_data: web::Data<AppData>) -> impl Responder {
+    let lobbies = LobbyList{ lobbies: shared_data.game_controller.get_all_lobbies().into_iter().map(|lobby| lobby.view_for(None)).collect() };
     HttpResponse::Ok().json(json!(lobbies))
 }
 
@@ -207,14 +621,364 @@ async fn get_situation_cards() -> impl Responder {
     HttpResponse::Ok().json(json!(situation_card_list_wrapper()))
 }
 
+#[get("/resources/rules")]
+async fn get_rules(shared_data: web::Data<AppData>) -> impl Responder {
+    HttpResponse::Ok().json(json!(shared_data.game_controller.get_rules_info()))
+}
+
+#[get("/resources/maps")]
+async fn get_maps() -> impl Responder {
+    HttpResponse::Ok().json(json!(MapList { maps: map_names() }))
+}
+
 #[get("/check-in/{player_id}")]
-async fn player_check_in(player_id: web::Path<i32>, shared_data: web::Data<AppData>) -> impl Responder {
-    let Ok(mut game_controller) = shared_data.game_controller.lock() else {
-        return HttpResponse::InternalServerError().body("Failed to get amount of player IDs because could not lock game controller".to_string());
+async fn player_check_in(req: HttpRequest, player_id: web::Path<i32>, shared_data: web::Data<AppData>) -> impl Responder {
+    let player_id = player_id.into_inner();
+    if let Err(e) = authenticate(&req, &shared_data, player_id) {
+        return error_response("Failed to check in", e);
+    }
+    let result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.update_check_in_and_remove_inactive(player_id)).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to check in", e),
     };
-    let result = game_controller.update_check_in_and_remove_inactive(*player_id);
+    push_updated_games(&shared_data).await;
     match result {
         Ok(_) => HttpResponse::Ok().body(""),
-        Err(e) => HttpResponse::InternalServerError().body(e),
+        Err(e) => error_response("Failed to check in", e),
+    }
+}
+
+#[post("/games/game/{game_id}/reconnect/{player_id}")]
+async fn reconnect_to_game(path: web::Path<(i32, i32)>, reconnect_info: web::Json<ReconnectInfo>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    let reconnect_code = reconnect_info.into_inner().reconnect_code;
+    let credentials_result = match run_on_game_controller(&shared_data, move |game_controller| game_controller.reconnect_player(game_id, player_id, Some(&reconnect_code))).await {
+        Ok(result) => result,
+        Err(e) => return error_response("Failed to reconnect to the game", e),
+    };
+    push_updated_games(&shared_data).await;
+    match credentials_result {
+        Ok(credentials) => HttpResponse::Ok().json(json!(credentials)),
+        Err(e) => error_response("Failed to reconnect to the game", e),
+    }
+}
+
+#[get("/games/game/{game_id}/subscribe/{player_id}")]
+async fn subscribe_to_game(req: HttpRequest, body: web::Payload, path: web::Path<(i32, i32)>, query: web::Query<SubscriptionQuery>, shared_data: web::Data<AppData>) -> impl Responder {
+    let (game_id, player_id) = path.into_inner();
+    let (token, token_is_protocol) = subscription_token(&req, &query);
+    if let Err(e) = shared_data.game_controller.authenticate(player_id, token.as_deref()) {
+        return error_response("Failed to subscribe to the game", e);
+    }
+    match shared_data.game_controller.get_game_by_id(game_id) {
+        Ok(game) => {
+            if !game.can_be_followed_by(player_id) {
+                return error_response("Failed to subscribe to the game", GameError::Forbidden(format!("The player with id {} is not a player or spectator in the game", player_id)));
+            }
+        }
+        Err(e) => return error_response("Failed to subscribe to the game", e),
+    }
+
+    let (mut response, session, message_stream) = match actix_ws::handle(&req, body) {
+        Ok(handle) => handle,
+        Err(e) => return error_response("Failed to open the WebSocket", GameError::Internal(e.to_string())),
+    };
+    // A browser closes the WebSocket if the server does not choose one of the subprotocols it asked for.
+    if token_is_protocol {
+        response.headers_mut().insert(header::SEC_WEBSOCKET_PROTOCOL, header::HeaderValue::from_static(WEBSOCKET_TOKEN_PROTOCOL));
+    }
+
+    match shared_data.game_subscribers.lock() {
+        Ok(mut game_subscribers) => {
+            game_subscribers.retain(|s| s.game_id != game_id || s.player_id != player_id);
+            game_subscribers.push(GameSubscriber { game_id, player_id, session: session.clone() });
+        }
+        Err(_) => return error_response("Failed to subscribe to the game", GameError::Internal("Could not lock the game subscribers".to_string())),
+    }
+    actix_web::rt::spawn(handle_game_subscription(shared_data.clone(), game_id, player_id, token, session, message_stream));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, pin::Pin};
+
+    use actix_http::h1;
+    use actix_web::{body::MessageBody, dev::Payload, http::StatusCode, test, web::Bytes};
+    use game_core::{game_data::{custom_types::ErrorData, enums::{game_error::InvalidInputReason, player_input_type::PlayerInputType}, structs::player_credentials::PlayerCredentials}, game_storage::GameStorage};
+    use logging::logger::LogLevel;
+
+    use super::*;
+
+    /// A storage that does not store the games, since the tests do not restart the server.
+    struct NoStorage;
+
+    impl GameStorage for NoStorage {
+        fn save_game(&self, _game: &GameState) -> Result<(), ErrorData> {
+            Ok(())
+        }
+
+        fn remove_game(&self, _game_id: GameID) -> Result<(), ErrorData> {
+            Ok(())
+        }
+
+        fn load_games(&self) -> Result<Vec<Result<GameState, ErrorData>>, ErrorData> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn app_data() -> web::Data<AppData> {
+        let logger = Arc::new(RwLock::new(ThresholdLogger::new(LogLevel::Ignore, LogLevel::Ignore)));
+        web::Data::new(AppData {
+            game_controller: GameController::new(logger, Box::new(GameRuleChecker::new()), Box::new(NoStorage)),
+            game_subscribers: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Creates a game with a host and returns the id of the game and the credentials of the host.
+    fn create_game(shared_data: &web::Data<AppData>) -> (GameID, PlayerCredentials) {
+        let game_controller = &shared_data.game_controller;
+        let host = game_controller.generate_player_credentials().unwrap();
+        let game = game_controller.create_new_game(NewGameInfo { host: Player::new(host.player_id, "Host".to_string()), name: "Game".to_string(), map_name: None, rule_set: Default::default(), settings: Default::default() }).unwrap();
+        (game.id, host)
+    }
+
+    /// Returns a request that opens a WebSocket to the subscribe endpoint with the given path and query.
+    fn websocket_request(path_and_query: &str) -> test::TestRequest {
+        test::TestRequest::get()
+            .uri(path_and_query)
+            .insert_header(("upgrade", "websocket"))
+            .insert_header(("connection", "upgrade"))
+            .insert_header(("sec-websocket-version", "13"))
+            .insert_header(("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="))
+    }
+
+    /// Opens a WebSocket to the subscribe endpoint with the token of the player, where the client sends the given frames, and returns the response of the server. The WebSocket is kept open by the client for as long as the second value is kept.
+    async fn subscribe(shared_data: &web::Data<AppData>, game_id: GameID, player: &PlayerCredentials, client_frames: &[Bytes]) -> (actix_web::dev::ServiceResponse<impl MessageBody>, impl Sized) {
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+        let (mut client_messages, payload) = h1::Payload::create(false);
+        for frame in client_frames {
+            client_messages.feed_data(frame.clone());
+        }
+        let (request, _) = websocket_request(&format!("/games/game/{}/subscribe/{}", game_id, player.player_id))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", player.token)))
+            .to_request()
+            .replace_payload(Payload::from(payload));
+        (test::call_service(&app, request).await, client_messages)
+    }
+
+    /// Reads the game sent in the next text frame from the server. The frames from the server are not masked, so the payload comes right after the length.
+    async fn next_game<B: MessageBody + Unpin>(body: &mut B) -> GameState {
+        let mut frames = Vec::new();
+        loop {
+            if let Some((opcode, payload, frame_length)) = read_frame(&frames) {
+                frames.drain(..frame_length);
+                if opcode == 0x1 {
+                    return serde_json::from_slice(&payload).expect("The server sends games");
+                }
+                continue;
+            }
+            match poll_fn(|cx| Pin::new(&mut *body).poll_next(cx)).await {
+                Some(Ok(bytes)) => frames.extend_from_slice(&bytes),
+                _ => panic!("The WebSocket was closed before the server sent a game"),
+            }
+        }
+    }
+
+    fn read_frame(frames: &[u8]) -> Option<(u8, Vec<u8>, usize)> {
+        let (opcode, length_byte) = (frames.first()? & 0x0f, frames.get(1)? & 0x7f);
+        let (length, header_length) = match length_byte {
+            126 => (u16::from_be_bytes([*frames.get(2)?, *frames.get(3)?]) as usize, 4),
+            127 => (u64::from_be_bytes(frames.get(2..10)?.try_into().ok()?) as usize, 10),
+            length => (length as usize, 2),
+        };
+        let payload = frames.get(header_length..header_length + length)?;
+        Some((opcode, payload.to_vec(), header_length + length))
+    }
+
+    /// Returns a close frame from the client. Frames from the client have to be masked, and a mask of zeroes leaves the payload as it is.
+    fn client_close_frame() -> Bytes {
+        Bytes::from_static(&[0x88, 0x80, 0, 0, 0, 0])
+    }
+
+    #[actix_web::test]
+    async fn subscriber_gets_the_game_and_every_update_of_it() {
+        let shared_data = app_data();
+        let (game_id, host) = create_game(&shared_data);
+        let (response, _client) = subscribe(&shared_data, game_id, &host, &[]).await;
+        assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+        let mut body = response.into_body();
+
+        assert_eq!(next_game(&mut body).await.players.len(), 1);
+        {
+            let game_controller = &shared_data.game_controller;
+            let player_id = game_controller.generate_player_id().unwrap();
+            game_controller.join_game(game_id, Player::new(player_id, "Player".to_string())).unwrap();
+        }
+        push_updated_games(&shared_data).await;
+
+        let game = next_game(&mut body).await;
+        assert_eq!(game.id, game_id);
+        assert_eq!(game.players.len(), 2);
+    }
+
+    #[actix_web::test]
+    async fn player_can_not_subscribe_to_a_game_they_are_not_in() {
+        let shared_data = app_data();
+        let (game_id, _) = create_game(&shared_data);
+        let other_player = shared_data.game_controller.generate_player_credentials().unwrap();
+
+        let (response, _client) = subscribe(&shared_data, game_id, &other_player, &[]).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(shared_data.game_subscribers.lock().unwrap().is_empty());
+    }
+
+    #[actix_web::test]
+    async fn subscriber_is_removed_when_the_client_closes_the_websocket() {
+        let shared_data = app_data();
+        let (game_id, host) = create_game(&shared_data);
+        let (response, _client) = subscribe(&shared_data, game_id, &host, &[client_close_frame()]).await;
+        assert_eq!(shared_data.game_subscribers.lock().unwrap().len(), 1);
+        let mut body = response.into_body();
+
+        next_game(&mut body).await;
+        while poll_fn(|cx| Pin::new(&mut body).poll_next(cx)).await.is_some() {}
+
+        assert!(shared_data.game_subscribers.lock().unwrap().is_empty());
     }
-}
\ No newline at end of file
+
+    async fn error_body(response: HttpResponse) -> ErrorResponse {
+        let bytes = actix_web::body::to_bytes(response.into_body()).await.unwrap_or_default();
+        serde_json::from_slice(&bytes).expect("The server sends an error response")
+    }
+
+    #[actix_web::test]
+    async fn game_errors_get_the_status_code_and_error_code_of_their_kind() {
+        let errors = [
+            (GameError::NotFound("Missing".to_string()), StatusCode::NOT_FOUND, "not_found"),
+            (GameError::Unauthorized("No token".to_string()), StatusCode::UNAUTHORIZED, "unauthorized"),
+            (GameError::Forbidden("Not allowed".to_string()), StatusCode::FORBIDDEN, "forbidden"),
+            (GameError::NotYourTurn("Wait".to_string()), StatusCode::FORBIDDEN, "not_your_turn"),
+            (GameError::InvalidInput(InvalidInputReason::NotEnoughMoves, "No moves".to_string()), StatusCode::BAD_REQUEST, "invalid_input.not_enough_moves"),
+            (GameError::Conflict("Started".to_string()), StatusCode::CONFLICT, "conflict"),
+            (GameError::Internal("Broken".to_string()), StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        ];
+
+        for (error, status, code) in errors {
+            let message = error.message().to_string();
+            let response = error_response("Failed", error);
+
+            assert_eq!(response.status(), status);
+            let body = error_body(response).await;
+            assert_eq!(body.code, code);
+            assert_eq!(body.message, format!("Failed because: {}", message));
+        }
+    }
+
+    fn next_turn_input(player_id: PlayerID, game_id: GameID) -> PlayerInput {
+        PlayerInput {
+            player_id,
+            game_id,
+            input_type: PlayerInputType::NextTurn,
+            related_role: None,
+            related_node_id: None,
+            district_modifier: None,
+            situation_card_id: None,
+            edge_modifier: None,
+            related_bool: None,
+            related_path: None,
+            vehicle_id: 0,
+        }
+    }
+
+    #[actix_web::test]
+    async fn input_to_a_game_that_does_not_exist_is_not_found() {
+        let shared_data = app_data();
+        let (_, host) = create_game(&shared_data);
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+
+        let request = test::TestRequest::post()
+            .uri("/games/input")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", host.token)))
+            .set_json(next_turn_input(host.player_id, 200))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: ErrorResponse = test::read_body_json(response).await;
+        assert_eq!(body.code, "not_found");
+    }
+
+    #[actix_web::test]
+    async fn input_without_the_token_of_the_player_is_unauthorized() {
+        let shared_data = app_data();
+        let (game_id, host) = create_game(&shared_data);
+        let other_player = shared_data.game_controller.generate_player_credentials().unwrap();
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+
+        let without_token = test::TestRequest::post().uri("/games/input").set_json(next_turn_input(host.player_id, game_id)).to_request();
+        let with_other_token = test::TestRequest::post()
+            .uri("/games/input")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", other_player.token)))
+            .set_json(next_turn_input(host.player_id, game_id))
+            .to_request();
+
+        assert_eq!(test::call_service(&app, without_token).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(test::call_service(&app, with_other_token).await.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn spectator_can_subscribe_to_the_game_they_spectate() {
+        let shared_data = app_data();
+        let (game_id, _) = create_game(&shared_data);
+        let spectator = shared_data.game_controller.generate_player_credentials().unwrap();
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+
+        let spectate = test::TestRequest::post()
+            .uri(&format!("/games/join/{}/spectator", game_id))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", spectator.token)))
+            .set_json(Spectator::new(spectator.player_id, "Spectator".to_string()))
+            .to_request();
+        let game: GameState = test::call_and_read_body_json(&app, spectate).await;
+        let (response, _client) = subscribe(&shared_data, game_id, &spectator, &[]).await;
+
+        assert_eq!(game.players.len(), 1);
+        assert_eq!(game.spectators, vec![Spectator::new(spectator.player_id, "Spectator".to_string())]);
+        assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+    }
+
+    #[actix_web::test]
+    async fn subscription_needs_the_token_which_can_be_given_in_the_query_or_as_a_subprotocol() {
+        let shared_data = app_data();
+        let (game_id, host) = create_game(&shared_data);
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+        let path = format!("/games/game/{}/subscribe/{}", game_id, host.player_id);
+
+        let without_token = test::call_service(&app, websocket_request(&path).to_request()).await;
+        let with_query_token = test::call_service(&app, websocket_request(&format!("{}?token={}", path, host.token)).to_request()).await;
+        let with_protocol_token = test::call_service(
+            &app,
+            websocket_request(&path).insert_header((header::SEC_WEBSOCKET_PROTOCOL, format!("bearer, {}", host.token))).to_request(),
+        )
+        .await;
+
+        assert_eq!(without_token.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(with_query_token.status(), StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(with_protocol_token.status(), StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(with_protocol_token.headers().get(header::SEC_WEBSOCKET_PROTOCOL).unwrap(), WEBSOCKET_TOKEN_PROTOCOL);
+    }
+
+    #[actix_web::test]
+    async fn routes_of_a_player_need_the_token_of_the_player() {
+        let shared_data = app_data();
+        let (game_id, host) = create_game(&shared_data);
+        let app = test::init_service(server_app_with_data!(shared_data)).await;
+
+        let reachable_nodes = test::TestRequest::get().uri(&format!("/games/game/{}/reachable/{}", game_id, host.player_id)).to_request();
+        let cheapest_route = test::TestRequest::get().uri(&format!("/games/game/{}/route/{}/1", game_id, host.player_id)).to_request();
+
+        assert_eq!(test::call_service(&app, reachable_nodes).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(test::call_service(&app, cheapest_route).await.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use game_core::{
+    game_storage::GameStorage,
+    game_data::{structs::{gamestate::GameState, game_snapshot::GameSnapshot}, custom_types::{ErrorData, GameID}},
+};
+
+const GAME_FILE_PREFIX: &str = "game_";
+const GAME_FILE_EXTENSION: &str = "json";
+
+/// This struct contains an implementation of the GameStorage trait.
+/// It stores every game as a [`GameSnapshot`] in its own JSON file in the given folder.
+/// 
+/// [`GameSnapshot`]: ../../game_core/game_data/structs/game_snapshot/struct.GameSnapshot.html
+pub struct FileGameStorage {
+    folder: PathBuf,
+}
+
+impl FileGameStorage {
+    /// Creates a new FileGameStorage that stores the games in the given folder. The folder is created when the first game is stored.
+    #[must_use]
+    pub const fn new(folder: PathBuf) -> Self {
+        Self { folder }
+    }
+
+    /// Creates a new FileGameStorage that stores the games in a folder with the given name, placed in the same folder as the executable. Returns an error if the folder of the executable could not be found.
+    pub fn new_in_executable_folder(folder_name: &str) -> Result<Self, String> {
+        match env::current_exe() {
+            Ok(path) => match path.parent() {
+                Some(exe_folder) => Ok(Self::new(Path::new(exe_folder).join(folder_name))),
+                None => Err("Failed to get path of the folder the executable is in.".to_string()),
+            },
+            Err(_) => Err("Failed to get the path to the executable.".to_string()),
+        }
+    }
+
+    fn file_path_for_game(&self, game_id: GameID) -> PathBuf {
+        self.folder
+            .join(format!("{GAME_FILE_PREFIX}{game_id}.{GAME_FILE_EXTENSION}"))
+    }
+
+    fn is_game_file(path: &Path) -> bool {
+        let has_extension = matches!(path.extension(), Some(extension) if extension == GAME_FILE_EXTENSION);
+        let has_prefix = matches!(path.file_name(), Some(name) if name.to_string_lossy().starts_with(GAME_FILE_PREFIX));
+        has_extension && has_prefix
+    }
+
+    fn load_game_from_file(path: &Path) -> Result<GameState, ErrorData> {
+        let contents = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => return Err(format!("Failed to read the game file {} because: {e}", path.display())),
+        };
+        let snapshot: GameSnapshot = match serde_json::from_str(&contents) {
+            Ok(s) => s,
+            Err(e) => return Err(format!("Failed to parse the game file {} because: {e}", path.display())),
+        };
+        match snapshot.into_game() {
+            Ok(game) => Ok(game),
+            Err(e) => Err(format!("Failed to restore the game in the file {} because: {e}", path.display())),
+        }
+    }
+}
+
+impl GameStorage for FileGameStorage {
+    /// Writes the game to a temporary file first and then moves it in place, so that a crash while writing does not corrupt the earlier stored version of the game.
+    fn save_game(&self, game: &GameState) -> Result<(), ErrorData> {
+        if let Err(e) = fs::create_dir_all(&self.folder) {
+            return Err(format!("Failed to create the folder {} because: {e}", self.folder.display()));
+        }
+        let contents = match serde_json::to_string(&GameSnapshot::from_game(game)) {
+            Ok(c) => c,
+            Err(e) => return Err(format!("Failed to serialize the game with id {} because: {e}", game.id)),
+        };
+        let file_path = self.file_path_for_game(game.id);
+        let temporary_file_path = file_path.with_extension("tmp");
+        let write_result = File::create(&temporary_file_path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()));
+        if let Err(e) = write_result {
+            return Err(format!("Failed to write the file {} because: {e}", temporary_file_path.display()));
+        }
+        match fs::rename(&temporary_file_path, &file_path) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to move the file {} to {} because: {e}", temporary_file_path.display(), file_path.display())),
+        }
+    }
+
+    fn remove_game(&self, game_id: GameID) -> Result<(), ErrorData> {
+        let file_path = self.file_path_for_game(game_id);
+        if !file_path.exists() {
+            return Ok(());
+        }
+        match fs::remove_file(&file_path) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to remove the file {} because: {e}", file_path.display())),
+        }
+    }
+
+    fn load_games(&self) -> Result<Vec<Result<GameState, ErrorData>>, ErrorData> {
+        if !self.folder.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = match fs::read_dir(&self.folder) {
+            Ok(e) => e,
+            Err(e) => return Err(format!("Failed to read the folder {} because: {e}", self.folder.display())),
+        };
+        let mut games = Vec::new();
+        for entry in entries {
+            let path = match entry {
+                Ok(e) => e.path(),
+                Err(e) => {
+                    games.push(Err(format!("Failed to read an entry in the folder {} because: {e}", self.folder.display())));
+                    continue;
+                }
+            };
+            if !Self::is_game_file(&path) {
+                continue;
+            }
+            games.push(Self::load_game_from_file(&path));
+        }
+        Ok(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use game_core::game_data::{enums::district::District, structs::player::Player};
+
+    use super::*;
+
+    /// A storage in its own temporary folder, which is removed again when the storage is dropped.
+    struct TemporaryStorage {
+        storage: FileGameStorage,
+    }
+
+    impl TemporaryStorage {
+        fn new(test_name: &str) -> Self {
+            let folder = env::temp_dir().join(format!("file_game_storage_{}_{}", test_name, process::id()));
+            let _ = fs::remove_dir_all(&folder);
+            Self { storage: FileGameStorage::new(folder) }
+        }
+    }
+
+    impl Drop for TemporaryStorage {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.storage.folder);
+        }
+    }
+
+    fn stored_game(game_id: GameID) -> GameState {
+        let mut game = GameState::new(format!("Game {}", game_id), game_id);
+        game.assign_player_to_game(Player::new(7, "Host".to_string())).unwrap();
+        game.accessed_districts.push((0, District::Port));
+        game
+    }
+
+    #[test]
+    fn stored_game_is_loaded_with_the_data_that_is_not_sent_to_the_clients() {
+        let temporary = TemporaryStorage::new("round_trip");
+        temporary.storage.save_game(&stored_game(3)).unwrap();
+
+        let mut games = temporary.storage.load_games().unwrap();
+
+        assert_eq!(games.len(), 1);
+        let game = games.remove(0).unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(game.name, "Game 3");
+        assert_eq!(game.players[0].name, "Host");
+        assert_eq!(game.accessed_districts, vec![(0, District::Port)]);
+        assert!(!game.map.nodes.is_empty());
+    }
+
+    #[test]
+    fn saving_a_game_again_replaces_it() {
+        let temporary = TemporaryStorage::new("replace");
+        let mut game = stored_game(4);
+        temporary.storage.save_game(&game).unwrap();
+        game.name = "Renamed".to_string();
+        temporary.storage.save_game(&game).unwrap();
+
+        let games = temporary.storage.load_games().unwrap();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].as_ref().unwrap().name, "Renamed");
+    }
+
+    #[test]
+    fn game_files_that_can_not_be_loaded_are_returned_as_errors_and_other_files_are_ignored() {
+        let temporary = TemporaryStorage::new("bad_files");
+        temporary.storage.save_game(&stored_game(5)).unwrap();
+        fs::write(temporary.storage.folder.join("game_6.json"), "not a game").unwrap();
+        fs::write(temporary.storage.folder.join("notes.json"), "not a game either").unwrap();
+
+        let games = temporary.storage.load_games().unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games.iter().filter(|game| matches!(game, Ok(game) if game.id == 5)).count(), 1);
+        assert_eq!(games.iter().filter(|game| matches!(game, Err(e) if e.contains("game_6.json"))).count(), 1);
+    }
+
+    #[test]
+    fn removed_game_is_not_loaded() {
+        let temporary = TemporaryStorage::new("remove");
+        temporary.storage.save_game(&stored_game(8)).unwrap();
+
+        temporary.storage.remove_game(8).unwrap();
+        temporary.storage.remove_game(9).unwrap();
+
+        assert!(temporary.storage.load_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_folder_has_no_games() {
+        let temporary = TemporaryStorage::new("missing_folder");
+
+        assert!(temporary.storage.load_games().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,4 @@
+//! This library contains implementations of the GameStorage trait.
+
+/// This module contains a GameStorage implementation that stores every game as a JSON file.
+pub mod file_game_storage;